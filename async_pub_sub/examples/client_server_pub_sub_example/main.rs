@@ -4,14 +4,38 @@ mod data_producer;
 mod persistency;
 mod timer;
 
-use async_pub_sub::Result;
-use async_pub_sub_macros::routes;
+use async_pub_sub::{Result, Topology};
+use async_pub_sub_macros::topology;
 use cache::CacheService;
 use data_consumer::DataConsumerService;
 use data_producer::DataProducerService;
 use persistency::PersistencyService;
 use simplelog::Config;
-use timer::{CacheTimerNotification, TimerService};
+use timer::{
+    CacheTimerNotification, DataConsumerTimerNotification, DataProducerTimerNotification,
+    TimerService,
+};
+
+topology! {
+    /// The services of the application, connected at creation.
+    struct Services {
+        timer_service: TimerService = TimerService::new(),
+        #[subscriber]
+        cache_service: CacheService = CacheService::new(),
+        #[subscriber]
+        persistency_service: PersistencyService = PersistencyService::new(),
+        #[subscriber]
+        data_producer_service: DataProducerService = DataProducerService::new(),
+        #[subscriber]
+        data_consumer_service: DataConsumerService = DataConsumerService::new(),
+    }
+
+    [data_consumer_service, data_producer_service] -> cache_service,
+    timer_service -> cache_service: CacheTimerNotification,
+    cache_service -> persistency_service,
+    timer_service -> data_consumer_service: DataConsumerTimerNotification,
+    timer_service -> data_producer_service: DataProducerTimerNotification,
+}
 
 #[tokio::main]
 async fn main() -> Result<()> {
@@ -23,28 +47,11 @@ async fn main() -> Result<()> {
     )?;
 
     log::info!("Starting Main");
-    let mut timer_service = TimerService::new();
-    let mut cache_service = CacheService::new();
-    let mut persistency_service = PersistencyService::new();
-    let mut data_producer_service = DataProducerService::new();
-    let mut data_consumer_service = DataConsumerService::new();
-
-    routes!(
-        data_consumer_service -> cache_service,
-        data_producer_service -> cache_service,
-        timer_service -> cache_service: CacheTimerNotification,
-        cache_service -> persistency_service,
-        timer_service -> data_consumer_service,
-        timer_service -> data_producer_service,
-    )?;
+    let services = Services::new()?;
+
+    log::info!("Service graph:\n{}", Topology::current().to_dot());
 
-    tokio::join!(
-        cache_service.run(),
-        data_consumer_service.run(),
-        data_producer_service.run(),
-        persistency_service.run(),
-        timer_service.run(),
-    );
+    services.run().await;
 
     Ok(())
 }
@@ -0,0 +1,32 @@
+use bytes::Bytes;
+
+use crate::Result;
+
+/// A trait for types that can convert messages to and from bytes.
+///
+/// Codecs are used by serialized transports and by the
+/// [`EncodingPublisherLayer`](crate::EncodingPublisherLayer) and
+/// [`DecodingSubscriberLayer`](crate::DecodingSubscriberLayer) middlewares, so that the
+/// encoding of the messages is not tied to a given format.
+///
+/// # Type Parameters
+/// * `Message` - The type of message handled by the codec
+pub trait Codec<Message>: Send + Sync {
+    /// Encodes a message into bytes.
+    ///
+    /// # Arguments
+    /// * `message` - The message to encode
+    ///
+    /// # Returns
+    /// The encoded message, or an error if the message cannot be encoded
+    fn encode(&self, message: &Message) -> Result<Bytes>;
+
+    /// Decodes a message from bytes.
+    ///
+    /// # Arguments
+    /// * `bytes` - The encoded message
+    ///
+    /// # Returns
+    /// The decoded message, or an error if the bytes are not a valid message
+    fn decode(&self, bytes: &[u8]) -> Result<Message>;
+}
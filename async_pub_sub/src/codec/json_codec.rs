@@ -0,0 +1,24 @@
+use bytes::Bytes;
+use serde::{Serialize, de::DeserializeOwned};
+
+use crate::{Codec, Result};
+
+/// A codec encoding messages as JSON.
+///
+/// This is the codec used by default by the remote RPC client and server, and by the
+/// serialized transports of `tokio_implementations`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct JsonCodec;
+
+impl<Message> Codec<Message> for JsonCodec
+where
+    Message: Serialize + DeserializeOwned,
+{
+    fn encode(&self, message: &Message) -> Result<Bytes> {
+        Ok(serde_json::to_vec(message)?.into())
+    }
+
+    fn decode(&self, bytes: &[u8]) -> Result<Message> {
+        Ok(serde_json::from_slice(bytes)?)
+    }
+}
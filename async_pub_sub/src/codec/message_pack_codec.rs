@@ -0,0 +1,24 @@
+use bytes::Bytes;
+use serde::{Serialize, de::DeserializeOwned};
+
+use crate::{Codec, Result};
+
+/// A codec encoding messages as [MessagePack](https://msgpack.org).
+///
+/// Structs are encoded as maps, so that messages stay readable by other MessagePack
+/// implementations.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct MessagePackCodec;
+
+impl<Message> Codec<Message> for MessagePackCodec
+where
+    Message: Serialize + DeserializeOwned,
+{
+    fn encode(&self, message: &Message) -> Result<Bytes> {
+        Ok(rmp_serde::to_vec_named(message)?.into())
+    }
+
+    fn decode(&self, bytes: &[u8]) -> Result<Message> {
+        Ok(rmp_serde::from_slice(bytes)?)
+    }
+}
@@ -0,0 +1,15 @@
+mod codec_trait;
+#[cfg(feature = "serde")]
+mod json_codec;
+#[cfg(feature = "msgpack")]
+mod message_pack_codec;
+#[cfg(feature = "postcard")]
+mod postcard_codec;
+
+pub use codec_trait::Codec;
+#[cfg(feature = "serde")]
+pub use json_codec::JsonCodec;
+#[cfg(feature = "msgpack")]
+pub use message_pack_codec::MessagePackCodec;
+#[cfg(feature = "postcard")]
+pub use postcard_codec::PostcardCodec;
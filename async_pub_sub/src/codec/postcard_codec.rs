@@ -0,0 +1,22 @@
+use bytes::Bytes;
+use serde::{Serialize, de::DeserializeOwned};
+
+use crate::{Codec, Result};
+
+/// A codec encoding messages with the compact binary format of
+/// [postcard](https://docs.rs/postcard).
+#[derive(Clone, Copy, Debug, Default)]
+pub struct PostcardCodec;
+
+impl<Message> Codec<Message> for PostcardCodec
+where
+    Message: Serialize + DeserializeOwned,
+{
+    fn encode(&self, message: &Message) -> Result<Bytes> {
+        Ok(postcard::to_allocvec(message)?.into())
+    }
+
+    fn decode(&self, bytes: &[u8]) -> Result<Message> {
+        Ok(postcard::from_bytes(bytes)?)
+    }
+}
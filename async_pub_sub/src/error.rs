@@ -1,2 +1,110 @@
 pub type Result<T> = core::result::Result<T, Error>;
 pub type Error = Box<dyn std::error::Error>; // For early dev.
+
+/// The error returned by publishers when a message could not be delivered.
+///
+/// The undeliverable message is handed back, so that it can be retried, persisted or
+/// rerouted, see [`crate::DeadLetterPublisherLayer`]. Publishers return it boxed in an
+/// [`Error`], it can be recovered by downcasting:
+///
+/// ```
+/// use async_pub_sub::{PublishError, Publisher, PublisherImpl};
+///
+/// let mut publisher = PublisherImpl::new("publisher", 1);
+/// // the subscriber is gone, the message cannot be delivered
+/// drop(publisher.get_message_stream("subscriber").unwrap());
+///
+/// let error = futures::executor::block_on(Publisher::publish(&publisher, 42)).unwrap_err();
+/// let error = error.downcast::<PublishError<i32>>().unwrap();
+/// assert_eq!(error.into_message(), 42);
+/// ```
+pub struct PublishError<Message> {
+    publisher_name: &'static str,
+    reason: String,
+    message: Message,
+}
+
+impl<Message> PublishError<Message> {
+    /// Creates a new error carrying the undeliverable message.
+    ///
+    /// # Arguments
+    /// * `publisher_name` - The name of the publisher that failed to deliver the message
+    /// * `reason` - Why the message could not be delivered
+    /// * `message` - The undeliverable message
+    pub fn new(publisher_name: &'static str, reason: impl Into<String>, message: Message) -> Self {
+        Self {
+            publisher_name,
+            reason: reason.into(),
+            message,
+        }
+    }
+
+    /// Returns the name of the publisher that failed to deliver the message.
+    pub fn publisher_name(&self) -> &'static str {
+        self.publisher_name
+    }
+
+    /// Returns why the message could not be delivered.
+    pub fn reason(&self) -> &str {
+        &self.reason
+    }
+
+    /// Returns the undeliverable message.
+    pub fn message(&self) -> &Message {
+        &self.message
+    }
+
+    /// Returns the undeliverable message, consuming the error.
+    pub fn into_message(self) -> Message {
+        self.message
+    }
+
+    /// Converts the undeliverable message, keeping the publisher name and the reason.
+    ///
+    /// Used by the middlewares changing the type of the published messages, so that the
+    /// error carries the message as it was handed to them.
+    pub fn map_message<Other>(self, f: impl FnOnce(Message) -> Other) -> PublishError<Other> {
+        PublishError {
+            publisher_name: self.publisher_name,
+            reason: self.reason,
+            message: f(self.message),
+        }
+    }
+}
+
+/// Converts the undeliverable message carried by a publishing error, the other errors being
+/// returned as is.
+pub(crate) fn map_undeliverable<Message, Other>(
+    error: Error,
+    f: impl FnOnce(Message) -> Other,
+) -> Error
+where
+    Message: 'static,
+    Other: 'static,
+{
+    match error.downcast::<PublishError<Message>>() {
+        Ok(error) => Box::new(error.map_message(f)),
+        Err(error) => error,
+    }
+}
+
+impl<Message> std::fmt::Debug for PublishError<Message> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PublishError")
+            .field("publisher_name", &self.publisher_name)
+            .field("reason", &self.reason)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<Message> std::fmt::Display for PublishError<Message> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} publisher failed to deliver message ({})",
+            self.publisher_name, self.reason
+        )
+    }
+}
+
+impl<Message> std::error::Error for PublishError<Message> {}
@@ -1,26 +1,65 @@
 //! Asynchronous publish-subscribe library for Rust.
 #![doc = include_str!("../README.md")]
 
+mod codec;
 mod publisher;
+#[cfg(feature = "serde")]
+mod rpc;
+mod service;
 mod subscriber;
 mod utils;
 
 mod error;
 
-pub use error::{Error, Result};
+pub use error::{Error, PublishError, Result};
+
+pub use codec::Codec;
+#[cfg(feature = "serde")]
+pub use codec::JsonCodec;
+#[cfg(feature = "msgpack")]
+pub use codec::MessagePackCodec;
+#[cfg(feature = "postcard")]
+pub use codec::PostcardCodec;
 
 pub use publisher::{
-    DebuggingPublisherLayer, LoggingPublisherLayer, Publisher, PublisherBuilder, PublisherImpl,
-    PublisherWrapper, Request,
+    DeadLetterPublisherLayer, DebuggingPublisherLayer, EncodingPublisherLayer,
+    EnvelopePublisherLayer, LoggingPublisherLayer, MetricsPublisherLayer, Publisher,
+    PublisherBuilder, PublisherImpl, PublisherWrapper, RateLimitPolicy, RateLimitPublisherLayer,
+    Request,
 };
+#[cfg(feature = "serde")]
+pub use rpc::{RemoteRpcClient, RemoteRpcMessage, RemoteRpcServer, ReplySender};
+pub use service::{RestartStrategy, Service, ShutdownSignal, Supervisor};
 pub use subscriber::{
-    DebuggingSubscriberLayer, LoggingSubscriberLayer, Subscriber, SubscriberBuilder,
-    SubscriberImpl, SubscriberWrapper,
+    DebuggingSubscriberLayer, DecodingSubscriberLayer, DedupSubscriberLayer,
+    EnvelopeSubscriberLayer, LoggingSubscriberLayer, MetricsSubscriberLayer, RetryPolicy,
+    Subscriber, SubscriberBuilder, SubscriberImpl, SubscriberWrapper,
+};
+pub use utils::{
+    BatchForwarder, BatchTransform, Bus, BusPublisher, ChannelMetrics, DebounceForwarder,
+    DebounceTransform, DebuggingForwarderLayer, DurationStats, Either, Envelope, FilterForwarder,
+    FilterMapForwarder, FilterMapTransform, FilterTransform, Forwarder, ForwarderBuilder,
+    ForwarderImpl, IdentityLayer, Layer, LayerFn, MapForwarder, MapTransform, Metadata,
+    MetricsRegistry, Router, RouterOutput, SampleForwarder, SampleTransform, Stack,
+    StreamTransform, Subscription, ThenForwarder, ThenTransform, Timer, TopicBus, TopicMessage,
+    TopicPublisher, Topology, TransformForwarder, layer_fn,
+};
+#[cfg(feature = "tracing")]
+pub use {
+    publisher::TracingPublisherLayer,
+    subscriber::TracingSubscriberLayer,
+    utils::{Traced, TracingForwarderLayer},
 };
-pub use utils::{DebuggingForwarderLayer, Forwarder, ForwarderBuilder, ForwarderImpl, Layer};
 
 // Re-export futures for use in macros and client code
 pub use futures;
 
+// Re-export bytes as it is the payload type of serialized messages
+pub use bytes;
+
+// Re-export serde for use in macros
+#[cfg(feature = "serde")]
+pub use serde;
+
 #[cfg(feature = "macros")]
 pub use async_pub_sub_macros as macros;
@@ -5,6 +5,13 @@ mod publisher_trait;
 mod publisher_types;
 
 pub use publisher_impl::PublisherImpl;
-pub use publisher_middlewares::{DebuggingPublisherLayer, LoggingPublisherLayer, PublisherBuilder};
+pub(crate) use publisher_middlewares::encode_stream;
+pub use publisher_middlewares::{
+    DeadLetterPublisherLayer, DebuggingPublisherLayer, EncodingPublisherLayer,
+    EnvelopePublisherLayer, LoggingPublisherLayer, MetricsPublisherLayer, PublisherBuilder,
+    RateLimitPolicy, RateLimitPublisherLayer,
+};
+#[cfg(feature = "tracing")]
+pub use publisher_middlewares::TracingPublisherLayer;
 pub use publisher_trait::{Publisher, PublisherWrapper};
 pub use publisher_types::Request;
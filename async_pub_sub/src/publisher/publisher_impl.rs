@@ -1,7 +1,14 @@
-use futures::{FutureExt, SinkExt};
+use std::sync::Mutex;
+
+use futures::{
+    FutureExt, StreamExt,
+    channel::oneshot,
+    future::{self, Shared},
+    stream,
+};
 
 use super::Publisher;
-use crate::Result;
+use crate::{PublishError, Result};
 
 /// A concrete implementation of the Publisher trait that handles message distribution
 /// to a single subscriber.
@@ -17,6 +24,9 @@ use crate::Result;
 /// * `subscriber_name` - The name of the currently bound subscriber, if any
 /// * `sender` - The sending half of the message channel
 /// * `receiver` - The receiving half of the channel, available until a subscriber is bound
+/// * `drained` - Completes once the subscriber stream has ended or has been dropped
+/// * `drained_sender` - Moved into the subscriber stream, dropping it completes `drained`, or
+///   dropped on close if no subscriber was bound
 ///
 /// # Example
 ///
@@ -47,6 +57,8 @@ where
     subscriber_name: Option<&'static str>,
     sender: futures::channel::mpsc::Sender<Message>,
     receiver: Option<futures::channel::mpsc::Receiver<Message>>,
+    drained: Shared<oneshot::Receiver<()>>,
+    drained_sender: Mutex<Option<oneshot::Sender<()>>>,
 }
 
 impl<Message> PublisherImpl<Message>
@@ -65,11 +77,14 @@ where
     /// A new `PublisherImpl` instance
     pub fn new(name: &'static str, buffer_size: usize) -> Self {
         let (sender, receiver) = futures::channel::mpsc::channel(buffer_size);
+        let (drained_sender, drained) = oneshot::channel();
         Self {
             name,
             subscriber_name: None,
             sender,
             receiver: Some(receiver),
+            drained: drained.shared(),
+            drained_sender: Mutex::new(Some(drained_sender)),
         }
     }
 
@@ -81,11 +96,60 @@ where
     ///
     /// # Returns
     ///
-    /// A Result indicating whether the message was successfully sent
+    /// A Result indicating whether the message was successfully sent, the error being a
+    /// [`PublishError`] carrying the message when it could not be delivered
     pub async fn publish(&self, message: Message) -> Result<()> {
-        self.sender.clone().send(message).await?;
-        Ok(())
+        send(self.name, self.sender.clone(), message).await
+    }
+
+    /// Closes the publisher.
+    ///
+    /// The messages published afterwards, including the ones of the `publish` calls still
+    /// waiting for room in the channel, are rejected with a [`PublishError`]. The subscriber
+    /// receives the messages already queued, then sees the end of the stream.
+    pub fn close(&self) {
+        // closing any sender closes the whole channel, even for the clones of in-flight
+        // publish calls
+        self.sender.clone().close_channel();
+        // without a subscriber, there is no queue to drain
+        self.drained_sender
+            .lock()
+            .expect("publisher lock poisoned")
+            .take();
+    }
+
+    /// Returns whether the publisher has been closed, or its subscriber dropped.
+    pub fn is_closed(&self) -> bool {
+        self.sender.is_closed()
+    }
+
+    /// Waits until the subscriber has received all the messages published before the
+    /// publisher was closed, or has been dropped. Completes as soon as the publisher is closed
+    /// if no subscriber was bound.
+    pub async fn closed(&self) {
+        let _ = self.drained.clone().await;
+    }
+}
+
+/// Sends a message through the channel, handing it back in the error if the channel is closed.
+async fn send<Message>(
+    publisher_name: &'static str,
+    mut sender: futures::channel::mpsc::Sender<Message>,
+    message: Message,
+) -> Result<()>
+where
+    Message: Send + 'static,
+{
+    // waiting for a free slot before sending keeps the message if the channel is closed
+    if let Err(err) = future::poll_fn(|cx| sender.poll_ready(cx)).await {
+        let reason = format!("Failed to send message (err: {err})");
+        return Err(PublishError::new(publisher_name, reason, message).into());
     }
+
+    sender.try_send(message).map_err(|err| {
+        let reason = format!("Failed to send message (err: {err})");
+        PublishError::new(publisher_name, reason, err.into_inner()).into()
+    })
 }
 
 impl<Message> Publisher for PublisherImpl<Message>
@@ -107,16 +171,26 @@ where
     ///
     /// # Returns
     ///
-    /// A boxed future that resolves to a Result indicating success or failure
-    fn publish(&self, message: Self::Message) -> futures::future::BoxFuture<Result<()>> {
-        let mut sender = self.sender.clone();
-        async move {
-            sender
-                .send(message)
-                .await
-                .map_err(|err| format!("Failed to send message (err: {err})").into())
-        }
-        .boxed()
+    /// A boxed future that resolves to a Result indicating success or failure, the error
+    /// being a [`PublishError`] carrying the message when it could not be delivered
+    fn publish(&self, message: Self::Message) -> futures::future::BoxFuture<'_, Result<()>> {
+        send(self.name, self.sender.clone(), message).boxed()
+    }
+
+    /// Closes the publisher, its subscriber seeing the end of the stream once it has received
+    /// the messages already queued.
+    fn close(&self) {
+        PublisherImpl::close(self)
+    }
+
+    /// Returns whether the publisher has been closed, or its subscriber dropped.
+    fn is_closed(&self) -> bool {
+        PublisherImpl::is_closed(self)
+    }
+
+    /// Waits until the subscriber has drained the queue of the closed publisher.
+    fn closed(&self) -> futures::future::BoxFuture<'_, ()> {
+        PublisherImpl::closed(self).boxed()
     }
 
     /// Binds a subscriber to this publisher and returns the message stream.
@@ -150,6 +224,15 @@ where
 
         self.subscriber_name = Some(subscriber_name);
 
-        Ok(Box::pin(receiver))
+        // the sender is dropped once the stream ends or is dropped, completing `closed`
+        let drained_sender = self
+            .drained_sender
+            .get_mut()
+            .expect("publisher lock poisoned")
+            .take();
+        let end =
+            stream::once(async move { drop(drained_sender) }).filter_map(|()| future::ready(None));
+
+        Ok(Box::pin(receiver.chain(end)))
     }
 }
@@ -0,0 +1,126 @@
+use std::{pin::Pin, sync::Arc};
+
+use futures::{FutureExt, Stream, future::BoxFuture};
+
+use crate::{Error, Layer, PublishError, Publisher, Result};
+
+/// A publisher middleware layer rerouting the undeliverable messages to a dead-letter
+/// publisher.
+///
+/// When the wrapped publisher fails with a [`PublishError`], because its subscriber is gone
+/// or because an overflow policy such as [`crate::RateLimitPolicy::Reject`] rejected the
+/// message, the message is published with the dead-letter publisher instead. Other errors are
+/// returned as is. The middlewares changing the type of the messages below this layer, like
+/// [`crate::EnvelopePublisherLayer`] and [`crate::EncodingPublisherLayer`], hand the message
+/// back in the type it was published with.
+///
+/// The dead-letter publisher is shared by all the publishers wrapped by this layer, it should
+/// be routed to its subscriber before being handed to the layer.
+///
+/// # Type Parameters
+/// * `D` - The dead-letter publisher
+pub struct DeadLetterPublisherLayer<D> {
+    dead_letter: Arc<D>,
+}
+
+impl<D> DeadLetterPublisherLayer<D>
+where
+    D: Publisher,
+{
+    /// Creates a new layer rerouting the undeliverable messages to the given publisher.
+    pub fn new(dead_letter: D) -> Self {
+        Self {
+            dead_letter: Arc::new(dead_letter),
+        }
+    }
+}
+
+impl<P, D> Layer<P> for DeadLetterPublisherLayer<D>
+where
+    P: Publisher,
+    D: Publisher<Message = P::Message>,
+{
+    type LayerType = DeadLetterPublisher<P, D>;
+
+    fn layer(&self, publisher: P) -> Self::LayerType {
+        DeadLetterPublisher {
+            publisher,
+            dead_letter: self.dead_letter.clone(),
+        }
+    }
+}
+
+/// A publisher wrapper rerouting the undeliverable messages of an existing publisher to a
+/// dead-letter publisher.
+pub struct DeadLetterPublisher<P, D> {
+    /// The underlying publisher being wrapped
+    publisher: P,
+    /// The publisher receiving the undeliverable messages
+    dead_letter: Arc<D>,
+}
+
+impl<P, D> Publisher for DeadLetterPublisher<P, D>
+where
+    P: Publisher,
+    D: Publisher<Message = P::Message>,
+    Self: Sync,
+{
+    type Message = P::Message;
+
+    /// Returns the name of the underlying publisher
+    fn get_name(&self) -> &'static str {
+        self.publisher.get_name()
+    }
+
+    /// Publishes a message, rerouting it to the dead-letter publisher if it cannot be
+    /// delivered.
+    ///
+    /// Once rerouted, the result is the one of the dead-letter publisher.
+    fn publish(&self, message: Self::Message) -> BoxFuture<'_, Result<()>> {
+        async move {
+            let error = match self.publisher.publish(message).await {
+                Ok(()) => return Ok(()),
+                Err(err) => err,
+            };
+            let error = undeliverable::<Self::Message>(error)?;
+
+            log::warn!(
+                "[{}]: {}, rerouting message to {}",
+                self.publisher.get_name(),
+                error,
+                self.dead_letter.get_name()
+            );
+            self.dead_letter.publish(error.into_message()).await
+        }
+        .boxed()
+    }
+
+    fn close(&self) {
+        self.publisher.close()
+    }
+
+    fn is_closed(&self) -> bool {
+        self.publisher.is_closed()
+    }
+
+    fn closed(&self) -> BoxFuture<'_, ()> {
+        self.publisher.closed()
+    }
+
+    fn get_message_stream(
+        &mut self,
+        subscriber_name: &'static str,
+    ) -> Result<Pin<Box<dyn Stream<Item = Self::Message> + Send + Sync + 'static>>> {
+        self.publisher.get_message_stream(subscriber_name)
+    }
+}
+
+/// Recovers the undeliverable message from a publishing error, if any.
+fn undeliverable<Message>(error: Error) -> Result<PublishError<Message>>
+where
+    Message: 'static,
+{
+    error
+        .downcast::<PublishError<Message>>()
+        .map(|error| *error)
+}
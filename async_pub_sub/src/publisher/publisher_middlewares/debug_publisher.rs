@@ -55,7 +55,7 @@ where
     /// * `message` - The message to publish
     ///
     /// Logs the message in the format: "[publisher_name] -> [subscriber_name]: message_debug_format"
-    fn publish(&self, message: Self::Message) -> BoxFuture<Result<()>> {
+    fn publish(&self, message: Self::Message) -> BoxFuture<'_, Result<()>> {
         async move {
             let message_str = format!("{:?}", &message);
             let result = self.publisher.publish(message).await;
@@ -71,6 +71,18 @@ where
         .boxed()
     }
 
+    fn close(&self) {
+        self.publisher.close()
+    }
+
+    fn is_closed(&self) -> bool {
+        self.publisher.is_closed()
+    }
+
+    fn closed(&self) -> BoxFuture<'_, ()> {
+        self.publisher.closed()
+    }
+
     /// Gets a message stream for the given subscriber name.
     /// Stores the subscriber name for use in debug logging.
     ///
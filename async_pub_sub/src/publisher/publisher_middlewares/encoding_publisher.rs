@@ -0,0 +1,131 @@
+use std::pin::Pin;
+
+use bytes::Bytes;
+use futures::{FutureExt, Stream, StreamExt, future::BoxFuture};
+
+use crate::{Codec, Layer, Publisher, Result, error::map_undeliverable};
+
+/// A publisher middleware layer that exposes a publisher as a publisher of encoded messages.
+/// The messages of the wrapped publisher are encoded with the given codec before being
+/// streamed to the subscriber, so that they can be sent over a serialized transport.
+pub struct EncodingPublisherLayer<C> {
+    codec: C,
+}
+
+impl<C> EncodingPublisherLayer<C> {
+    /// Creates a new layer encoding the messages with the given codec.
+    pub fn new(codec: C) -> Self {
+        Self { codec }
+    }
+}
+
+impl<P, C> Layer<P> for EncodingPublisherLayer<C>
+where
+    P: Publisher,
+    C: Codec<P::Message> + Clone + 'static,
+{
+    type LayerType = EncodingPublisher<P, C>;
+
+    fn layer(&self, publisher: P) -> Self::LayerType {
+        EncodingPublisher {
+            publisher,
+            codec: self.codec.clone(),
+        }
+    }
+}
+
+/// A publisher wrapper publishing the encoded messages of an existing publisher.
+///
+/// Messages published through this wrapper are decoded before being handed to the wrapped
+/// publisher, and the messages of the wrapped publisher are encoded before reaching the
+/// subscriber. Messages that cannot be encoded are logged and dropped.
+pub struct EncodingPublisher<P, C> {
+    /// The underlying publisher being wrapped
+    publisher: P,
+    /// The codec used to encode and decode the messages
+    codec: C,
+}
+
+impl<P, C> Publisher for EncodingPublisher<P, C>
+where
+    P: Publisher,
+    C: Codec<P::Message> + Clone + 'static,
+    Self: Sync,
+{
+    type Message = Bytes;
+
+    fn get_name(&self) -> &'static str {
+        self.publisher.get_name()
+    }
+
+    /// Decodes the message and publishes it with the wrapped publisher
+    ///
+    /// A [`crate::PublishError`] of the wrapped publisher is returned with the encoded
+    /// message.
+    fn publish(&self, message: Self::Message) -> BoxFuture<'_, Result<()>> {
+        async move {
+            let decoded = self.codec.decode(&message)?;
+            self.publisher
+                .publish(decoded)
+                .await
+                .map_err(|err| map_undeliverable(err, |_: P::Message| message))
+        }
+        .boxed()
+    }
+
+    fn close(&self) {
+        self.publisher.close()
+    }
+
+    fn is_closed(&self) -> bool {
+        self.publisher.is_closed()
+    }
+
+    fn closed(&self) -> BoxFuture<'_, ()> {
+        self.publisher.closed()
+    }
+
+    /// Returns the message stream of the wrapped publisher, with the messages encoded
+    fn get_message_stream(
+        &mut self,
+        subscriber_name: &'static str,
+    ) -> Result<Pin<Box<dyn Stream<Item = Self::Message> + Send + Sync + 'static>>> {
+        let stream = self.publisher.get_message_stream(subscriber_name)?;
+        Ok(encode_stream(
+            self.publisher.get_name(),
+            subscriber_name,
+            stream,
+            self.codec.clone(),
+        ))
+    }
+}
+
+/// Encodes the messages of a stream, logging and dropping the ones that cannot be encoded.
+pub(crate) fn encode_stream<Message, C>(
+    publisher_name: &'static str,
+    subscriber_name: &'static str,
+    stream: Pin<Box<dyn Stream<Item = Message> + Send + Sync + 'static>>,
+    codec: C,
+) -> Pin<Box<dyn Stream<Item = Bytes> + Send + Sync + 'static>>
+where
+    Message: Send + 'static,
+    C: Codec<Message> + 'static,
+{
+    let encoded_stream = stream.filter_map(move |message| {
+        let encoded = match codec.encode(&message) {
+            Ok(bytes) => Some(bytes),
+            Err(err) => {
+                log::warn!(
+                    "[{}] -> [{}]: failed to encode message, dropping it (err: {})",
+                    publisher_name,
+                    subscriber_name,
+                    err
+                );
+                None
+            }
+        };
+        futures::future::ready(encoded)
+    });
+
+    Box::pin(encoded_stream)
+}
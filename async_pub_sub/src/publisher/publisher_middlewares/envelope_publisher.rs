@@ -0,0 +1,103 @@
+use std::pin::Pin;
+
+use futures::{FutureExt, Stream, StreamExt, future::BoxFuture};
+
+use crate::{Envelope, Layer, Publisher, Result, error::map_undeliverable};
+
+/// A publisher middleware layer wrapping the published messages in an [`Envelope`].
+///
+/// The layer sits at the edge of a middleware stack: the messages published through it are
+/// wrapped in an envelope whose source is the publisher name, the middlewares below it handle
+/// the envelopes, and the messages are unwrapped again before reaching the subscriber.
+///
+/// # Example
+///
+/// ```
+/// use async_pub_sub::{
+///     Envelope, EnvelopePublisherLayer, Publisher, PublisherBuilder, PublisherImpl,
+///     Subscriber, SubscriberImpl,
+/// };
+///
+/// # #[tokio::main]
+/// # async fn main() -> async_pub_sub::Result<()> {
+/// let mut publisher = PublisherBuilder::new()
+///     .layer(EnvelopePublisherLayer)
+///     .publisher(PublisherImpl::<Envelope<i32>>::new("publisher", 1));
+///
+/// let mut subscriber = SubscriberImpl::new("subscriber");
+/// subscriber.subscribe_to(&mut publisher)?;
+///
+/// publisher.publish(42).await?;
+/// assert_eq!(subscriber.receive().await, 42);
+/// # Ok(())
+/// # }
+/// ```
+pub struct EnvelopePublisherLayer;
+
+impl<P, Message> Layer<P> for EnvelopePublisherLayer
+where
+    P: Publisher<Message = Envelope<Message>> + Sync,
+    Message: Send + 'static,
+{
+    type LayerType = EnvelopePublisher<P>;
+
+    fn layer(&self, publisher: P) -> Self::LayerType {
+        EnvelopePublisher { publisher }
+    }
+}
+
+/// A publisher wrapper exposing a publisher of envelopes as a publisher of bare messages.
+pub struct EnvelopePublisher<P> {
+    /// The underlying publisher being wrapped
+    publisher: P,
+}
+
+impl<P, Message> Publisher for EnvelopePublisher<P>
+where
+    P: Publisher<Message = Envelope<Message>> + Sync,
+    Message: Send + 'static,
+{
+    type Message = Message;
+
+    fn get_name(&self) -> &'static str {
+        self.publisher.get_name()
+    }
+
+    /// Wraps the message in an envelope and publishes it with the wrapped publisher
+    ///
+    /// A [`crate::PublishError`] of the wrapped publisher is returned with the message
+    /// unwrapped from its envelope.
+    fn publish(&self, message: Self::Message) -> BoxFuture<'_, Result<()>> {
+        let mut envelope = Envelope::new(message);
+        envelope
+            .metadata_mut()
+            .set_source(self.publisher.get_name());
+        self.publisher
+            .publish(envelope)
+            .map(|result| {
+                result.map_err(|err| map_undeliverable(err, Envelope::<Message>::into_inner))
+            })
+            .boxed()
+    }
+
+    fn close(&self) {
+        self.publisher.close()
+    }
+
+    fn is_closed(&self) -> bool {
+        self.publisher.is_closed()
+    }
+
+    fn closed(&self) -> BoxFuture<'_, ()> {
+        self.publisher.closed()
+    }
+
+    /// Returns the message stream of the wrapped publisher, with the messages unwrapped
+    fn get_message_stream(
+        &mut self,
+        subscriber_name: &'static str,
+    ) -> Result<Pin<Box<dyn Stream<Item = Self::Message> + Send + Sync + 'static>>> {
+        let stream = self.publisher.get_message_stream(subscriber_name)?;
+        Ok(Box::pin(stream.map(Envelope::into_inner)))
+    }
+}
@@ -51,7 +51,7 @@ where
     }
 
     /// Publishes a message and logs the operation with source publisher and destination subscriber
-    fn publish(&self, message: Self::Message) -> BoxFuture<Result<()>> {
+    fn publish(&self, message: Self::Message) -> BoxFuture<'_, Result<()>> {
         async move {
             let message_str = format!("{}", &message);
             let result = self.publisher.publish(message).await;
@@ -67,6 +67,18 @@ where
         .boxed()
     }
 
+    fn close(&self) {
+        self.publisher.close()
+    }
+
+    fn is_closed(&self) -> bool {
+        self.publisher.is_closed()
+    }
+
+    fn closed(&self) -> BoxFuture<'_, ()> {
+        self.publisher.closed()
+    }
+
     /// Sets up a message stream for a subscriber and stores the subscriber's name for logging
     fn get_message_stream(
         &mut self,
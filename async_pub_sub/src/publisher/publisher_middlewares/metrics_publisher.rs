@@ -0,0 +1,100 @@
+use std::pin::Pin;
+
+use futures::{FutureExt, Stream, StreamExt, future::BoxFuture};
+
+use crate::{Layer, MetricsRegistry, Publisher, Result};
+
+/// A publisher middleware layer recording the metrics of the published messages.
+///
+/// The published messages, the publish wait time, the queue depth and the latency of the
+/// messages are recorded in the [`MetricsRegistry`], labelled by the name of the publisher and
+/// of its subscriber.
+pub struct MetricsPublisherLayer {
+    registry: MetricsRegistry,
+}
+
+impl MetricsPublisherLayer {
+    /// Creates a new layer recording the metrics in the given registry.
+    pub fn new(registry: MetricsRegistry) -> Self {
+        Self { registry }
+    }
+}
+
+impl<P> Layer<P> for MetricsPublisherLayer
+where
+    P: Publisher,
+{
+    type LayerType = MetricsPublisher<P>;
+
+    fn layer(&self, publisher: P) -> Self::LayerType {
+        MetricsPublisher {
+            index: self.registry.register_publisher(publisher.get_name()),
+            registry: self.registry.clone(),
+            publisher,
+        }
+    }
+}
+
+/// A publisher wrapper recording the metrics of the messages published by an existing
+/// publisher.
+pub struct MetricsPublisher<P> {
+    /// The underlying publisher being wrapped
+    publisher: P,
+    /// The registry recording the metrics
+    registry: MetricsRegistry,
+    /// The index of the publisher's channel in the registry
+    index: usize,
+}
+
+impl<P> Publisher for MetricsPublisher<P>
+where
+    P: Publisher,
+    Self: Sync,
+{
+    type Message = P::Message;
+
+    /// Returns the name of the underlying publisher
+    fn get_name(&self) -> &'static str {
+        self.publisher.get_name()
+    }
+
+    /// Publishes a message, recording how long the publication took
+    fn publish(&self, message: Self::Message) -> BoxFuture<'_, Result<()>> {
+        async move {
+            let started_at = self.registry.now();
+            let result = self.publisher.publish(message).await;
+            self.registry
+                .publish_completed(self.index, started_at, result.is_ok());
+            result
+        }
+        .boxed()
+    }
+
+    fn close(&self) {
+        self.publisher.close()
+    }
+
+    fn is_closed(&self) -> bool {
+        self.publisher.is_closed()
+    }
+
+    fn closed(&self) -> BoxFuture<'_, ()> {
+        self.publisher.closed()
+    }
+
+    /// Gets the message stream, recording the delivery of each message
+    fn get_message_stream(
+        &mut self,
+        subscriber_name: &'static str,
+    ) -> Result<Pin<Box<dyn Stream<Item = Self::Message> + Send + Sync + 'static>>> {
+        let stream = self.publisher.get_message_stream(subscriber_name)?;
+        self.registry.bind(self.index, subscriber_name);
+
+        let registry = self.registry.clone();
+        let index = self.index;
+        Ok(Box::pin(stream.map(move |message| {
+            registry.delivered(index);
+            message
+        })))
+    }
+}
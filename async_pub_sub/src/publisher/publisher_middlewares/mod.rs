@@ -1,7 +1,22 @@
+mod dead_letter_publisher;
 mod debug_publisher;
+mod encoding_publisher;
+mod envelope_publisher;
 mod logging_publisher;
+mod metrics_publisher;
 mod publisher_builder;
+mod rate_limit_publisher;
+#[cfg(feature = "tracing")]
+mod tracing_publisher;
 
+pub use dead_letter_publisher::DeadLetterPublisherLayer;
 pub use debug_publisher::DebuggingPublisherLayer;
+pub use encoding_publisher::EncodingPublisherLayer;
+pub(crate) use encoding_publisher::encode_stream;
+pub use envelope_publisher::EnvelopePublisherLayer;
 pub use logging_publisher::LoggingPublisherLayer;
+pub use metrics_publisher::MetricsPublisherLayer;
 pub use publisher_builder::PublisherBuilder;
+pub use rate_limit_publisher::{RateLimitPolicy, RateLimitPublisherLayer};
+#[cfg(feature = "tracing")]
+pub use tracing_publisher::TracingPublisherLayer;
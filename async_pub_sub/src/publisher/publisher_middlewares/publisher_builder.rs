@@ -1,4 +1,7 @@
-use crate::{utils::IdentityLayer, Layer, Publisher};
+use crate::{
+    utils::{IdentityLayer, Stack},
+    Layer, Publisher,
+};
 
 /// A builder pattern implementation for constructing a publisher with middleware layers.
 /// This struct allows for composing multiple middleware layers.
@@ -25,10 +28,15 @@ impl PublisherBuilder {
 impl<L> PublisherBuilder<L> {
     /// Adds a middleware layer to the builder.
     ///
+    /// The layers are nested in the order they are added: the first layer added is the
+    /// outermost one, and the last layer added directly wraps the publisher.
+    ///
     /// # Arguments
     /// * `layer` - The middleware layer to add
-    pub fn layer<NewLayer>(self, layer: NewLayer) -> PublisherBuilder<NewLayer> {
-        PublisherBuilder { layer }
+    pub fn layer<NewLayer>(self, layer: NewLayer) -> PublisherBuilder<Stack<NewLayer, L>> {
+        PublisherBuilder {
+            layer: Stack::new(layer, self.layer),
+        }
     }
 
     /// Wraps a publisher with the composed layers.
@@ -0,0 +1,222 @@
+use std::{
+    pin::Pin,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use futures::{FutureExt, Stream, future::BoxFuture};
+
+use crate::{Layer, PublishError, Publisher, Result, Timer};
+
+/// What a rate limited publisher does with a message published while the rate limit is
+/// exceeded.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RateLimitPolicy {
+    /// Wait until the message can be published
+    Delay,
+    /// Silently drop the message
+    Drop,
+    /// Return a [`PublishError`] carrying the message
+    Reject,
+}
+
+/// A publisher middleware layer limiting the rate at which messages are published.
+///
+/// The rate is enforced with a token bucket: up to `burst_size` messages can be published at
+/// once, then messages are published at `messages_per_second` on average. Messages exceeding
+/// the rate are handled according to the [`RateLimitPolicy`].
+///
+/// # Type Parameters
+/// * `T` - The timer used to measure time and wait
+pub struct RateLimitPublisherLayer<T> {
+    messages_per_second: f64,
+    burst_size: u32,
+    policy: RateLimitPolicy,
+    timer: T,
+}
+
+impl<T> RateLimitPublisherLayer<T>
+where
+    T: Timer + Clone,
+{
+    /// Creates a new rate limiting layer.
+    ///
+    /// # Arguments
+    /// * `messages_per_second` - The average number of messages published per second
+    /// * `burst_size` - The number of messages that can be published at once
+    /// * `policy` - What to do with the messages exceeding the rate
+    /// * `timer` - The timer of the async runtime
+    ///
+    /// # Panics
+    /// Panics if `messages_per_second` is not strictly positive or if `burst_size` is zero.
+    pub fn new(
+        messages_per_second: f64,
+        burst_size: u32,
+        policy: RateLimitPolicy,
+        timer: T,
+    ) -> Self {
+        assert!(
+            messages_per_second > 0.0,
+            "the rate limit must be strictly positive"
+        );
+        assert!(burst_size > 0, "the burst size must be strictly positive");
+
+        Self {
+            messages_per_second,
+            burst_size,
+            policy,
+            timer,
+        }
+    }
+}
+
+impl<P, T> Layer<P> for RateLimitPublisherLayer<T>
+where
+    P: Publisher,
+    T: Timer + Clone,
+{
+    type LayerType = RateLimitPublisher<P, T>;
+
+    fn layer(&self, publisher: P) -> Self::LayerType {
+        RateLimitPublisher {
+            publisher,
+            policy: self.policy,
+            bucket: Mutex::new(TokenBucket {
+                capacity: self.burst_size as f64,
+                refill_rate: self.messages_per_second,
+                tokens: self.burst_size as f64,
+                last_refill: self.timer.now(),
+            }),
+            timer: self.timer.clone(),
+        }
+    }
+}
+
+/// A publisher wrapper limiting the rate at which messages are published by an existing
+/// publisher.
+pub struct RateLimitPublisher<P, T> {
+    /// The underlying publisher being wrapped
+    publisher: P,
+    /// What to do with the messages exceeding the rate
+    policy: RateLimitPolicy,
+    /// The token bucket enforcing the rate
+    bucket: Mutex<TokenBucket>,
+    /// The timer used to measure time and wait
+    timer: T,
+}
+
+impl<P, T> Publisher for RateLimitPublisher<P, T>
+where
+    P: Publisher,
+    T: Timer,
+    Self: Sync,
+{
+    type Message = P::Message;
+
+    /// Returns the name of the underlying publisher
+    fn get_name(&self) -> &'static str {
+        self.publisher.get_name()
+    }
+
+    /// Publishes a message once the rate limit allows it, or drops or rejects it depending
+    /// on the policy
+    fn publish(&self, message: Self::Message) -> BoxFuture<'_, Result<()>> {
+        async move {
+            let now = self.timer.now();
+            let delay = {
+                let mut bucket = self
+                    .bucket
+                    .lock()
+                    .expect("the bucket lock is never poisoned");
+                match self.policy {
+                    RateLimitPolicy::Delay => bucket.reserve(now),
+                    RateLimitPolicy::Drop | RateLimitPolicy::Reject => {
+                        if bucket.try_acquire(now) {
+                            Duration::ZERO
+                        } else if self.policy == RateLimitPolicy::Drop {
+                            log::debug!(
+                                "[{}]: rate limit exceeded, dropping message",
+                                self.publisher.get_name()
+                            );
+                            return Ok(());
+                        } else {
+                            return Err(PublishError::new(
+                                self.publisher.get_name(),
+                                "rate limit exceeded",
+                                message,
+                            )
+                            .into());
+                        }
+                    }
+                }
+            };
+
+            if !delay.is_zero() {
+                self.timer.sleep(delay).await;
+            }
+
+            self.publisher.publish(message).await
+        }
+        .boxed()
+    }
+
+    fn close(&self) {
+        self.publisher.close()
+    }
+
+    fn is_closed(&self) -> bool {
+        self.publisher.is_closed()
+    }
+
+    fn closed(&self) -> BoxFuture<'_, ()> {
+        self.publisher.closed()
+    }
+
+    fn get_message_stream(
+        &mut self,
+        subscriber_name: &'static str,
+    ) -> Result<Pin<Box<dyn Stream<Item = Self::Message> + Send + Sync + 'static>>> {
+        self.publisher.get_message_stream(subscriber_name)
+    }
+}
+
+/// A token bucket, refilled continuously at a fixed rate.
+struct TokenBucket {
+    capacity: f64,
+    refill_rate: f64,
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now
+            .saturating_duration_since(self.last_refill)
+            .as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.refill_rate).min(self.capacity);
+        self.last_refill = now;
+    }
+
+    /// Takes a token if one is available.
+    fn try_acquire(&mut self, now: Instant) -> bool {
+        self.refill(now);
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Takes a token, possibly ahead of time, and returns how long to wait until it is
+    /// actually available. Reserving ahead of time keeps concurrent publications ordered.
+    fn reserve(&mut self, now: Instant) -> Duration {
+        self.refill(now);
+        self.tokens -= 1.0;
+        if self.tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-self.tokens / self.refill_rate)
+        }
+    }
+}
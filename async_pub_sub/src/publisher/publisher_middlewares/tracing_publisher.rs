@@ -0,0 +1,98 @@
+use std::pin::Pin;
+
+use futures::{FutureExt, Stream, StreamExt, future::BoxFuture};
+use tracing::Instrument;
+
+use crate::{Layer, Publisher, Result, Traced, error::map_undeliverable, utils::hand_over};
+
+/// A publisher middleware layer opening a tracing span for each published message.
+///
+/// The layer sits at the edge of a middleware stack: the messages are published within a
+/// `publish` span, child of the current span, and wrapped in a [`Traced`] envelope carrying
+/// it for the middlewares below. The messages are unwrapped again in the stream, their span
+/// being handed over to a subscriber wrapped with [`crate::TracingSubscriberLayer`], so that
+/// the next hops are recorded within the same trace.
+pub struct TracingPublisherLayer;
+
+impl<P, Message> Layer<P> for TracingPublisherLayer
+where
+    P: Publisher<Message = Traced<Message>>,
+    Message: Send + 'static,
+{
+    type LayerType = TracingPublisher<P>;
+
+    fn layer(&self, publisher: P) -> Self::LayerType {
+        TracingPublisher {
+            subscriber_name: None,
+            publisher,
+        }
+    }
+}
+
+/// A publisher wrapper exposing a publisher of [`Traced`] messages as a publisher of bare
+/// messages, opening a tracing span for each published message.
+pub struct TracingPublisher<P> {
+    /// The name of the subscriber receiving messages, set when get_message_stream is called
+    subscriber_name: Option<&'static str>,
+    /// The underlying publisher being wrapped
+    publisher: P,
+}
+
+impl<P, Message> Publisher for TracingPublisher<P>
+where
+    P: Publisher<Message = Traced<Message>>,
+    Message: Send + 'static,
+    Self: Sync,
+{
+    type Message = Message;
+
+    /// Returns the name of the underlying publisher
+    fn get_name(&self) -> &'static str {
+        self.publisher.get_name()
+    }
+
+    /// Publishes a message within a `publish` span, child of the current span
+    fn publish(&self, message: Self::Message) -> BoxFuture<'_, Result<()>> {
+        async move {
+            // the span is opened when the future is first polled, within the caller's span
+            let span = tracing::info_span!(
+                "publish",
+                publisher = self.publisher.get_name(),
+                subscriber = self.subscriber_name,
+            );
+            let message = Traced::with_span(message, span.clone());
+
+            async move {
+                tracing::trace!("publishing message");
+                self.publisher
+                    .publish(message)
+                    .await
+                    .map_err(|err| map_undeliverable(err, Traced::<Message>::into_inner))
+            }
+            .instrument(span)
+            .await
+        }
+        .boxed()
+    }
+
+    fn close(&self) {
+        self.publisher.close()
+    }
+
+    fn is_closed(&self) -> bool {
+        self.publisher.is_closed()
+    }
+
+    fn closed(&self) -> BoxFuture<'_, ()> {
+        self.publisher.closed()
+    }
+
+    fn get_message_stream(
+        &mut self,
+        subscriber_name: &'static str,
+    ) -> Result<Pin<Box<dyn Stream<Item = Self::Message> + Send + Sync + 'static>>> {
+        self.subscriber_name = Some(subscriber_name);
+        let stream = self.publisher.get_message_stream(subscriber_name)?;
+        Ok(Box::pin(stream.map(hand_over)))
+    }
+}
@@ -1,6 +1,6 @@
 use std::pin::Pin;
 use std::ops::{Deref, DerefMut};
-use futures::{Stream, future::BoxFuture};
+use futures::{FutureExt, Stream, future::{self, BoxFuture}};
 
 use crate::Result;
 
@@ -25,7 +25,36 @@ pub trait Publisher {
     ///
     /// # Returns
     /// A future that resolves to a Result indicating success or failure of the publish operation.
-    fn publish(&self, message: Self::Message) -> BoxFuture<Result<()>>;
+    fn publish(&self, message: Self::Message) -> BoxFuture<'_, Result<()>>;
+
+    /// Closes the publisher.
+    ///
+    /// The messages published afterwards are rejected, and the subscriber sees the end of the
+    /// stream once it has received all the messages already queued. The forwarders, which do
+    /// not queue the messages, end their stream right away.
+    ///
+    /// The default does nothing, for the publishers that cannot be closed. The publishers
+    /// owning a queue, a connection or a stream implement it, and the ones wrapping another
+    /// publisher forward it.
+    fn close(&self) {}
+
+    /// Returns whether the publisher has been closed.
+    ///
+    /// The default always returns `false`, for the publishers that cannot be closed.
+    fn is_closed(&self) -> bool {
+        false
+    }
+
+    /// Waits until the subscriber has received all the messages published before the
+    /// publisher was closed, or has been dropped.
+    ///
+    /// The default completes right away, for the publishers that cannot be closed.
+    ///
+    /// # Returns
+    /// A future completing once the queue is drained.
+    fn closed(&self) -> BoxFuture<'_, ()> {
+        future::ready(()).boxed()
+    }
 
     /// Creates a new message stream for a subscriber.
     ///
@@ -52,10 +81,22 @@ where
         self.deref().get_name()
     }
 
-    fn publish(&self, message: Self::Message) -> BoxFuture<Result<()>> {
+    fn publish(&self, message: Self::Message) -> BoxFuture<'_, Result<()>> {
         self.deref().publish(message)
     }
 
+    fn close(&self) {
+        self.deref().close()
+    }
+
+    fn is_closed(&self) -> bool {
+        self.deref().is_closed()
+    }
+
+    fn closed(&self) -> BoxFuture<'_, ()> {
+        self.deref().closed()
+    }
+
     fn get_message_stream(
         &mut self,
         subscriber_name: &'static str,
@@ -99,10 +140,28 @@ where
     ///
     /// # Returns
     /// A future that resolves to a Result indicating success or failure of the publish operation.
-    fn publish(&self, message: Message) -> futures::future::BoxFuture<Result<()>> {
+    fn publish(&self, message: Message) -> futures::future::BoxFuture<'_, Result<()>> {
         Publisher::publish(self.get_publisher(), message)
     }
 
+    /// Closes the wrapped publisher.
+    fn close(&self) {
+        Publisher::close(self.get_publisher())
+    }
+
+    /// Returns whether the wrapped publisher has been closed.
+    fn is_closed(&self) -> bool {
+        Publisher::is_closed(self.get_publisher())
+    }
+
+    /// Waits until the subscriber of the wrapped publisher has drained its queue.
+    ///
+    /// # Returns
+    /// A future completing once the queue is drained.
+    fn closed(&self) -> futures::future::BoxFuture<'_, ()> {
+        Publisher::closed(self.get_publisher())
+    }
+
     /// Creates a new message stream using the wrapped publisher.
     ///
     /// # Arguments
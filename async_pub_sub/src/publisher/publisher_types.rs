@@ -1,5 +1,7 @@
 use std::fmt::{Debug, Display};
 
+use crate::Metadata;
+
 /// A request structure that represents a request-response pattern for asynchronous communication.
 ///
 /// This struct encapsulates a request of type `Req` and provides a mechanism to send back
@@ -14,6 +16,11 @@ use std::fmt::{Debug, Display};
 ///
 /// * `content` - The actual request content
 /// * `response_sender` - A oneshot channel sender for sending the response
+/// * `metadata` - The [`Metadata`] of the request, e.g. its correlation id
+///
+/// Adding the `metadata` field is a breaking change for the code building a request with a
+/// struct expression, which should use [`Request::new`] or [`Request::with_metadata`]
+/// instead, and for the code destructuring every field of a request.
 ///
 /// # Examples
 ///
@@ -23,6 +30,7 @@ use std::fmt::{Debug, Display};
 /// # async fn main() {
 /// let (request, response_receiver) = Request::new(String::from("hello"));
 /// assert_eq!(request.content, "hello");
+/// assert_eq!(request.metadata().correlation_id(), None);
 /// request.respond(42);
 /// assert_eq!(response_receiver.await.unwrap(), 42);
 /// # }
@@ -34,6 +42,7 @@ where
 {
     pub content: Req,
     pub response_sender: futures::channel::oneshot::Sender<Rsp>,
+    pub metadata: Metadata,
 }
 
 impl<Req, Rsp> Request<Req, Rsp>
@@ -42,16 +51,42 @@ where
     Rsp: Debug,
 {
     pub fn new(content: Req) -> (Self, futures::channel::oneshot::Receiver<Rsp>) {
+        Self::with_metadata(content, Metadata::new())
+    }
+
+    /// Creates a request carrying the given metadata, e.g. the metadata of a reply to
+    /// another message so that both share the same correlation id.
+    pub fn with_metadata(
+        content: Req,
+        metadata: Metadata,
+    ) -> (Self, futures::channel::oneshot::Receiver<Rsp>) {
         let (response_sender, response_receiver) = futures::channel::oneshot::channel();
         (
             Self {
                 content,
                 response_sender,
+                metadata,
             },
             response_receiver,
         )
     }
 
+    /// Sets the id correlating the request with other messages.
+    pub fn with_correlation_id(mut self, correlation_id: u64) -> Self {
+        self.metadata.set_correlation_id(correlation_id);
+        self
+    }
+
+    /// Returns the metadata of the request.
+    pub fn metadata(&self) -> &Metadata {
+        &self.metadata
+    }
+
+    /// Returns the metadata of the request, mutably.
+    pub fn metadata_mut(&mut self) -> &mut Metadata {
+        &mut self.metadata
+    }
+
     pub fn respond(self, response: Rsp) {
         self.response_sender
             .send(response)
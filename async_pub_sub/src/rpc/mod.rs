@@ -0,0 +1,7 @@
+mod remote_rpc_client;
+mod remote_rpc_server;
+mod remote_rpc_trait;
+
+pub use remote_rpc_client::RemoteRpcClient;
+pub use remote_rpc_server::RemoteRpcServer;
+pub use remote_rpc_trait::{RemoteRpcMessage, ReplySender};
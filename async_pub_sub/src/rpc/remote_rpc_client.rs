@@ -0,0 +1,239 @@
+use std::{collections::HashMap, pin::Pin, sync::Arc, time::Duration};
+
+use bytes::Bytes;
+use futures::{
+    FutureExt, Stream, StreamExt,
+    future::BoxFuture,
+    stream::{self, FusedStream, FuturesUnordered},
+};
+
+use super::remote_rpc_trait::{RemoteRpcMessage, ReplySender};
+use crate::{Codec, JsonCodec, Publisher, Result, Subscriber, SubscriberImpl, Timer};
+
+/// A local stand-in for an RPC server living in another process.
+///
+/// The remote client subscribes to the local RPC client (e.g. a generated `XxxClient`),
+/// serializes every request into a call carrying its metadata and publishes it on a
+/// byte transport. Serialized replies received from the transport are matched with the
+/// pending requests and delivered back to the local caller.
+///
+/// # Type Parameters
+/// * `M` - The RPC message type, generated by the `rpc_interface` macro
+/// * `C` - The codec encoding the calls and decoding the replies, JSON by default
+///
+/// # Example
+///
+/// ```ignore
+/// let mut client = PersistencyInterfaceClient::new(PublisherImpl::new("cache", 10));
+/// let mut remote = RemoteRpcClient::new("persistency", transport_publisher, transport_subscriber);
+///
+/// route!(client -> remote)?;
+/// tokio::spawn(remote.run());
+/// ```
+pub struct RemoteRpcClient<M, C = JsonCodec>
+where
+    M: RemoteRpcMessage,
+{
+    /// The name identifier of this remote client
+    name: &'static str,
+    /// The subscriber receiving the requests of the local RPC clients
+    requests: SubscriberImpl<M>,
+    /// The publisher sending serialized calls on the transport
+    outgoing: Box<dyn Publisher<Message = Bytes> + Send + Sync>,
+    /// The subscriber receiving serialized replies from the transport
+    incoming: Box<dyn Subscriber<Message = Bytes> + Send>,
+    /// The codec encoding the calls and decoding the replies
+    codec: C,
+    /// The duration after which a call left without reply is dropped, and the timer measuring it
+    call_timeout: Option<(Duration, Arc<dyn Timer>)>,
+}
+
+impl<M> RemoteRpcClient<M>
+where
+    M: RemoteRpcMessage,
+{
+    /// Creates a new remote client on top of a byte transport.
+    ///
+    /// # Arguments
+    /// * `name` - A static string identifier for the remote client
+    /// * `outgoing` - The publisher used to send serialized calls
+    /// * `incoming` - The subscriber used to receive serialized replies, already bound
+    ///   to the transport
+    pub fn new<P, S>(name: &'static str, outgoing: P, incoming: S) -> Self
+    where
+        P: Publisher<Message = Bytes> + Send + Sync + 'static,
+        S: Subscriber<Message = Bytes> + Send + 'static,
+    {
+        Self {
+            name,
+            requests: SubscriberImpl::new(name),
+            outgoing: Box::new(outgoing),
+            incoming: Box::new(incoming),
+            codec: JsonCodec,
+            call_timeout: None,
+        }
+    }
+}
+
+impl<M, C> RemoteRpcClient<M, C>
+where
+    M: RemoteRpcMessage,
+    C: Codec<M::Call> + Codec<M::Reply>,
+{
+    /// Replaces the codec encoding the calls and decoding the replies, the remote server
+    /// must use the same codec.
+    ///
+    /// # Arguments
+    /// * `codec` - The codec of the calls and replies
+    pub fn with_codec<C2>(self, codec: C2) -> RemoteRpcClient<M, C2> {
+        RemoteRpcClient {
+            name: self.name,
+            requests: self.requests,
+            outgoing: self.outgoing,
+            incoming: self.incoming,
+            codec,
+            call_timeout: self.call_timeout,
+        }
+    }
+
+    /// Drops the calls left without reply for the given duration, their caller being
+    /// notified that the request was dropped. Without a timeout, a call whose reply is
+    /// lost stays pending until the transport ends.
+    ///
+    /// # Arguments
+    /// * `timeout` - The duration to wait for the reply of a call
+    /// * `timer` - The timer measuring the timeout
+    pub fn with_call_timeout(self, timeout: Duration, timer: impl Timer) -> Self {
+        Self {
+            call_timeout: Some((timeout, Arc::new(timer))),
+            ..self
+        }
+    }
+
+    /// Forwards the local requests to the transport and dispatches the replies.
+    ///
+    /// A call that cannot be serialized or published is dropped, its caller being notified
+    /// that the request was dropped, and the remote client keeps running.
+    ///
+    /// # Returns
+    /// Once the local requests have ended and every pending call is settled, or once the
+    /// transport has ended, the calls still pending being dropped
+    pub async fn run(self) -> Result<()> {
+        let Self {
+            name,
+            requests,
+            outgoing,
+            incoming,
+            codec,
+            call_timeout,
+        } = self;
+
+        let mut pending: HashMap<u64, ReplySender<M::Reply>> = HashMap::new();
+        let mut timeouts = FuturesUnordered::new();
+
+        let mut requests = subscriber_stream(requests).fuse();
+        let mut replies = subscriber_stream(incoming).fuse();
+
+        loop {
+            futures::select! {
+                request = requests.next() => {
+                    let Some(request) = request else {
+                        if pending.is_empty() {
+                            return Ok(());
+                        }
+                        continue;
+                    };
+
+                    let (call, reply_sender) = request.into_call();
+                    let correlation_id = M::call_correlation_id(&call);
+                    let call = match codec.encode(&call) {
+                        Ok(call) => call,
+                        Err(err) => {
+                            log::warn!("[{}] failed to encode call {} (err: {})", name, correlation_id, err);
+                            continue;
+                        }
+                    };
+                    if let Err(err) = outgoing.publish(call).await {
+                        log::warn!("[{}] failed to publish call {} (err: {})", name, correlation_id, err);
+                        continue;
+                    }
+
+                    pending.insert(correlation_id, reply_sender);
+                    if let Some((timeout, timer)) = &call_timeout {
+                        timeouts.push(timer.sleep(*timeout).map(move |_| correlation_id));
+                    }
+                }
+                reply = replies.next() => {
+                    let Some(reply) = reply else {
+                        if !pending.is_empty() {
+                            log::warn!("[{}] transport ended, dropping {} pending calls", name, pending.len());
+                        }
+                        return Ok(());
+                    };
+
+                    let reply = match Codec::<M::Reply>::decode(&codec, &reply) {
+                        Ok(reply) => reply,
+                        Err(err) => {
+                            log::warn!("[{}] failed to decode reply (err: {})", name, err);
+                            continue;
+                        }
+                    };
+
+                    let correlation_id = M::reply_correlation_id(&reply);
+                    let Some(reply_sender) = pending.remove(&correlation_id) else {
+                        log::warn!("[{}] received reply for unknown call {}", name, correlation_id);
+                        continue;
+                    };
+
+                    if let Err(err) = reply_sender(reply) {
+                        log::warn!("[{}] failed to deliver reply {} (err: {})", name, correlation_id, err);
+                    }
+                }
+                correlation_id = timeouts.select_next_some() => {
+                    if pending.remove(&correlation_id).is_some() {
+                        log::warn!("[{}] call {} timed out", name, correlation_id);
+                    }
+                }
+            }
+
+            if requests.is_terminated() && pending.is_empty() {
+                return Ok(());
+            }
+        }
+    }
+}
+
+impl<M, C> Subscriber for RemoteRpcClient<M, C>
+where
+    M: RemoteRpcMessage,
+{
+    type Message = M;
+
+    fn get_name(&self) -> &'static str {
+        self.name
+    }
+
+    fn subscribe_to(&mut self, publisher: &mut dyn Publisher<Message = M>) -> Result<()> {
+        self.requests.subscribe_to(publisher)
+    }
+
+    /// Not implemented for RemoteRpcClient, requests are forwarded by `run`. Will panic if called.
+    fn receive(&mut self) -> BoxFuture<'_, M> {
+        panic!("RemoteRpcClient does not implement receive method")
+    }
+}
+
+pub(crate) fn subscriber_stream<S>(
+    subscriber: S,
+) -> Pin<Box<dyn Stream<Item = S::Message> + Send + 'static>>
+where
+    S: Subscriber + Send + 'static,
+{
+    Box::pin(stream::unfold(subscriber, |mut subscriber| {
+        async move {
+            let message = subscriber.next_message().await?;
+            Some((message, subscriber))
+        }
+        .boxed()
+    }))
+}
@@ -0,0 +1,165 @@
+use std::pin::Pin;
+
+use bytes::Bytes;
+use futures::{FutureExt, Stream, StreamExt, future::BoxFuture, stream::FuturesUnordered};
+
+use super::{
+    remote_rpc_client::subscriber_stream,
+    remote_rpc_trait::RemoteRpcMessage,
+};
+use crate::{Codec, JsonCodec, Publisher, PublisherImpl, Result, Subscriber};
+
+/// A local stand-in for RPC clients living in another process.
+///
+/// The remote server receives serialized calls from a byte transport, rebuilds the local
+/// RPC messages and publishes them to the local RPC server (e.g. a type implementing a
+/// generated `XxxServer` trait). Once handled, the replies are serialized with the
+/// correlation id of their call and published back on the transport.
+///
+/// # Type Parameters
+/// * `M` - The RPC message type, generated by the `rpc_interface` macro
+/// * `C` - The codec decoding the calls and encoding the replies, JSON by default
+///
+/// # Example
+///
+/// ```ignore
+/// let mut remote = RemoteRpcServer::new("cache", 10, transport_subscriber, transport_publisher);
+/// let mut persistency_service = PersistencyService::new();
+///
+/// route!(remote -> persistency_service)?;
+/// tokio::spawn(remote.run());
+/// ```
+pub struct RemoteRpcServer<M, C = JsonCodec>
+where
+    M: RemoteRpcMessage,
+{
+    /// The name identifier of this remote server
+    name: &'static str,
+    /// The publisher sending the rebuilt requests to the local RPC server
+    requests: PublisherImpl<M>,
+    /// The subscriber receiving serialized calls from the transport
+    incoming: Box<dyn Subscriber<Message = Bytes> + Send>,
+    /// The publisher sending serialized replies on the transport
+    outgoing: Box<dyn Publisher<Message = Bytes> + Send + Sync>,
+    /// The codec decoding the calls and encoding the replies
+    codec: C,
+}
+
+impl<M> RemoteRpcServer<M>
+where
+    M: RemoteRpcMessage,
+{
+    /// Creates a new remote server on top of a byte transport.
+    ///
+    /// # Arguments
+    /// * `name` - A static string identifier for the remote server
+    /// * `buffer_size` - The size of the buffer of requests sent to the local RPC server
+    /// * `incoming` - The subscriber used to receive serialized calls, already bound
+    ///   to the transport
+    /// * `outgoing` - The publisher used to send serialized replies
+    pub fn new<S, P>(name: &'static str, buffer_size: usize, incoming: S, outgoing: P) -> Self
+    where
+        S: Subscriber<Message = Bytes> + Send + 'static,
+        P: Publisher<Message = Bytes> + Send + Sync + 'static,
+    {
+        Self {
+            name,
+            requests: PublisherImpl::new(name, buffer_size),
+            incoming: Box::new(incoming),
+            outgoing: Box::new(outgoing),
+            codec: JsonCodec,
+        }
+    }
+}
+
+impl<M, C> RemoteRpcServer<M, C>
+where
+    M: RemoteRpcMessage,
+    C: Codec<M::Call> + Codec<M::Reply>,
+{
+    /// Replaces the codec decoding the calls and encoding the replies, the remote client
+    /// must use the same codec.
+    ///
+    /// # Arguments
+    /// * `codec` - The codec of the calls and replies
+    pub fn with_codec<C2>(self, codec: C2) -> RemoteRpcServer<M, C2> {
+        RemoteRpcServer {
+            name: self.name,
+            requests: self.requests,
+            incoming: self.incoming,
+            outgoing: self.outgoing,
+            codec,
+        }
+    }
+
+    /// Forwards the calls received from the transport to the local RPC server and
+    /// publishes the replies.
+    ///
+    /// # Returns
+    /// Once the transport has ended and every pending call has been replied to, or an error
+    /// if a reply cannot be serialized or published
+    pub async fn run(self) -> Result<()> {
+        let Self {
+            name,
+            requests,
+            incoming,
+            outgoing,
+            codec,
+        } = self;
+
+        let mut calls = subscriber_stream(incoming).fuse();
+        let mut replies = FuturesUnordered::new();
+
+        loop {
+            futures::select! {
+                call = calls.select_next_some() => {
+                    let call = match Codec::<M::Call>::decode(&codec, &call) {
+                        Ok(call) => call,
+                        Err(err) => {
+                            log::warn!("[{}] failed to decode call (err: {})", name, err);
+                            continue;
+                        }
+                    };
+
+                    let correlation_id = M::call_correlation_id(&call);
+                    let (request, reply) = M::from_call(call);
+                    requests.publish(request).await?;
+                    replies.push(reply.map(move |reply| (correlation_id, reply)));
+                }
+                (correlation_id, reply) = replies.select_next_some() => {
+                    let Some(reply) = reply else {
+                        log::warn!("[{}] call {} was dropped without reply", name, correlation_id);
+                        continue;
+                    };
+
+                    let reply = codec.encode(&reply)?;
+                    outgoing.publish(reply).await?;
+                }
+                complete => return Ok(()),
+            }
+        }
+    }
+}
+
+impl<M, C> Publisher for RemoteRpcServer<M, C>
+where
+    M: RemoteRpcMessage + Sync,
+    C: Sync,
+{
+    type Message = M;
+
+    fn get_name(&self) -> &'static str {
+        self.name
+    }
+
+    fn publish(&self, message: M) -> BoxFuture<'_, Result<()>> {
+        Publisher::publish(&self.requests, message)
+    }
+
+    fn get_message_stream(
+        &mut self,
+        subscriber_name: &'static str,
+    ) -> Result<Pin<Box<dyn Stream<Item = M> + Send + Sync + 'static>>> {
+        self.requests.get_message_stream(subscriber_name)
+    }
+}
@@ -0,0 +1,40 @@
+use futures::future::BoxFuture;
+use serde::{Serialize, de::DeserializeOwned};
+
+use crate::Result;
+
+/// A callback delivering a serialized RPC reply back to the local caller.
+pub type ReplySender<Reply> = Box<dyn FnOnce(Reply) -> Result<()> + Send>;
+
+/// A trait for RPC messages that can be sent across process boundaries.
+///
+/// RPC messages generated by the `rpc_interface` macro carry a oneshot channel that cannot be
+/// serialized. This trait splits such a message into a serializable call carrying the
+/// [`crate::Metadata`] of the request, and rebuilds a local message carrying the same metadata
+/// from a received call. The replies are correlated with the id of that metadata.
+///
+/// It is implemented by the `rpc_interface` macro when the `serde` option is given.
+pub trait RemoteRpcMessage: Sized + Send + 'static {
+    /// The serializable form of a call.
+    type Call: Serialize + DeserializeOwned + Send + 'static;
+
+    /// The serializable form of a reply.
+    type Reply: Serialize + DeserializeOwned + Send + 'static;
+
+    /// Splits a local message into its serializable call and the callback used to
+    /// deliver the matching reply.
+    fn into_call(self) -> (Self::Call, ReplySender<Self::Reply>);
+
+    /// Rebuilds a local message from a received call.
+    ///
+    /// # Returns
+    /// The local message and a future resolving to the reply once the message has been
+    /// handled, or to `None` if the handler dropped the request.
+    fn from_call(call: Self::Call) -> (Self, BoxFuture<'static, Option<Self::Reply>>);
+
+    /// Returns the correlation id of a call, the id of the metadata of its request.
+    fn call_correlation_id(call: &Self::Call) -> u64;
+
+    /// Returns the correlation id of a reply.
+    fn reply_correlation_id(reply: &Self::Reply) -> u64;
+}
@@ -0,0 +1,7 @@
+mod service_trait;
+mod shutdown_signal;
+mod supervisor;
+
+pub use service_trait::Service;
+pub use shutdown_signal::ShutdownSignal;
+pub use supervisor::{RestartStrategy, Supervisor};
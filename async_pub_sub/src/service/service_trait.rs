@@ -0,0 +1,37 @@
+use futures::{FutureExt, future::BoxFuture};
+
+use crate::{Result, ShutdownSignal};
+
+/// A long-running component of an application, managed by a [`crate::Supervisor`].
+///
+/// A service usually owns publishers and subscribers, handling the received messages in
+/// [`Service::run`] until its shutdown signal is cancelled.
+pub trait Service: Send {
+    /// Returns the name of the service.
+    fn get_name(&self) -> &'static str;
+
+    /// Prepares the service before each run, including after a restart.
+    ///
+    /// Does nothing by default.
+    fn start(&mut self) -> BoxFuture<'_, Result<()>> {
+        async { Ok(()) }.boxed()
+    }
+
+    /// Runs the service until the shutdown signal is cancelled.
+    ///
+    /// Returning an error makes the supervisor restart the service according to its
+    /// [`crate::RestartStrategy`], while returning `Ok` before the cancellation marks the
+    /// service as completed.
+    ///
+    /// # Arguments
+    /// * `shutdown` - The signal cancelled when the service must stop
+    fn run(&mut self, shutdown: ShutdownSignal) -> BoxFuture<'_, Result<()>>;
+
+    /// Stops the service once it is no longer running, for instance by handling the messages
+    /// still queued in its subscribers.
+    ///
+    /// Does nothing by default.
+    fn shutdown(&mut self) -> BoxFuture<'_, Result<()>> {
+        async { Ok(()) }.boxed()
+    }
+}
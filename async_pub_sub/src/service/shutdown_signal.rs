@@ -0,0 +1,89 @@
+use std::sync::{
+    Arc, Mutex,
+    atomic::{AtomicBool, Ordering},
+};
+
+use futures::{
+    FutureExt,
+    channel::oneshot,
+    future::{BoxFuture, Shared},
+};
+
+/// A cancellation signal shared between a [`crate::Supervisor`] and its services.
+///
+/// All the clones of a signal are cancelled at once. The signal does not depend on any
+/// runtime, so that services can wait for it with any executor.
+///
+/// # Example
+///
+/// ```
+/// use async_pub_sub::ShutdownSignal;
+///
+/// let shutdown = ShutdownSignal::new();
+/// let service_shutdown = shutdown.clone();
+///
+/// shutdown.cancel();
+///
+/// assert!(service_shutdown.is_cancelled());
+/// futures::executor::block_on(service_shutdown.cancelled());
+/// ```
+#[derive(Clone)]
+pub struct ShutdownSignal {
+    is_cancelled: Arc<AtomicBool>,
+    sender: Arc<Mutex<Option<oneshot::Sender<()>>>>,
+    receiver: Shared<oneshot::Receiver<()>>,
+}
+
+impl ShutdownSignal {
+    /// Creates a new signal, not cancelled yet.
+    pub fn new() -> Self {
+        let (sender, receiver) = oneshot::channel();
+        Self {
+            is_cancelled: Arc::new(AtomicBool::new(false)),
+            sender: Arc::new(Mutex::new(Some(sender))),
+            receiver: receiver.shared(),
+        }
+    }
+
+    /// Cancels the signal, waking up all the futures waiting for it.
+    ///
+    /// Cancelling an already cancelled signal has no effect.
+    pub fn cancel(&self) {
+        self.is_cancelled.store(true, Ordering::SeqCst);
+        let sender = self
+            .sender
+            .lock()
+            .expect("shutdown signal lock poisoned")
+            .take();
+        if let Some(sender) = sender {
+            let _ = sender.send(());
+        }
+    }
+
+    /// Returns whether the signal has been cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.is_cancelled.load(Ordering::SeqCst)
+    }
+
+    /// Returns a future completing once the signal is cancelled.
+    pub fn cancelled(&self) -> BoxFuture<'static, ()> {
+        // the sender is kept alive by the future, so that the receiver only completes on
+        // cancellation
+        let sender = self.sender.clone();
+        self.receiver.clone().map(move |_| drop(sender)).boxed()
+    }
+}
+
+impl Default for ShutdownSignal {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Debug for ShutdownSignal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ShutdownSignal")
+            .field("is_cancelled", &self.is_cancelled())
+            .finish()
+    }
+}
@@ -0,0 +1,284 @@
+use std::{any::Any, panic::AssertUnwindSafe, sync::Arc, time::Duration};
+
+use futures::{
+    FutureExt, StreamExt,
+    future::{BoxFuture, Either, select},
+    stream::FuturesUnordered,
+};
+
+use crate::{Result, Service, ShutdownSignal, Timer};
+
+/// The outcome of a run of a service, the errors being converted to strings so that the
+/// supervisor remains `Send`.
+type RunningService =
+    BoxFuture<'static, (usize, Box<dyn Service>, std::result::Result<(), String>)>;
+
+/// Which services a [`Supervisor`] restarts when one of them fails.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RestartStrategy {
+    /// Only the failed service is restarted.
+    OneForOne,
+    /// All the running services are stopped and restarted along with the failed one, for
+    /// services depending on each other's state.
+    OneForAll,
+}
+
+/// Runs a set of [`Service`]s, restarting them when they fail and stopping them gracefully.
+///
+/// The services are run concurrently within the future returned by [`Supervisor::run`]. This
+/// future is `Send`, but its error is not: to spawn it on a runtime requiring a `Send` output,
+/// convert the error, e.g. `tokio::spawn(async move { supervisor.run().await.map_err(|err|
+/// err.to_string()) })`. Each of them receives a [`ShutdownSignal`], cancelled
+/// when the shutdown signal of the supervisor is, or when the service must be stopped to be
+/// restarted along with a failed one.
+///
+/// A service fails when [`Service::start`] or [`Service::run`] returns an error or panics. It is
+/// then restarted according to the [`RestartStrategy`], by calling [`Service::start`] and
+/// [`Service::run`] again on the same instance, so that the messages queued in its subscribers
+/// are not lost, after the restart backoff if one is set. A service restarted after a panic
+/// must be able to resume from whatever state the panic left it in. Once a service has failed more than the maximum
+/// number of restarts, all the services are stopped and the supervisor returns an error.
+///
+/// On shutdown, the supervisor waits for all the services to return from [`Service::run`],
+/// then calls [`Service::shutdown`] on each of them, in their order of addition, so that they
+/// can drain their in-flight messages.
+///
+/// # Example
+///
+/// ```
+/// use async_pub_sub::{
+///     Result, RestartStrategy, Service, ShutdownSignal, Supervisor,
+///     futures::{FutureExt, future::BoxFuture},
+/// };
+///
+/// struct Heartbeat;
+///
+/// impl Service for Heartbeat {
+///     fn get_name(&self) -> &'static str {
+///         "heartbeat"
+///     }
+///
+///     fn run(&mut self, shutdown: ShutdownSignal) -> BoxFuture<'_, Result<()>> {
+///         async move {
+///             shutdown.cancelled().await;
+///             Ok(())
+///         }
+///         .boxed()
+///     }
+/// }
+///
+/// let supervisor = Supervisor::new(RestartStrategy::OneForOne).with_service(Heartbeat);
+/// let shutdown = supervisor.shutdown_signal();
+///
+/// shutdown.cancel();
+/// futures::executor::block_on(supervisor.run()).unwrap();
+/// ```
+pub struct Supervisor {
+    strategy: RestartStrategy,
+    max_restarts: usize,
+    backoff: Option<(Duration, Arc<dyn Timer>)>,
+    services: Vec<Box<dyn Service>>,
+    shutdown: ShutdownSignal,
+}
+
+impl Supervisor {
+    /// Creates a new supervisor without services, restarting each service up to 3 times.
+    ///
+    /// # Arguments
+    /// * `strategy` - Which services are restarted when one of them fails
+    pub fn new(strategy: RestartStrategy) -> Self {
+        Self {
+            strategy,
+            max_restarts: 3,
+            backoff: None,
+            services: Vec::new(),
+            shutdown: ShutdownSignal::new(),
+        }
+    }
+
+    /// Sets the number of times each service can be restarted before the supervisor gives up.
+    pub fn with_max_restarts(self, max_restarts: usize) -> Self {
+        Self {
+            max_restarts,
+            ..self
+        }
+    }
+
+    /// Waits before restarting a failed service, the backoff doubling after each of its
+    /// restarts. The services are restarted right away by default.
+    ///
+    /// # Arguments
+    /// * `initial_backoff` - The duration to wait before the first restart of a service
+    /// * `timer` - The timer of the async runtime
+    pub fn with_restart_backoff(self, initial_backoff: Duration, timer: impl Timer) -> Self {
+        Self {
+            backoff: Some((initial_backoff, Arc::new(timer))),
+            ..self
+        }
+    }
+
+    /// Adds a service to the supervisor.
+    pub fn with_service(mut self, service: impl Service + 'static) -> Self {
+        self.services.push(Box::new(service));
+        self
+    }
+
+    /// Returns the signal stopping the supervisor and all its services once cancelled.
+    pub fn shutdown_signal(&self) -> ShutdownSignal {
+        self.shutdown.clone()
+    }
+
+    /// Runs the services until the shutdown signal is cancelled, all the services complete or
+    /// one of them fails too many times.
+    ///
+    /// # Returns
+    /// An error if a service exceeded the maximum number of restarts
+    pub async fn run(self) -> Result<()> {
+        let Self {
+            strategy,
+            max_restarts,
+            backoff,
+            services,
+            shutdown,
+        } = self;
+
+        let names: Vec<_> = services.iter().map(|service| service.get_name()).collect();
+        let mut restarts = vec![0; services.len()];
+        let mut stopped: Vec<Option<Box<dyn Service>>> = services.iter().map(|_| None).collect();
+        let mut failure = None;
+
+        let mut generation = ShutdownSignal::new();
+        let mut running: FuturesUnordered<_> = services
+            .into_iter()
+            .enumerate()
+            .map(|(index, service)| launch(index, service, generation.clone(), None))
+            .collect();
+
+        let mut shutdown_requested = shutdown.cancelled();
+        loop {
+            let (index, service, result) =
+                match select(running.next(), &mut shutdown_requested).await {
+                    Either::Left((Some(completed), _)) => completed,
+                    Either::Left((None, _)) | Either::Right(_) => break,
+                };
+            let name = names[index];
+
+            let Err(err) = result else {
+                log::info!("[{name}] service completed");
+                stopped[index] = Some(service);
+                continue;
+            };
+
+            restarts[index] += 1;
+            if restarts[index] > max_restarts {
+                log::error!("[{name}] service failed too many times, shutting down (err: {err})");
+                failure = Some(format!(
+                    "service `{name}` failed more than {max_restarts} times, last error: {err}"
+                ));
+                stopped[index] = Some(service);
+                break;
+            }
+            log::warn!("[{name}] service failed, restarting it (err: {err})");
+
+            let delay = backoff.as_ref().map(|(initial_backoff, timer)| {
+                let factor = 2u32.saturating_pow(restarts[index] as u32 - 1);
+                timer.sleep(initial_backoff.saturating_mul(factor))
+            });
+
+            match strategy {
+                RestartStrategy::OneForOne => {
+                    running.push(launch(index, service, generation.clone(), delay));
+                }
+                RestartStrategy::OneForAll => {
+                    generation.cancel();
+                    let mut restarted = vec![(index, service)];
+                    while let Some((index, service, result)) = running.next().await {
+                        if let Err(err) = result {
+                            log::warn!(
+                                "[{}] service failed while stopping (err: {err})",
+                                names[index]
+                            );
+                        }
+                        restarted.push((index, service));
+                    }
+
+                    // the services are restarted together, once the backoff has elapsed
+                    if let Some(delay) = delay {
+                        delay.await;
+                    }
+                    generation = ShutdownSignal::new();
+                    running.extend(
+                        restarted.into_iter().map(|(index, service)| {
+                            launch(index, service, generation.clone(), None)
+                        }),
+                    );
+                }
+            }
+        }
+
+        generation.cancel();
+        while let Some((index, service, result)) = running.next().await {
+            if let Err(err) = result {
+                log::warn!(
+                    "[{}] service failed while stopping (err: {err})",
+                    names[index]
+                );
+            }
+            stopped[index] = Some(service);
+        }
+
+        for service in stopped.iter_mut().flatten() {
+            let result = service.shutdown().await.map_err(|err| err.to_string());
+            if let Err(err) = result {
+                log::warn!(
+                    "[{}] failed to shut down service (err: {err})",
+                    service.get_name()
+                );
+            }
+        }
+
+        match failure {
+            Some(failure) => Err(failure.into()),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Starts and runs a service once the delay has elapsed, handing it back along with the
+/// outcome of the run, a panic being reported as a failure.
+fn launch(
+    index: usize,
+    mut service: Box<dyn Service>,
+    shutdown: ShutdownSignal,
+    delay: Option<BoxFuture<'static, ()>>,
+) -> RunningService {
+    async move {
+        if let Some(delay) = delay {
+            delay.await;
+        }
+        let result = AssertUnwindSafe(start_and_run(service.as_mut(), shutdown))
+            .catch_unwind()
+            .await
+            .unwrap_or_else(|panic| Err(format!("panicked: {}", panic_message(&*panic))));
+        (index, service, result)
+    }
+    .boxed()
+}
+
+async fn start_and_run(
+    service: &mut dyn Service,
+    shutdown: ShutdownSignal,
+) -> std::result::Result<(), String> {
+    service.start().await.map_err(|err| err.to_string())?;
+    service.run(shutdown).await.map_err(|err| err.to_string())
+}
+
+fn panic_message(panic: &(dyn Any + Send)) -> &str {
+    if let Some(message) = panic.downcast_ref::<&'static str>() {
+        message
+    } else if let Some(message) = panic.downcast_ref::<String>() {
+        message
+    } else {
+        "unknown panic"
+    }
+}
@@ -1,7 +1,14 @@
+mod retry;
 mod subscriber_impl;
-mod subscriber_trait;
 mod subscriber_middlewares;
+mod subscriber_trait;
 
+pub use retry::RetryPolicy;
 pub use subscriber_impl::SubscriberImpl;
-pub use subscriber_trait::{SubscriberWrapper, Subscriber};
-pub use subscriber_middlewares::{DebuggingSubscriberLayer, LoggingSubscriberLayer, SubscriberBuilder};
+pub use subscriber_middlewares::{
+    DebuggingSubscriberLayer, DecodingSubscriberLayer, DedupSubscriberLayer,
+    EnvelopeSubscriberLayer, LoggingSubscriberLayer, MetricsSubscriberLayer, SubscriberBuilder,
+};
+#[cfg(feature = "tracing")]
+pub use subscriber_middlewares::TracingSubscriberLayer;
+pub use subscriber_trait::{Subscriber, SubscriberWrapper};
@@ -0,0 +1,154 @@
+use std::{sync::Arc, time::Duration};
+
+use crate::{Publisher, Result, Subscriber, Timer};
+
+/// The retry policy of [`Subscriber::for_each_with_retry`].
+///
+/// A failing message is handled again after an exponentially growing backoff, until it is
+/// handled successfully or until `max_attempts` attempts have failed. It is then handed to
+/// the dead-letter publisher, if any, or dropped.
+///
+/// # Type Parameters
+/// * `Message` - The type of the handled messages
+pub struct RetryPolicy<Message> {
+    max_attempts: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    multiplier: f64,
+    timer: Arc<dyn Timer>,
+    dead_letter: Option<Box<dyn Publisher<Message = Message> + Send + Sync>>,
+}
+
+impl<Message> RetryPolicy<Message>
+where
+    Message: Send + 'static,
+{
+    /// Creates a new retry policy doubling the backoff after each failed attempt.
+    ///
+    /// # Arguments
+    /// * `max_attempts` - The maximum number of attempts to handle a message, including the
+    ///   first one
+    /// * `initial_backoff` - The duration to wait after the first failed attempt
+    /// * `timer` - The timer of the async runtime
+    ///
+    /// # Panics
+    /// Panics if `max_attempts` is zero.
+    pub fn new(max_attempts: u32, initial_backoff: Duration, timer: impl Timer) -> Self {
+        assert!(max_attempts > 0, "at least one attempt must be made");
+
+        Self {
+            max_attempts,
+            initial_backoff,
+            max_backoff: Duration::MAX,
+            multiplier: 2.0,
+            timer: Arc::new(timer),
+            dead_letter: None,
+        }
+    }
+
+    /// Caps the backoff between two attempts.
+    pub fn with_max_backoff(self, max_backoff: Duration) -> Self {
+        Self {
+            max_backoff,
+            ..self
+        }
+    }
+
+    /// Sets the factor applied to the backoff after each failed attempt.
+    ///
+    /// # Panics
+    /// Panics if `multiplier` is lower than 1.
+    pub fn with_multiplier(self, multiplier: f64) -> Self {
+        assert!(
+            multiplier >= 1.0,
+            "the backoff multiplier must be at least 1"
+        );
+
+        Self { multiplier, ..self }
+    }
+
+    /// Publishes the messages that could not be handled with the given publisher.
+    ///
+    /// The publisher should already be routed to its subscribers, a diagnostics service for
+    /// instance.
+    pub fn with_dead_letter(
+        self,
+        dead_letter: impl Publisher<Message = Message> + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            dead_letter: Some(Box::new(dead_letter)),
+            ..self
+        }
+    }
+
+    /// Returns the duration to wait after the given failed attempt, starting at 1.
+    fn backoff(&self, attempt: u32) -> Duration {
+        let exponent = i32::try_from(attempt - 1).unwrap_or(i32::MAX);
+        let backoff = self.initial_backoff.as_secs_f64() * self.multiplier.powi(exponent);
+        Duration::try_from_secs_f64(backoff)
+            .unwrap_or(self.max_backoff)
+            .min(self.max_backoff)
+    }
+}
+
+/// Implementation of [`Subscriber::for_each_with_retry`].
+pub(crate) async fn for_each_with_retry<S, H>(
+    subscriber: &mut S,
+    mut handler: H,
+    policy: RetryPolicy<S::Message>,
+) where
+    S: Subscriber,
+    H: AsyncFnMut(&S::Message) -> Result<()>,
+{
+    let subscriber_name = subscriber.get_name();
+
+    while let Some(message) = subscriber.next_message().await {
+        let mut attempt = 1;
+        loop {
+            // the error is not `Send`, it must not be held across an await point
+            let error = match handler(&message).await {
+                Ok(()) => break,
+                Err(err) => err.to_string(),
+            };
+
+            if attempt >= policy.max_attempts {
+                log::error!(
+                    "[{subscriber_name}]: failed to handle message after {attempt} attempts (err: {error})"
+                );
+                dead_letter(subscriber_name, &policy, message).await;
+                break;
+            }
+
+            let backoff = policy.backoff(attempt);
+            log::warn!(
+                "[{subscriber_name}]: failed to handle message, retrying in {backoff:?} (err: {error})"
+            );
+            policy.timer.sleep(backoff).await;
+            attempt += 1;
+        }
+    }
+}
+
+async fn dead_letter<Message>(
+    subscriber_name: &'static str,
+    policy: &RetryPolicy<Message>,
+    message: Message,
+) where
+    Message: Send + 'static,
+{
+    let Some(dead_letter) = &policy.dead_letter else {
+        log::warn!("[{subscriber_name}]: no dead-letter publisher, dropping message");
+        return;
+    };
+
+    if let Err(err) = dead_letter
+        .publish(message)
+        .await
+        .map_err(|err| err.to_string())
+    {
+        log::error!(
+            "[{subscriber_name}]: failed to publish message to the {} dead-letter publisher (err: {err})",
+            dead_letter.get_name()
+        );
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{Publisher, Result, Subscriber};
+use crate::{Publisher, Result, Subscriber, Topology};
 use futures::{FutureExt, Stream, StreamExt, future::BoxFuture, stream::SelectAll};
 use std::pin::Pin;
 
@@ -14,6 +14,8 @@ where
     name: &'static str,
     /// Combined stream of messages from all subscribed publishers
     messages: SelectAll<Pin<Box<dyn Stream<Item = Message> + Send + Sync + 'static>>>,
+    /// The names of the subscribed publishers, forgotten by the topology on drop
+    publishers: Vec<&'static str>,
 }
 
 impl<Message> SubscriberImpl<Message>
@@ -28,12 +30,29 @@ where
     /// # Returns
     /// A new instance of SubscriberImpl
     pub fn new(name: &'static str) -> Self {
-        let messages = SelectAll::new();
-        Self { name, messages }
+        Self {
+            name,
+            messages: SelectAll::new(),
+            publishers: Vec::new(),
+        }
+    }
+
+    /// Creates a new SubscriberImpl receiving the messages of the given stream, without
+    /// reporting it to the [`crate::Topology`].
+    pub(crate) fn from_stream(
+        name: &'static str,
+        stream: Pin<Box<dyn Stream<Item = Message> + Send + Sync + 'static>>,
+    ) -> Self {
+        let mut subscriber = Self::new(name);
+        subscriber.messages.push(stream);
+        subscriber
     }
 
     /// Subscribes to a publisher to receive its messages.
     ///
+    /// The subscription is reported to the [`crate::Topology`], and forgotten when the
+    /// subscriber is dropped.
+    ///
     /// # Parameters
     /// * `publisher` - A mutable reference to any type implementing PublisherWrapper
     ///
@@ -42,6 +61,8 @@ where
     pub fn subscribe_to(&mut self, publisher: &mut dyn Publisher<Message = Message>) -> Result<()> {
         let stream = publisher.get_message_stream(self.name)?;
         self.messages.push(stream);
+        Topology::record::<Message>(publisher.get_name(), self.name);
+        self.publishers.push(publisher.get_name());
         Ok(())
     }
 
@@ -53,6 +74,48 @@ where
         // TODO: this can panic, find a way to avoid it
         self.messages.select_next_some().await
     }
+
+    /// Asynchronously receives the next message, or `None` once all the subscribed publishers
+    /// are closed or dropped and their queued messages have been received.
+    ///
+    /// Unlike [`SubscriberImpl::receive`], this lets the subscriber stop at the end of the
+    /// streams instead of panicking.
+    pub async fn next_message(&mut self) -> Option<Message> {
+        self.messages.next().await
+    }
+
+    /// Returns the next message if one is immediately available, without waiting.
+    ///
+    /// Unlike [`SubscriberImpl::receive`], this does not panic once all the subscribed
+    /// publishers are dropped, so that the remaining messages can be drained on shutdown.
+    ///
+    /// # Returns
+    /// The next message, or `None` if no message is ready yet
+    pub fn try_receive(&mut self) -> Option<Message> {
+        self.messages.next().now_or_never().flatten()
+    }
+
+    /// Converts the subscriber into the combined stream of the messages of all subscribed
+    /// publishers.
+    ///
+    /// The stream ends once all the subscribed publishers are dropped, or right away if the
+    /// subscriber is not subscribed to any publisher. The subscriptions stay in the
+    /// [`crate::Topology`].
+    pub fn into_stream(mut self) -> Pin<Box<dyn Stream<Item = Message> + Send + Sync + 'static>> {
+        self.publishers.clear();
+        Box::pin(std::mem::take(&mut self.messages))
+    }
+}
+
+impl<Message> Drop for SubscriberImpl<Message>
+where
+    Message: Send + 'static,
+{
+    fn drop(&mut self) {
+        for publisher in &self.publishers {
+            Topology::forget::<Message>(publisher, self.name);
+        }
+    }
 }
 
 /// Implementation of the Subscriber trait for SubscriberImpl
@@ -76,7 +139,15 @@ where
         SubscriberImpl::subscribe_to(self, publisher)
     }
 
-    fn receive(&mut self) -> BoxFuture<Message> {
+    fn receive(&mut self) -> BoxFuture<'_, Message> {
         SubscriberImpl::receive(self).boxed()
     }
+
+    fn try_receive(&mut self) -> Option<Message> {
+        SubscriberImpl::try_receive(self)
+    }
+
+    fn next_message(&mut self) -> BoxFuture<'_, Option<Message>> {
+        SubscriberImpl::next_message(self).boxed()
+    }
 }
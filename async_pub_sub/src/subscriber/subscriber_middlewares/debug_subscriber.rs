@@ -56,7 +56,7 @@ where
         self.subscriber.subscribe_to(publisher)
     }
 
-    fn receive(&mut self) -> BoxFuture<Self::Message> {
+    fn receive(&mut self) -> BoxFuture<'_, Self::Message> {
         let publisher_name = self.publisher_name.expect("publisher name should be known");
         let subscriber_name = self.subscriber.get_name();
 
@@ -72,4 +72,32 @@ where
         }
         .boxed()
     }
+
+    fn try_receive(&mut self) -> Option<Self::Message> {
+        let message = self.subscriber.try_receive()?;
+        log::info!(
+            "[{}] <- [{}]: {:?}",
+            self.subscriber.get_name(),
+            self.publisher_name.expect("publisher name should be known"),
+            message
+        );
+        Some(message)
+    }
+
+    fn next_message(&mut self) -> BoxFuture<'_, Option<Self::Message>> {
+        let publisher_name = self.publisher_name.expect("publisher name should be known");
+        let subscriber_name = self.subscriber.get_name();
+
+        async move {
+            let message = self.subscriber.next_message().await?;
+            log::info!(
+                "[{}] <- [{}]: {:?}",
+                subscriber_name,
+                publisher_name,
+                message
+            );
+            Some(message)
+        }
+        .boxed()
+    }
 }
@@ -0,0 +1,146 @@
+use std::marker::PhantomData;
+
+use bytes::Bytes;
+use futures::{FutureExt, future::BoxFuture};
+
+use crate::{
+    Codec, Layer, Publisher, Result, Subscriber, publisher::encode_stream, utils::StreamPublisher,
+};
+
+/// A subscriber middleware layer that exposes a subscriber of encoded messages as a
+/// subscriber of decoded messages.
+/// The messages received by the wrapped subscriber, typically from a serialized transport,
+/// are decoded with the given codec.
+///
+/// # Type Parameters
+/// * `Message` - The type of the decoded messages
+/// * `C` - The codec used to decode the messages
+pub struct DecodingSubscriberLayer<Message, C> {
+    codec: C,
+    _message: PhantomData<fn() -> Message>,
+}
+
+impl<Message, C> DecodingSubscriberLayer<Message, C> {
+    /// Creates a new layer decoding the messages with the given codec.
+    pub fn new(codec: C) -> Self {
+        Self {
+            codec,
+            _message: PhantomData,
+        }
+    }
+}
+
+impl<S, Message, C> Layer<S> for DecodingSubscriberLayer<Message, C>
+where
+    S: Subscriber<Message = Bytes> + Send,
+    Message: Send + 'static,
+    C: Codec<Message> + Clone + 'static,
+{
+    type LayerType = DecodingSubscriber<S, Message, C>;
+
+    fn layer(&self, subscriber: S) -> Self::LayerType {
+        DecodingSubscriber {
+            subscriber,
+            codec: self.codec.clone(),
+            _message: PhantomData,
+        }
+    }
+}
+
+/// A subscriber wrapper decoding the messages received by an existing subscriber.
+///
+/// Publishers of decoded messages can still be subscribed to, their messages are encoded
+/// before being handed to the wrapped subscriber. Messages that cannot be decoded are logged
+/// and skipped.
+pub struct DecodingSubscriber<S, Message, C> {
+    /// The underlying subscriber being wrapped
+    subscriber: S,
+    /// The codec used to encode and decode the messages
+    codec: C,
+    _message: PhantomData<fn() -> Message>,
+}
+
+impl<S, Message, C> DecodingSubscriber<S, Message, C>
+where
+    S: Subscriber<Message = Bytes> + Send,
+    Message: Send + 'static,
+    C: Codec<Message> + Clone + 'static,
+{
+    /// Decodes a received message, logging and skipping it if it cannot be decoded.
+    fn decode(&self, bytes: &Bytes) -> Option<Message> {
+        self.codec
+            .decode(bytes)
+            .inspect_err(|err| {
+                log::warn!(
+                    "[{}]: failed to decode message, skipping it (err: {})",
+                    self.subscriber.get_name(),
+                    err
+                )
+            })
+            .ok()
+    }
+}
+
+impl<S, Message, C> Subscriber for DecodingSubscriber<S, Message, C>
+where
+    S: Subscriber<Message = Bytes> + Send,
+    Message: Send + 'static,
+    C: Codec<Message> + Clone + 'static,
+{
+    type Message = Message;
+
+    fn get_name(&self) -> &'static str {
+        self.subscriber.get_name()
+    }
+
+    fn subscribe_to(
+        &mut self,
+        publisher: &mut dyn Publisher<Message = Self::Message>,
+    ) -> Result<()> {
+        let publisher_name = publisher.get_name();
+        let stream = publisher.get_message_stream(self.subscriber.get_name())?;
+        let mut publisher = StreamPublisher::new(
+            publisher_name,
+            encode_stream(
+                publisher_name,
+                self.subscriber.get_name(),
+                stream,
+                self.codec.clone(),
+            ),
+        );
+        self.subscriber.subscribe_to(&mut publisher)
+    }
+
+    fn receive(&mut self) -> BoxFuture<'_, Self::Message> {
+        async move {
+            loop {
+                let bytes = self.subscriber.receive().await;
+                if let Some(message) = self.decode(&bytes) {
+                    return message;
+                }
+            }
+        }
+        .boxed()
+    }
+
+    fn try_receive(&mut self) -> Option<Self::Message> {
+        loop {
+            let bytes = self.subscriber.try_receive()?;
+            if let Some(message) = self.decode(&bytes) {
+                return Some(message);
+            }
+        }
+    }
+
+    fn next_message(&mut self) -> BoxFuture<'_, Option<Self::Message>> {
+        async move {
+            loop {
+                let bytes = self.subscriber.next_message().await?;
+                if let Some(message) = self.decode(&bytes) {
+                    return Some(message);
+                }
+            }
+        }
+        .boxed()
+    }
+}
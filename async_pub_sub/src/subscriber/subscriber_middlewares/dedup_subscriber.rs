@@ -0,0 +1,256 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    hash::Hash,
+    marker::PhantomData,
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+    time::{Duration, Instant},
+};
+
+use futures::{FutureExt, future::BoxFuture};
+
+use crate::{Envelope, Layer, Publisher, Result, Subscriber, Timer};
+
+/// A subscriber middleware layer dropping the duplicated messages.
+///
+/// Two messages are duplicates when the key extraction function returns the same key for
+/// both. The keys of the last `capacity` distinct messages are remembered, the least recently
+/// seen key being forgotten first. When a time to live is set with
+/// [`DedupSubscriberLayer::with_ttl`], a key is also forgotten once the time to live has
+/// elapsed since its first message.
+///
+/// The number of suppressed duplicates of all the subscribers wrapped by this layer can be read
+/// through the counter returned by [`DedupSubscriberLayer::suppressed_counter`].
+///
+/// # Type Parameters
+/// * `K` - The type of the message keys
+/// * `F` - The key extraction function
+pub struct DedupSubscriberLayer<K, F> {
+    key_fn: Arc<F>,
+    capacity: usize,
+    ttl: Option<(Duration, Arc<dyn Timer>)>,
+    suppressed: Arc<AtomicUsize>,
+    _key: PhantomData<fn() -> K>,
+}
+
+impl<K, F> DedupSubscriberLayer<K, F> {
+    /// Creates a new deduplication layer.
+    ///
+    /// # Arguments
+    /// * `capacity` - The number of distinct keys remembered
+    /// * `key_fn` - The function extracting the identity of a message
+    ///
+    /// # Panics
+    /// Panics if `capacity` is zero.
+    pub fn new(capacity: usize, key_fn: F) -> Self {
+        assert!(capacity > 0, "the deduplication window must not be empty");
+
+        Self {
+            key_fn: Arc::new(key_fn),
+            capacity,
+            ttl: None,
+            suppressed: Arc::new(AtomicUsize::new(0)),
+            _key: PhantomData,
+        }
+    }
+
+    /// Forgets the keys once `ttl` has elapsed since their first message.
+    ///
+    /// # Arguments
+    /// * `ttl` - The time during which the repeats of a message are dropped
+    /// * `timer` - The timer of the async runtime
+    pub fn with_ttl(self, ttl: Duration, timer: impl Timer) -> Self {
+        Self {
+            ttl: Some((ttl, Arc::new(timer))),
+            ..self
+        }
+    }
+
+    /// Returns the counter of suppressed duplicates, shared by all the subscribers wrapped by
+    /// this layer.
+    pub fn suppressed_counter(&self) -> Arc<AtomicUsize> {
+        self.suppressed.clone()
+    }
+}
+
+impl<Message> DedupSubscriberLayer<u64, fn(&Envelope<Message>) -> u64> {
+    /// Creates a new deduplication layer identifying the messages by the id of their
+    /// [`crate::Metadata`], for subscribers of envelopes.
+    ///
+    /// # Arguments
+    /// * `capacity` - The number of distinct ids remembered
+    ///
+    /// # Panics
+    /// Panics if `capacity` is zero.
+    pub fn by_message_id(capacity: usize) -> Self {
+        Self::new(capacity, |envelope| envelope.metadata().id())
+    }
+}
+
+impl<S, K, F> Layer<S> for DedupSubscriberLayer<K, F>
+where
+    S: Subscriber + Send,
+    K: Hash + Eq + Clone + Send + 'static,
+    F: Fn(&S::Message) -> K + Send + Sync + 'static,
+{
+    type LayerType = DedupSubscriber<S, K, F>;
+
+    fn layer(&self, subscriber: S) -> Self::LayerType {
+        DedupSubscriber {
+            subscriber,
+            key_fn: self.key_fn.clone(),
+            window: DedupWindow {
+                capacity: self.capacity,
+                ttl: self.ttl.clone(),
+                entries: HashMap::new(),
+                recency: BTreeMap::new(),
+                next_rank: 0,
+            },
+            suppressed: self.suppressed.clone(),
+        }
+    }
+}
+
+/// A subscriber wrapper dropping the duplicated messages received by an existing subscriber.
+pub struct DedupSubscriber<S, K, F> {
+    /// The underlying subscriber being wrapped
+    subscriber: S,
+    /// The function extracting the identity of a message
+    key_fn: Arc<F>,
+    /// The keys of the messages already received
+    window: DedupWindow<K>,
+    /// The number of duplicates dropped
+    suppressed: Arc<AtomicUsize>,
+}
+
+impl<S, K, F> DedupSubscriber<S, K, F> {
+    /// Returns the number of duplicates suppressed so far by all the subscribers sharing this
+    /// subscriber's counter.
+    pub fn suppressed_count(&self) -> usize {
+        self.suppressed.load(Ordering::Relaxed)
+    }
+}
+
+impl<S, K, F> DedupSubscriber<S, K, F>
+where
+    S: Subscriber + Send,
+    K: Hash + Eq + Clone + Send + 'static,
+    F: Fn(&S::Message) -> K + Send + Sync + 'static,
+{
+    /// Returns whether a received message is new, counting and logging the duplicates.
+    fn accept(&mut self, message: &S::Message) -> bool {
+        if !self.window.is_duplicate((self.key_fn)(message)) {
+            return true;
+        }
+
+        self.suppressed.fetch_add(1, Ordering::Relaxed);
+        log::debug!(
+            "[{}]: dropping duplicated message",
+            self.subscriber.get_name()
+        );
+        false
+    }
+}
+
+impl<S, K, F> Subscriber for DedupSubscriber<S, K, F>
+where
+    S: Subscriber + Send,
+    K: Hash + Eq + Clone + Send + 'static,
+    F: Fn(&S::Message) -> K + Send + Sync + 'static,
+{
+    type Message = S::Message;
+
+    fn get_name(&self) -> &'static str {
+        self.subscriber.get_name()
+    }
+
+    fn subscribe_to(
+        &mut self,
+        publisher: &mut dyn Publisher<Message = Self::Message>,
+    ) -> Result<()> {
+        self.subscriber.subscribe_to(publisher)
+    }
+
+    fn receive(&mut self) -> BoxFuture<'_, Self::Message> {
+        async move {
+            loop {
+                let message = self.subscriber.receive().await;
+                if self.accept(&message) {
+                    return message;
+                }
+            }
+        }
+        .boxed()
+    }
+
+    fn try_receive(&mut self) -> Option<Self::Message> {
+        loop {
+            let message = self.subscriber.try_receive()?;
+            if self.accept(&message) {
+                return Some(message);
+            }
+        }
+    }
+
+    fn next_message(&mut self) -> BoxFuture<'_, Option<Self::Message>> {
+        async move {
+            loop {
+                let message = self.subscriber.next_message().await?;
+                if self.accept(&message) {
+                    return Some(message);
+                }
+            }
+        }
+        .boxed()
+    }
+}
+
+/// The bounded set of the keys already seen.
+struct DedupWindow<K> {
+    capacity: usize,
+    ttl: Option<(Duration, Arc<dyn Timer>)>,
+    /// The rank in `recency` and the time of the first message of each key
+    entries: HashMap<K, (u64, Option<Instant>)>,
+    /// The keys ordered from the least to the most recently seen
+    recency: BTreeMap<u64, K>,
+    next_rank: u64,
+}
+
+impl<K> DedupWindow<K>
+where
+    K: Hash + Eq + Clone,
+{
+    /// Records the key, returning whether it was already seen.
+    fn is_duplicate(&mut self, key: K) -> bool {
+        let now = self.ttl.as_ref().map(|(_, timer)| timer.now());
+        let rank = self.next_rank;
+        self.next_rank += 1;
+
+        if let Some((previous_rank, first_seen)) = self.entries.get_mut(&key) {
+            self.recency.remove(previous_rank);
+            self.recency.insert(rank, key);
+            *previous_rank = rank;
+
+            let expired = match (&self.ttl, now, *first_seen) {
+                (Some((ttl, _)), Some(now), Some(first_seen)) => now - first_seen >= *ttl,
+                _ => false,
+            };
+            if expired {
+                *first_seen = now;
+            }
+            return !expired;
+        }
+
+        self.entries.insert(key.clone(), (rank, now));
+        self.recency.insert(rank, key);
+        if self.entries.len() > self.capacity
+            && let Some((_, oldest)) = self.recency.pop_first()
+        {
+            self.entries.remove(&oldest);
+        }
+
+        false
+    }
+}
@@ -0,0 +1,70 @@
+use futures::{FutureExt, StreamExt, future::BoxFuture};
+
+use crate::{Envelope, Layer, Publisher, Result, Subscriber, utils::StreamPublisher};
+
+/// A subscriber middleware layer wrapping the received messages in an [`Envelope`].
+///
+/// The layer sits at the edge of a middleware stack: the messages of the subscribed publishers
+/// are wrapped in an envelope whose source is the publisher name, the middlewares below it
+/// handle the envelopes, and the messages are unwrapped again when received.
+pub struct EnvelopeSubscriberLayer;
+
+impl<S, Message> Layer<S> for EnvelopeSubscriberLayer
+where
+    S: Subscriber<Message = Envelope<Message>> + Send,
+    Message: Send + 'static,
+{
+    type LayerType = EnvelopeSubscriber<S>;
+
+    fn layer(&self, subscriber: S) -> Self::LayerType {
+        EnvelopeSubscriber { subscriber }
+    }
+}
+
+/// A subscriber wrapper exposing a subscriber of envelopes as a subscriber of bare messages.
+pub struct EnvelopeSubscriber<S> {
+    /// The underlying subscriber being wrapped
+    subscriber: S,
+}
+
+impl<S, Message> Subscriber for EnvelopeSubscriber<S>
+where
+    S: Subscriber<Message = Envelope<Message>> + Send,
+    Message: Send + 'static,
+{
+    type Message = Message;
+
+    fn get_name(&self) -> &'static str {
+        self.subscriber.get_name()
+    }
+
+    fn subscribe_to(
+        &mut self,
+        publisher: &mut dyn Publisher<Message = Self::Message>,
+    ) -> Result<()> {
+        let publisher_name = publisher.get_name();
+        let stream = publisher.get_message_stream(self.subscriber.get_name())?;
+        let stream = stream.map(move |message| {
+            let mut envelope = Envelope::new(message);
+            envelope.metadata_mut().set_source(publisher_name);
+            envelope
+        });
+        let mut publisher = StreamPublisher::new(publisher_name, Box::pin(stream));
+        self.subscriber.subscribe_to(&mut publisher)
+    }
+
+    fn receive(&mut self) -> BoxFuture<'_, Self::Message> {
+        self.subscriber.receive().map(Envelope::into_inner).boxed()
+    }
+
+    fn try_receive(&mut self) -> Option<Self::Message> {
+        self.subscriber.try_receive().map(Envelope::into_inner)
+    }
+
+    fn next_message(&mut self) -> BoxFuture<'_, Option<Self::Message>> {
+        self.subscriber
+            .next_message()
+            .map(|message| message.map(Envelope::into_inner))
+            .boxed()
+    }
+}
@@ -0,0 +1,82 @@
+use futures::{StreamExt, future::BoxFuture};
+
+use crate::{Layer, MetricsRegistry, Publisher, Result, Subscriber, utils::StreamPublisher};
+
+/// A subscriber middleware layer recording the number of received messages.
+///
+/// The messages are counted in the [`MetricsRegistry`] per publisher, labelled by the name of
+/// the publisher and of the subscriber.
+pub struct MetricsSubscriberLayer {
+    registry: MetricsRegistry,
+}
+
+impl MetricsSubscriberLayer {
+    /// Creates a new layer recording the metrics in the given registry.
+    pub fn new(registry: MetricsRegistry) -> Self {
+        Self { registry }
+    }
+}
+
+impl<S> Layer<S> for MetricsSubscriberLayer
+where
+    S: Subscriber + Send,
+{
+    type LayerType = MetricsSubscriber<S>;
+
+    fn layer(&self, subscriber: S) -> Self::LayerType {
+        MetricsSubscriber {
+            subscriber,
+            registry: self.registry.clone(),
+        }
+    }
+}
+
+/// A subscriber wrapper recording the number of messages received by an existing subscriber.
+pub struct MetricsSubscriber<S> {
+    /// The underlying subscriber being wrapped
+    subscriber: S,
+    /// The registry recording the metrics
+    registry: MetricsRegistry,
+}
+
+impl<S> Subscriber for MetricsSubscriber<S>
+where
+    S: Subscriber + Send,
+{
+    type Message = S::Message;
+
+    fn get_name(&self) -> &'static str {
+        self.subscriber.get_name()
+    }
+
+    fn subscribe_to(
+        &mut self,
+        publisher: &mut dyn Publisher<Message = Self::Message>,
+    ) -> Result<()> {
+        let publisher_name = publisher.get_name();
+        let subscriber_name = self.subscriber.get_name();
+        let stream = publisher.get_message_stream(subscriber_name)?;
+
+        let registry = self.registry.clone();
+        let index = registry.channel_index(publisher_name, subscriber_name);
+        let stream = stream.map(move |message| {
+            registry.received(index);
+            message
+        });
+
+        self.subscriber
+            .subscribe_to(&mut StreamPublisher::new(publisher_name, Box::pin(stream)))
+    }
+
+    fn receive(&mut self) -> BoxFuture<'_, Self::Message> {
+        self.subscriber.receive()
+    }
+
+    fn try_receive(&mut self) -> Option<Self::Message> {
+        self.subscriber.try_receive()
+    }
+
+    fn next_message(&mut self) -> BoxFuture<'_, Option<Self::Message>> {
+        self.subscriber.next_message()
+    }
+}
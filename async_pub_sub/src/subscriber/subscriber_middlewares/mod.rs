@@ -1,7 +1,19 @@
 mod debug_subscriber;
+mod decoding_subscriber;
+mod dedup_subscriber;
+mod envelope_subscriber;
 mod logging_subscriber;
+mod metrics_subscriber;
 mod subscriber_builder;
+#[cfg(feature = "tracing")]
+mod tracing_subscriber;
 
 pub use debug_subscriber::DebuggingSubscriberLayer;
+pub use decoding_subscriber::DecodingSubscriberLayer;
+pub use dedup_subscriber::DedupSubscriberLayer;
+pub use envelope_subscriber::EnvelopeSubscriberLayer;
 pub use logging_subscriber::LoggingSubscriberLayer;
+pub use metrics_subscriber::MetricsSubscriberLayer;
 pub use subscriber_builder::SubscriberBuilder;
+#[cfg(feature = "tracing")]
+pub use tracing_subscriber::TracingSubscriberLayer;
@@ -1,5 +1,5 @@
 use crate::{
-    utils::{IdentityLayer, Layer},
+    utils::{IdentityLayer, Layer, Stack},
     Subscriber,
 };
 
@@ -28,10 +28,15 @@ impl SubscriberBuilder {
 impl<L> SubscriberBuilder<L> {
     /// Adds a middleware layer to the builder.
     ///
+    /// The layers are nested in the order they are added: the first layer added is the
+    /// outermost one, and the last layer added directly wraps the subscriber.
+    ///
     /// # Arguments
     /// * `layer` - The middleware layer to add
-    pub fn layer<NewLayer>(self, layer: NewLayer) -> SubscriberBuilder<NewLayer> {
-        SubscriberBuilder { layer }
+    pub fn layer<NewLayer>(self, layer: NewLayer) -> SubscriberBuilder<Stack<NewLayer, L>> {
+        SubscriberBuilder {
+            layer: Stack::new(layer, self.layer),
+        }
     }
 
     /// Wraps a subscriber with the composed layers.
@@ -0,0 +1,104 @@
+use futures::{FutureExt, future::BoxFuture};
+
+use crate::{
+    Layer, Publisher, Result, Subscriber, Traced,
+    utils::{StreamPublisher, take_over},
+};
+
+/// A subscriber middleware layer opening a tracing span for each received message.
+///
+/// The layer sits at the edge of a middleware stack: the messages of the subscribed publishers
+/// are wrapped in a [`Traced`] envelope carrying the span handed over by a publisher wrapped
+/// with [`crate::TracingPublisherLayer`], or the current span otherwise. The messages are
+/// received within a `receive` span, child of the span of the envelope, and unwrapped again.
+/// [`TracingSubscriber::receive_traced`] returns them along with that span instead, so that
+/// their handling is recorded in their trace.
+pub struct TracingSubscriberLayer;
+
+impl<S, Message> Layer<S> for TracingSubscriberLayer
+where
+    S: Subscriber<Message = Traced<Message>> + Send,
+    Message: Send + 'static,
+{
+    type LayerType = TracingSubscriber<S>;
+
+    fn layer(&self, subscriber: S) -> Self::LayerType {
+        TracingSubscriber { subscriber }
+    }
+}
+
+/// A subscriber wrapper exposing a subscriber of [`Traced`] messages as a subscriber of bare
+/// messages, opening a tracing span for each received message.
+pub struct TracingSubscriber<S> {
+    /// The underlying subscriber being wrapped
+    subscriber: S,
+}
+
+impl<S, Message> TracingSubscriber<S>
+where
+    S: Subscriber<Message = Traced<Message>> + Send,
+    Message: Send + 'static,
+{
+    /// Receives the next message along with its `receive` span.
+    pub async fn receive_traced(&mut self) -> Traced<Message> {
+        let message = self.subscriber.receive().await;
+        self.open_receive_span(message)
+    }
+
+    /// Returns the next message along with its `receive` span if one is immediately
+    /// available, without waiting.
+    pub fn try_receive_traced(&mut self) -> Option<Traced<Message>> {
+        let message = self.subscriber.try_receive()?;
+        Some(self.open_receive_span(message))
+    }
+
+    /// Replaces the span of a received message with a `receive` span, child of it.
+    fn open_receive_span(&self, mut message: Traced<Message>) -> Traced<Message> {
+        let span = tracing::info_span!(
+            parent: message.span(),
+            "receive",
+            subscriber = self.subscriber.get_name(),
+        );
+        span.in_scope(|| tracing::trace!("message received"));
+        message.set_span(span);
+        message
+    }
+}
+
+impl<S, Message> Subscriber for TracingSubscriber<S>
+where
+    S: Subscriber<Message = Traced<Message>> + Send,
+    Message: Send + 'static,
+{
+    type Message = Message;
+
+    fn get_name(&self) -> &'static str {
+        self.subscriber.get_name()
+    }
+
+    fn subscribe_to(
+        &mut self,
+        publisher: &mut dyn Publisher<Message = Self::Message>,
+    ) -> Result<()> {
+        let publisher_name = publisher.get_name();
+        let stream = publisher.get_message_stream(self.subscriber.get_name())?;
+        let mut publisher = StreamPublisher::new(publisher_name, Box::pin(take_over(stream)));
+        self.subscriber.subscribe_to(&mut publisher)
+    }
+
+    fn receive(&mut self) -> BoxFuture<'_, Self::Message> {
+        self.receive_traced().map(Traced::into_inner).boxed()
+    }
+
+    fn try_receive(&mut self) -> Option<Self::Message> {
+        self.try_receive_traced().map(Traced::into_inner)
+    }
+
+    fn next_message(&mut self) -> BoxFuture<'_, Option<Self::Message>> {
+        async move {
+            let message = self.subscriber.next_message().await?;
+            Some(self.open_receive_span(message).into_inner())
+        }
+        .boxed()
+    }
+}
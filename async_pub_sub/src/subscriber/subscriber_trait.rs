@@ -1,8 +1,11 @@
+use futures::FutureExt;
 use futures::future::BoxFuture;
 use std::future::Future;
 use std::ops::{Deref, DerefMut};
 
-use crate::{Publisher, Result};
+use crate::{Publisher, Result, RetryPolicy};
+
+use super::retry;
 
 /// Defines the core functionality for a subscriber in the pub/sub system.
 ///
@@ -34,7 +37,57 @@ pub trait Subscriber {
     ///
     /// # Returns
     /// A Future that resolves to the next message of type Message
-    fn receive(&mut self) -> BoxFuture<Self::Message>;
+    fn receive(&mut self) -> BoxFuture<'_, Self::Message>;
+
+    /// Returns the next message if one is immediately available, without waiting.
+    ///
+    /// Useful to drain the messages still queued when shutting down.
+    ///
+    /// The default polls [`Subscriber::receive`] once. The subscribers wrapping another one
+    /// forward this method to it, and the subscribers whose `receive` cannot be polled once
+    /// their publishers are gone override it.
+    ///
+    /// # Returns
+    /// The next message, or `None` if no message is ready yet
+    fn try_receive(&mut self) -> Option<Self::Message> {
+        self.receive().now_or_never()
+    }
+
+    /// Asynchronously receives the next message, or `None` once all the subscribed publishers
+    /// are closed or dropped and their queued messages have been received.
+    ///
+    /// Unlike [`Subscriber::receive`], this lets the subscriber stop at the end of its streams
+    /// instead of panicking. The default never sees the end, it waits for
+    /// [`Subscriber::receive`]: the subscribers wrapping another one forward this method to
+    /// it, and the subscribers whose streams can end override it.
+    ///
+    /// # Returns
+    /// A Future that resolves to the next message, or to `None` at the end of the streams
+    fn next_message(&mut self) -> BoxFuture<'_, Option<Self::Message>> {
+        self.receive().map(Some).boxed()
+    }
+
+    /// Handles the received messages with a fallible handler, retrying the failed messages.
+    ///
+    /// Failed messages are retried according to the [`RetryPolicy`], and handed to its
+    /// dead-letter publisher once all the attempts have failed. The returned future completes
+    /// once all the subscribed publishers are closed or dropped and their queued messages have
+    /// been handled.
+    ///
+    /// # Parameters
+    /// * `handler` - The async function handling a message
+    /// * `policy` - The retry policy applied to the failed messages
+    fn for_each_with_retry<H>(
+        &mut self,
+        handler: H,
+        policy: RetryPolicy<Self::Message>,
+    ) -> impl Future<Output = ()>
+    where
+        Self: Sized,
+        H: AsyncFnMut(&Self::Message) -> Result<()>,
+    {
+        retry::for_each_with_retry(self, handler, policy)
+    }
 }
 
 // Add blanket implementation for types that can be dereferenced into a Subscriber
@@ -56,9 +109,17 @@ where
         self.deref_mut().subscribe_to(publisher)
     }
 
-    fn receive(&mut self) -> BoxFuture<Self::Message> {
+    fn receive(&mut self) -> BoxFuture<'_, Self::Message> {
         self.deref_mut().receive()
     }
+
+    fn try_receive(&mut self) -> Option<Self::Message> {
+        self.deref_mut().try_receive()
+    }
+
+    fn next_message(&mut self) -> BoxFuture<'_, Option<Self::Message>> {
+        self.deref_mut().next_message()
+    }
 }
 
 /// A wrapper trait that provides a unified interface for working with Subscriber implementations.
@@ -92,6 +153,18 @@ where
     fn receive(&mut self) -> impl Future<Output = Message> {
         Subscriber::receive(self.get_subscriber_mut())
     }
+
+    /// Returns the next message if one is immediately available, without waiting.
+    /// Delegates to the underlying subscriber's try_receive implementation.
+    fn try_receive(&mut self) -> Option<Message> {
+        Subscriber::try_receive(self.get_subscriber_mut())
+    }
+
+    /// Receives the next message asynchronously, or `None` at the end of the streams.
+    /// Delegates to the underlying subscriber's next_message implementation.
+    fn next_message(&mut self) -> impl Future<Output = Option<Message>> {
+        Subscriber::next_message(self.get_subscriber_mut())
+    }
 }
 
 /// Blanket implementation of SubscriberWrapper for any type that implements Subscriber.
@@ -0,0 +1,458 @@
+use std::{
+    any::{Any, TypeId},
+    collections::HashMap,
+    pin::Pin,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, Ordering},
+    },
+};
+
+use futures::{
+    FutureExt, Stream, StreamExt,
+    channel::{mpsc, oneshot},
+    future::{self, BoxFuture, Shared},
+    stream,
+};
+
+use super::sync_stream::sync_stream;
+use crate::{PublishError, Publisher, Result, SubscriberImpl, SubscriberWrapper, Topology};
+
+/// The channels of a [`Bus`], by publisher name and message type.
+type Channels = HashMap<(&'static str, TypeId), Box<dyn Any + Send + Sync>>;
+
+/// The inboxes of a [`Bus`], by subscriber name and message type.
+type Inboxes = HashMap<(&'static str, TypeId), Box<dyn Any + Send + Sync>>;
+
+/// A message stream bound to a subscriber of a [`Bus`].
+type MessageStream<Message> = Pin<Box<dyn Stream<Item = Message> + Send + Sync + 'static>>;
+
+/// A registry of named publishers and subscribers, letting services be wired by name and
+/// message type.
+///
+/// Each publisher of the bus is identified by its name and the type of its messages. A
+/// producer gets its publisher with [`Bus::publisher`], and consumers subscribe to it with
+/// [`Bus::subscribe`], each from their own constructor, without holding a reference to the
+/// producer. The publishers are created on first use, so that producers and consumers can be
+/// created in any order.
+///
+/// Unlike [`crate::PublisherImpl`], a [`BusPublisher`] can be bound to any number of
+/// subscribers, each of them receiving a clone of every message published after its
+/// subscription. Once closed, a publisher is replaced by a new one on the next call to
+/// [`Bus::publisher`] or [`Bus::subscribe`] with its name, the handles of the closed one
+/// staying closed.
+///
+/// The other way around, a consumer registers its subscriber with [`Bus::subscriber`], and
+/// producers bind any publisher to it by name with [`Bus::bind`], without holding a
+/// reference to the consumer. The publishers can be bound before the subscriber is
+/// registered, their messages being received once it is.
+///
+/// # Example
+///
+/// ```
+/// use async_pub_sub::{Bus, BusPublisher, Publisher, SubscriberImpl};
+///
+/// struct Timer {
+///     ticks: BusPublisher<u64>,
+/// }
+///
+/// struct Cache {
+///     ticks: SubscriberImpl<u64>,
+/// }
+///
+/// impl Cache {
+///     fn new(bus: &Bus) -> async_pub_sub::Result<Self> {
+///         let mut ticks = SubscriberImpl::new("Cache");
+///         bus.subscribe::<u64>("Timer", &mut ticks)?;
+///         Ok(Self { ticks })
+///     }
+/// }
+///
+/// # futures::executor::block_on(async {
+/// let bus = Bus::new(10);
+/// let mut cache = Cache::new(&bus).unwrap();
+/// let timer = Timer {
+///     ticks: bus.publisher("Timer"),
+/// };
+///
+/// timer.ticks.publish(42).await.unwrap();
+/// assert_eq!(cache.ticks.receive().await, 42);
+/// # });
+/// ```
+#[derive(Clone)]
+pub struct Bus {
+    buffer_size: usize,
+    channels: Arc<Mutex<Channels>>,
+    inboxes: Arc<Mutex<Inboxes>>,
+}
+
+impl Bus {
+    /// Creates a new bus without publishers.
+    ///
+    /// # Arguments
+    /// * `buffer_size` - The size of the message buffer of each subscription
+    pub fn new(buffer_size: usize) -> Self {
+        Self {
+            buffer_size,
+            channels: Arc::default(),
+            inboxes: Arc::default(),
+        }
+    }
+
+    /// Returns the publisher of the bus with the given name and message type, creating it if
+    /// needed.
+    ///
+    /// All the publishers returned for the same name and message type share the same
+    /// subscribers. A closed publisher is replaced by a new one, without subscribers.
+    ///
+    /// # Type Parameters
+    /// * `Message` - The type of the published messages
+    ///
+    /// # Arguments
+    /// * `name` - The name of the publisher
+    pub fn publisher<Message>(&self, name: &'static str) -> BusPublisher<Message>
+    where
+        Message: Clone + Send + Sync + 'static,
+    {
+        let mut channels = self.channels.lock().expect("bus lock poisoned");
+        let channel = channels
+            .entry((name, TypeId::of::<Message>()))
+            .or_insert_with(|| Box::new(Arc::new(Channel::<Message>::new(name, self.buffer_size))))
+            .downcast_mut::<Arc<Channel<Message>>>()
+            .expect("the channel should match its message type");
+
+        // reopens the publisher, the subscribers of the closed one seeing the end of its stream
+        if channel.is_closed.load(Ordering::SeqCst) {
+            *channel = Arc::new(Channel::new(name, self.buffer_size));
+        }
+
+        BusPublisher {
+            channel: channel.clone(),
+        }
+    }
+
+    /// Subscribes a subscriber to the publisher of the bus with the given name and message
+    /// type, creating the publisher if needed.
+    ///
+    /// The subscription is reported to the [`crate::Topology`].
+    ///
+    /// # Type Parameters
+    /// * `Message` - The type of the received messages
+    ///
+    /// # Arguments
+    /// * `publisher_name` - The name of the publisher to subscribe to
+    /// * `subscriber` - The subscriber receiving the messages
+    pub fn subscribe<Message>(
+        &self,
+        publisher_name: &'static str,
+        subscriber: &mut impl SubscriberWrapper<Message>,
+    ) -> Result<()>
+    where
+        Message: Clone + Send + Sync + 'static,
+    {
+        subscriber.subscribe_to(&mut self.publisher::<Message>(publisher_name))?;
+        Topology::record::<Message>(publisher_name, subscriber.get_name());
+        Ok(())
+    }
+
+    /// Registers a subscriber under the given name and message type, receiving the messages of
+    /// the publishers bound to it with [`Bus::bind`], before or after its registration.
+    ///
+    /// The subscriber keeps waiting for new publishers as long as the bus exists. Once it is
+    /// dropped, a new subscriber can be registered under the same name.
+    ///
+    /// # Type Parameters
+    /// * `Message` - The type of the received messages
+    ///
+    /// # Arguments
+    /// * `name` - The name of the subscriber
+    ///
+    /// # Returns
+    /// An error if a subscriber with the same name and message type is already registered
+    pub fn subscriber<Message>(&self, name: &'static str) -> Result<SubscriberImpl<Message>>
+    where
+        Message: Send + 'static,
+    {
+        let receiver = self
+            .with_inbox::<Message, _>(name, |inbox| inbox.receiver.take())
+            .ok_or_else(|| format!("a subscriber named {name} is already registered on the bus"))?;
+
+        // the messages of each bound publisher are merged as they are bound, the subscriptions
+        // being reported to the topology by `bind`
+        let streams = sync_stream(receiver.flatten_unordered(None));
+        Ok(SubscriberImpl::from_stream(name, streams))
+    }
+
+    /// Binds a publisher to the subscriber of the bus with the given name and message type,
+    /// whether it is already registered or not.
+    ///
+    /// The subscription is reported to the [`crate::Topology`], and forgotten when the
+    /// subscriber is dropped.
+    ///
+    /// # Type Parameters
+    /// * `Message` - The type of the published messages
+    ///
+    /// # Arguments
+    /// * `publisher` - The publisher whose messages are received by the subscriber
+    /// * `subscriber_name` - The name of the subscriber
+    ///
+    /// # Returns
+    /// An error if the publisher cannot be bound, or if the subscriber has been dropped
+    pub fn bind<Message>(
+        &self,
+        publisher: &mut dyn Publisher<Message = Message>,
+        subscriber_name: &'static str,
+    ) -> Result<()>
+    where
+        Message: Send + 'static,
+    {
+        let publisher_name = publisher.get_name();
+        let stream = publisher.get_message_stream(subscriber_name)?;
+
+        Topology::record::<Message>(publisher_name, subscriber_name);
+        let recorded = RecordedSubscription::<Message>::new(publisher_name, subscriber_name);
+        let stream: MessageStream<Message> = Box::pin(stream.map(move |message| {
+            let _ = &recorded;
+            message
+        }));
+
+        self.with_inbox::<Message, _>(subscriber_name, |inbox| inbox.sender.unbounded_send(stream))
+            .map_err(|_| format!("the subscriber {subscriber_name} of the bus has been dropped"))?;
+        Ok(())
+    }
+
+    /// Returns whether the bus has a publisher with the given name and message type.
+    pub fn contains<Message>(&self, name: &'static str) -> bool
+    where
+        Message: 'static,
+    {
+        self.channels
+            .lock()
+            .expect("bus lock poisoned")
+            .contains_key(&(name, TypeId::of::<Message>()))
+    }
+}
+
+impl Bus {
+    /// Runs a function on the inbox of the subscriber with the given name and message type,
+    /// creating the inbox if needed, or replacing it if its subscriber has been dropped.
+    fn with_inbox<Message, T>(
+        &self,
+        name: &'static str,
+        f: impl FnOnce(&mut Inbox<Message>) -> T,
+    ) -> T
+    where
+        Message: Send + 'static,
+    {
+        let mut inboxes = self.inboxes.lock().expect("bus lock poisoned");
+        let inbox = inboxes
+            .entry((name, TypeId::of::<Message>()))
+            .or_insert_with(|| Box::new(Inbox::<Message>::new()))
+            .downcast_mut::<Inbox<Message>>()
+            .expect("the inbox should match its message type");
+
+        if inbox.sender.is_closed() {
+            *inbox = Inbox::new();
+        }
+        f(inbox)
+    }
+}
+
+impl Default for Bus {
+    /// Creates a new bus buffering 16 messages per subscription.
+    fn default() -> Self {
+        Self::new(16)
+    }
+}
+
+/// The streams of the publishers bound to a named subscriber of a [`Bus`].
+struct Inbox<Message> {
+    sender: mpsc::UnboundedSender<MessageStream<Message>>,
+    /// Taken by the subscriber once registered
+    receiver: Option<mpsc::UnboundedReceiver<MessageStream<Message>>>,
+}
+
+impl<Message> Inbox<Message> {
+    fn new() -> Self {
+        let (sender, receiver) = mpsc::unbounded();
+        Self {
+            sender,
+            receiver: Some(receiver),
+        }
+    }
+}
+
+/// A subscription made with [`Bus::bind`], forgotten by the topology once the stream holding
+/// it is dropped along with its subscriber.
+struct RecordedSubscription<Message: 'static> {
+    publisher: &'static str,
+    subscriber: &'static str,
+    message_type: std::marker::PhantomData<fn() -> Message>,
+}
+
+impl<Message> RecordedSubscription<Message> {
+    fn new(publisher: &'static str, subscriber: &'static str) -> Self {
+        Self {
+            publisher,
+            subscriber,
+            message_type: std::marker::PhantomData,
+        }
+    }
+}
+
+impl<Message> Drop for RecordedSubscription<Message> {
+    fn drop(&mut self) {
+        Topology::forget::<Message>(self.publisher, self.subscriber);
+    }
+}
+
+/// A subscription of a [`BusPublisher`].
+struct Subscription<Message> {
+    sender: mpsc::Sender<Message>,
+    /// Completes once the stream of the subscriber has ended or has been dropped
+    drained: Shared<oneshot::Receiver<()>>,
+}
+
+/// A named publisher of a [`Bus`] and its subscriptions.
+struct Channel<Message> {
+    name: &'static str,
+    buffer_size: usize,
+    is_closed: AtomicBool,
+    subscriptions: Mutex<Vec<Subscription<Message>>>,
+}
+
+impl<Message> Channel<Message> {
+    fn new(name: &'static str, buffer_size: usize) -> Self {
+        Self {
+            name,
+            buffer_size,
+            is_closed: AtomicBool::new(false),
+            subscriptions: Mutex::new(Vec::new()),
+        }
+    }
+
+    fn subscriptions(&self) -> std::sync::MutexGuard<'_, Vec<Subscription<Message>>> {
+        self.subscriptions
+            .lock()
+            .expect("bus channel lock poisoned")
+    }
+}
+
+/// A publisher of a [`Bus`], sending a clone of each message to all its subscribers.
+///
+/// The handles of a given name and message type are cheap to clone and share the same
+/// subscribers.
+pub struct BusPublisher<Message> {
+    channel: Arc<Channel<Message>>,
+}
+
+impl<Message> Clone for BusPublisher<Message> {
+    fn clone(&self) -> Self {
+        Self {
+            channel: self.channel.clone(),
+        }
+    }
+}
+
+impl<Message> Publisher for BusPublisher<Message>
+where
+    Message: Clone + Send + Sync + 'static,
+{
+    type Message = Message;
+
+    /// Returns the name of the publisher on the bus.
+    fn get_name(&self) -> &'static str {
+        self.channel.name
+    }
+
+    /// Publishes a clone of the message to each subscriber, failing with a [`PublishError`]
+    /// when no subscriber received it.
+    fn publish(&self, message: Message) -> BoxFuture<'_, Result<()>> {
+        let senders: Vec<_> = self
+            .channel
+            .subscriptions()
+            .iter()
+            .map(|subscription| subscription.sender.clone())
+            .collect();
+
+        async move {
+            let mut delivered = false;
+            for mut sender in senders {
+                if future::poll_fn(|cx| sender.poll_ready(cx)).await.is_ok() {
+                    delivered |= sender.try_send(message.clone()).is_ok();
+                }
+            }
+
+            // forgets the subscribers that have been dropped, the subscriptions of a closed
+            // publisher being kept until they are drained
+            if !self.is_closed() {
+                self.channel
+                    .subscriptions()
+                    .retain(|subscription| !subscription.sender.is_closed());
+            }
+
+            if delivered {
+                Ok(())
+            } else {
+                let reason = if self.is_closed() {
+                    "the publisher is closed"
+                } else {
+                    "no subscriber"
+                };
+                Err(PublishError::new(self.channel.name, reason, message).into())
+            }
+        }
+        .boxed()
+    }
+
+    /// Closes the publisher for all its handles, each subscriber seeing the end of the stream
+    /// once it has received the messages already queued.
+    fn close(&self) {
+        self.channel.is_closed.store(true, Ordering::SeqCst);
+        for subscription in self.channel.subscriptions().iter() {
+            subscription.sender.clone().close_channel();
+        }
+    }
+
+    /// Returns whether the publisher has been closed.
+    fn is_closed(&self) -> bool {
+        self.channel.is_closed.load(Ordering::SeqCst)
+    }
+
+    /// Waits until all the subscribers have drained their queue, or have been dropped.
+    fn closed(&self) -> BoxFuture<'_, ()> {
+        let drained: Vec<_> = self
+            .channel
+            .subscriptions()
+            .iter()
+            .map(|subscription| subscription.drained.clone())
+            .collect();
+
+        future::join_all(drained).map(|_| ()).boxed()
+    }
+
+    /// Adds a subscriber to the publisher, which can be bound to any number of subscribers.
+    ///
+    /// The stream of a closed publisher ends right away.
+    fn get_message_stream(
+        &mut self,
+        _subscriber_name: &'static str,
+    ) -> Result<Pin<Box<dyn Stream<Item = Message> + Send + Sync + 'static>>> {
+        let (mut sender, receiver) = mpsc::channel(self.channel.buffer_size);
+        let (drained_sender, drained) = oneshot::channel();
+
+        if self.is_closed() {
+            sender.close_channel();
+        }
+        self.channel.subscriptions().push(Subscription {
+            sender,
+            drained: drained.shared(),
+        });
+
+        // the sender is dropped once the stream ends or is dropped, completing `closed`
+        let end =
+            stream::once(async move { drop(drained_sender) }).filter_map(|()| future::ready(None));
+
+        Ok(Box::pin(receiver.chain(end)))
+    }
+}
@@ -0,0 +1,167 @@
+use std::pin::Pin;
+
+use futures::{Stream, future::BoxFuture};
+
+use crate::{Forwarder, Layer, Publisher, Result, Subscriber};
+
+/// A value of one of two types, used to select a middleware at runtime.
+///
+/// As a layer, `Either` applies the layer it holds. The resulting `Either` of the two
+/// possible wrapped components implements [`Publisher`], [`Subscriber`] and [`Forwarder`]
+/// when both of them do.
+///
+/// `Option<L>` is also a layer producing an `Either`, wrapping the component with `L` when
+/// set and leaving it untouched otherwise:
+///
+/// ```
+/// use async_pub_sub::{LoggingPublisherLayer, PublisherBuilder, PublisherImpl};
+///
+/// let verbose = false;
+/// let publisher = PublisherBuilder::new()
+///     .layer(verbose.then_some(LoggingPublisherLayer))
+///     .publisher(PublisherImpl::<i32>::new("publisher", 1));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Either<A, B> {
+    /// The first alternative
+    Left(A),
+    /// The second alternative
+    Right(B),
+}
+
+impl<T, A, B> Layer<T> for Either<A, B>
+where
+    A: Layer<T>,
+    B: Layer<T>,
+{
+    type LayerType = Either<A::LayerType, B::LayerType>;
+
+    fn layer(&self, inner: T) -> Self::LayerType {
+        match self {
+            Either::Left(layer) => Either::Left(layer.layer(inner)),
+            Either::Right(layer) => Either::Right(layer.layer(inner)),
+        }
+    }
+}
+
+impl<T, L> Layer<T> for Option<L>
+where
+    L: Layer<T>,
+{
+    type LayerType = Either<L::LayerType, T>;
+
+    fn layer(&self, inner: T) -> Self::LayerType {
+        match self {
+            Some(layer) => Either::Left(layer.layer(inner)),
+            None => Either::Right(inner),
+        }
+    }
+}
+
+impl<A, B> Publisher for Either<A, B>
+where
+    A: Publisher,
+    B: Publisher<Message = A::Message>,
+{
+    type Message = A::Message;
+
+    fn get_name(&self) -> &'static str {
+        match self {
+            Either::Left(publisher) => publisher.get_name(),
+            Either::Right(publisher) => publisher.get_name(),
+        }
+    }
+
+    fn publish(&self, message: Self::Message) -> BoxFuture<'_, Result<()>> {
+        match self {
+            Either::Left(publisher) => publisher.publish(message),
+            Either::Right(publisher) => publisher.publish(message),
+        }
+    }
+
+    fn close(&self) {
+        match self {
+            Either::Left(publisher) => publisher.close(),
+            Either::Right(publisher) => publisher.close(),
+        }
+    }
+
+    fn is_closed(&self) -> bool {
+        match self {
+            Either::Left(publisher) => publisher.is_closed(),
+            Either::Right(publisher) => publisher.is_closed(),
+        }
+    }
+
+    fn closed(&self) -> BoxFuture<'_, ()> {
+        match self {
+            Either::Left(publisher) => publisher.closed(),
+            Either::Right(publisher) => publisher.closed(),
+        }
+    }
+
+    fn get_message_stream(
+        &mut self,
+        subscriber_name: &'static str,
+    ) -> Result<Pin<Box<dyn Stream<Item = Self::Message> + Send + Sync + 'static>>> {
+        match self {
+            Either::Left(publisher) => publisher.get_message_stream(subscriber_name),
+            Either::Right(publisher) => publisher.get_message_stream(subscriber_name),
+        }
+    }
+}
+
+impl<A, B> Subscriber for Either<A, B>
+where
+    A: Subscriber,
+    B: Subscriber<Message = A::Message>,
+{
+    type Message = A::Message;
+
+    fn get_name(&self) -> &'static str {
+        match self {
+            Either::Left(subscriber) => subscriber.get_name(),
+            Either::Right(subscriber) => subscriber.get_name(),
+        }
+    }
+
+    fn subscribe_to(
+        &mut self,
+        publisher: &mut dyn Publisher<Message = Self::Message>,
+    ) -> Result<()> {
+        match self {
+            Either::Left(subscriber) => subscriber.subscribe_to(publisher),
+            Either::Right(subscriber) => subscriber.subscribe_to(publisher),
+        }
+    }
+
+    fn receive(&mut self) -> BoxFuture<'_, Self::Message> {
+        match self {
+            Either::Left(subscriber) => subscriber.receive(),
+            Either::Right(subscriber) => subscriber.receive(),
+        }
+    }
+
+    fn try_receive(&mut self) -> Option<Self::Message> {
+        match self {
+            Either::Left(subscriber) => subscriber.try_receive(),
+            Either::Right(subscriber) => subscriber.try_receive(),
+        }
+    }
+
+    fn next_message(&mut self) -> BoxFuture<'_, Option<Self::Message>> {
+        match self {
+            Either::Left(subscriber) => subscriber.next_message(),
+            Either::Right(subscriber) => subscriber.next_message(),
+        }
+    }
+}
+
+impl<A, B> Forwarder for Either<A, B>
+where
+    A: Forwarder,
+    B: Forwarder
+        + Publisher<Message = <A as Publisher>::Message>
+        + Subscriber<Message = <A as Subscriber>::Message>,
+{
+}
@@ -0,0 +1,209 @@
+use std::{
+    collections::BTreeMap,
+    sync::atomic::{AtomicU64, Ordering},
+    time::SystemTime,
+};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// The identifier of the next created metadata, unique within the process.
+static NEXT_MESSAGE_ID: AtomicU64 = AtomicU64::new(1);
+
+/// The metadata carried along a message by an [`Envelope`].
+///
+/// Every metadata gets a message id, unique within the process, and the time it was created.
+/// The source publisher, the correlation id and the user headers are optional.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Metadata {
+    id: u64,
+    timestamp: SystemTime,
+    source: Option<String>,
+    correlation_id: Option<u64>,
+    headers: BTreeMap<String, String>,
+}
+
+impl Metadata {
+    /// Creates the metadata of a new message, with a fresh id and the current time.
+    pub fn new() -> Self {
+        Self {
+            id: NEXT_MESSAGE_ID.fetch_add(1, Ordering::Relaxed),
+            timestamp: SystemTime::now(),
+            source: None,
+            correlation_id: None,
+            headers: BTreeMap::new(),
+        }
+    }
+
+    /// Returns the id of the message.
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// Returns the time the message was created.
+    pub fn timestamp(&self) -> SystemTime {
+        self.timestamp
+    }
+
+    /// Returns the name of the publisher the message comes from, if known.
+    pub fn source(&self) -> Option<&str> {
+        self.source.as_deref()
+    }
+
+    /// Returns the id correlating the message with others, if any.
+    pub fn correlation_id(&self) -> Option<u64> {
+        self.correlation_id
+    }
+
+    /// Returns the value of the given user header, if set.
+    pub fn header(&self, key: &str) -> Option<&str> {
+        self.headers.get(key).map(String::as_str)
+    }
+
+    /// Returns the user headers.
+    pub fn headers(&self) -> &BTreeMap<String, String> {
+        &self.headers
+    }
+
+    /// Sets the name of the publisher the message comes from.
+    pub fn set_source(&mut self, source: impl Into<String>) {
+        self.source = Some(source.into());
+    }
+
+    /// Sets the id correlating the message with others.
+    pub fn set_correlation_id(&mut self, correlation_id: u64) {
+        self.correlation_id = Some(correlation_id);
+    }
+
+    /// Sets a user header, returning its previous value if any.
+    pub fn insert_header(
+        &mut self,
+        key: impl Into<String>,
+        value: impl Into<String>,
+    ) -> Option<String> {
+        self.headers.insert(key.into(), value.into())
+    }
+
+    /// Removes a user header, returning its value if it was set.
+    pub fn remove_header(&mut self, key: &str) -> Option<String> {
+        self.headers.remove(key)
+    }
+
+    /// Creates the metadata of a reply to this message.
+    ///
+    /// The reply is correlated with the correlation id of this message, or with its id if it
+    /// has none, so that a whole exchange shares the same correlation id.
+    pub fn reply(&self) -> Self {
+        let mut metadata = Self::new();
+        metadata.correlation_id = Some(self.correlation_id.unwrap_or(self.id));
+        metadata
+    }
+}
+
+impl Default for Metadata {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A message envelope carrying the [`Metadata`] of the message.
+///
+/// The envelope gives the middlewares a common place to read and write the message id,
+/// timestamp, source publisher, correlation id and user headers. The
+/// [`crate::EnvelopePublisherLayer`] and [`crate::EnvelopeSubscriberLayer`] wrap and unwrap
+/// the messages at the edges of a middleware stack, so that the rest of the application keeps
+/// handling bare messages.
+///
+/// # Example
+///
+/// ```
+/// use async_pub_sub::Envelope;
+///
+/// let request = Envelope::new("ping").with_header("priority", "high");
+/// assert_eq!(request.metadata().header("priority"), Some("high"));
+///
+/// // the reply is correlated with the request
+/// let reply = request.reply("pong");
+/// assert_eq!(reply.metadata().correlation_id(), Some(request.metadata().id()));
+/// assert_eq!(reply.into_inner(), "pong");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Envelope<Message> {
+    metadata: Metadata,
+    message: Message,
+}
+
+impl<Message> Envelope<Message> {
+    /// Wraps a message in an envelope with new metadata.
+    pub fn new(message: Message) -> Self {
+        Self::with_metadata(message, Metadata::new())
+    }
+
+    /// Wraps a message in an envelope with the given metadata.
+    pub fn with_metadata(message: Message, metadata: Metadata) -> Self {
+        Self { metadata, message }
+    }
+
+    /// Sets the id correlating the message with others.
+    pub fn with_correlation_id(mut self, correlation_id: u64) -> Self {
+        self.metadata.set_correlation_id(correlation_id);
+        self
+    }
+
+    /// Sets a user header.
+    pub fn with_header(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.metadata.insert_header(key, value);
+        self
+    }
+
+    /// Returns the metadata of the message.
+    pub fn metadata(&self) -> &Metadata {
+        &self.metadata
+    }
+
+    /// Returns the metadata of the message, mutably.
+    pub fn metadata_mut(&mut self) -> &mut Metadata {
+        &mut self.metadata
+    }
+
+    /// Returns the message.
+    pub fn message(&self) -> &Message {
+        &self.message
+    }
+
+    /// Returns the message, mutably.
+    pub fn message_mut(&mut self) -> &mut Message {
+        &mut self.message
+    }
+
+    /// Returns the message, dropping its metadata.
+    pub fn into_inner(self) -> Message {
+        self.message
+    }
+
+    /// Returns the message and its metadata.
+    pub fn into_parts(self) -> (Message, Metadata) {
+        (self.message, self.metadata)
+    }
+
+    /// Converts the message, keeping its metadata.
+    pub fn map<Output>(self, f: impl FnOnce(Message) -> Output) -> Envelope<Output> {
+        Envelope {
+            metadata: self.metadata,
+            message: f(self.message),
+        }
+    }
+
+    /// Wraps a reply to this message in an envelope correlated with it.
+    pub fn reply<Reply>(&self, reply: Reply) -> Envelope<Reply> {
+        Envelope::with_metadata(reply, self.metadata.reply())
+    }
+}
+
+impl<Message> From<Message> for Envelope<Message> {
+    fn from(message: Message) -> Self {
+        Self::new(message)
+    }
+}
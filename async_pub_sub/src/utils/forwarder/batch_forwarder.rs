@@ -0,0 +1,94 @@
+use std::{pin::Pin, time::Duration};
+
+use futures::{FutureExt, Stream, StreamExt, select_biased, stream};
+
+use crate::{Timer, utils::sync_stream::sync_stream};
+
+use super::{StreamTransform, TransformForwarder};
+
+/// A forwarder grouping the messages it receives into batches.
+///
+/// A batch is forwarded once it holds `max_size` messages, or once `max_delay` has elapsed
+/// since its first message was received, whichever comes first. Empty batches are never
+/// forwarded.
+///
+/// # Type Parameters
+/// * `Message` - The type of message being batched
+/// * `T` - The timer used to measure the delay
+pub type BatchForwarder<Message, T> = TransformForwarder<Message, BatchTransform<T>>;
+
+/// The transform of a [`BatchForwarder`], grouping the messages into batches.
+pub struct BatchTransform<T> {
+    /// The maximum number of messages in a batch
+    max_size: usize,
+    /// The maximum delay between the reception of the first message of a batch and the
+    /// forwarding of the batch
+    max_delay: Duration,
+    /// The timer used to measure the delay
+    timer: T,
+}
+
+impl<Message, T> BatchForwarder<Message, T>
+where
+    Message: Send + 'static,
+    T: Timer + Clone,
+{
+    /// Creates a new BatchForwarder with the specified name.
+    ///
+    /// # Arguments
+    /// * `name` - A static string identifier for this forwarder instance
+    /// * `max_size` - The maximum number of messages in a batch
+    /// * `max_delay` - The maximum delay before forwarding a batch
+    /// * `timer` - The timer of the async runtime
+    ///
+    /// # Panics
+    /// Panics if `max_size` is zero.
+    pub fn new(name: &'static str, max_size: usize, max_delay: Duration, timer: T) -> Self {
+        assert!(max_size > 0, "the batch size must be strictly positive");
+
+        Self::with_transform(
+            name,
+            BatchTransform {
+                max_size,
+                max_delay,
+                timer,
+            },
+        )
+    }
+}
+
+impl<Message, T> StreamTransform<Message> for BatchTransform<T>
+where
+    Message: Send + 'static,
+    T: Timer + Clone,
+{
+    type Out = Vec<Message>;
+
+    fn transform(
+        &self,
+        stream: Pin<Box<dyn Stream<Item = Message> + Send + Sync + 'static>>,
+    ) -> Pin<Box<dyn Stream<Item = Vec<Message>> + Send + Sync + 'static>> {
+        let input = stream.fuse();
+        let state = (input, self.timer.clone(), self.max_size, self.max_delay);
+
+        sync_stream(stream::unfold(
+            state,
+            |(mut input, timer, max_size, max_delay)| async move {
+                let mut batch = vec![input.next().await?];
+                let mut max_delay_elapsed = timer.sleep(max_delay).fuse();
+
+                while batch.len() < max_size {
+                    select_biased! {
+                        message = input.next() => match message {
+                            Some(message) => batch.push(message),
+                            None => break,
+                        },
+                        () = max_delay_elapsed => break,
+                    }
+                }
+
+                Some((batch, (input, timer, max_size, max_delay)))
+            },
+        ))
+    }
+}
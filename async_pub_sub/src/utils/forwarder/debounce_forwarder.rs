@@ -0,0 +1,84 @@
+use std::{pin::Pin, time::Duration};
+
+use futures::{FutureExt, Stream, StreamExt, select_biased, stream};
+
+use crate::{Timer, utils::sync_stream::sync_stream};
+
+use super::{StreamTransform, TransformForwarder};
+
+/// A forwarder only forwarding the last message of a burst.
+///
+/// A received message is forwarded once no other message has been received for the quiet
+/// period, otherwise it is replaced by the newer message.
+///
+/// # Type Parameters
+/// * `Message` - The type of message being forwarded
+/// * `T` - The timer used to measure the quiet period
+pub type DebounceForwarder<Message, T> = TransformForwarder<Message, DebounceTransform<T>>;
+
+/// The transform of a [`DebounceForwarder`], only keeping the last message of a burst.
+pub struct DebounceTransform<T> {
+    /// The duration without messages after which the last message is forwarded
+    quiet_period: Duration,
+    /// The timer used to measure the quiet period
+    timer: T,
+}
+
+impl<Message, T> DebounceForwarder<Message, T>
+where
+    Message: Send + 'static,
+    T: Timer + Clone,
+{
+    /// Creates a new DebounceForwarder with the specified name.
+    ///
+    /// # Arguments
+    /// * `name` - A static string identifier for this forwarder instance
+    /// * `quiet_period` - The duration without messages after which the last message is
+    ///   forwarded
+    /// * `timer` - The timer of the async runtime
+    pub fn new(name: &'static str, quiet_period: Duration, timer: T) -> Self {
+        Self::with_transform(
+            name,
+            DebounceTransform {
+                quiet_period,
+                timer,
+            },
+        )
+    }
+}
+
+impl<Message, T> StreamTransform<Message> for DebounceTransform<T>
+where
+    Message: Send + 'static,
+    T: Timer + Clone,
+{
+    type Out = Message;
+
+    fn transform(
+        &self,
+        stream: Pin<Box<dyn Stream<Item = Message> + Send + Sync + 'static>>,
+    ) -> Pin<Box<dyn Stream<Item = Message> + Send + Sync + 'static>> {
+        let input = stream.fuse();
+        let state = (input, self.timer.clone(), self.quiet_period);
+
+        sync_stream(stream::unfold(
+            state,
+            |(mut input, timer, quiet_period)| async move {
+                let mut pending = input.next().await?;
+
+                loop {
+                    let mut quiet_period_elapsed = timer.sleep(quiet_period).fuse();
+                    select_biased! {
+                        message = input.next() => match message {
+                            Some(message) => pending = message,
+                            None => break,
+                        },
+                        () = quiet_period_elapsed => break,
+                    }
+                }
+
+                Some((pending, (input, timer, quiet_period)))
+            },
+        ))
+    }
+}
@@ -0,0 +1,87 @@
+use std::{
+    pin::Pin,
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+};
+
+use futures::Stream;
+
+use super::{StreamTransform, TransformForwarder, filter_map_forwarder::filter_map_stream};
+
+/// A forwarder only forwarding the messages matching a predicate.
+///
+/// Messages for which the predicate returns `false` are dropped, the number of dropped
+/// messages can be read with [`FilterForwarder::dropped_count`], or through the counter
+/// returned by [`FilterForwarder::dropped_counter`] once the forwarder has been wrapped in
+/// middleware layers.
+///
+/// # Type Parameters
+/// * `Message` - The type of message being forwarded
+/// * `F` - The predicate selecting the messages to forward
+pub type FilterForwarder<Message, F> = TransformForwarder<Message, FilterTransform<F>>;
+
+/// The transform of a [`FilterForwarder`], dropping the messages not matching a predicate.
+pub struct FilterTransform<F> {
+    /// The predicate selecting the messages to forward
+    predicate: Arc<F>,
+    /// The number of messages dropped by the predicate
+    dropped: Arc<AtomicUsize>,
+}
+
+impl<Message, F> FilterForwarder<Message, F>
+where
+    Message: Send + 'static,
+    F: Fn(&Message) -> bool + Send + Sync + 'static,
+{
+    /// Creates a new FilterForwarder with the specified name.
+    ///
+    /// # Arguments
+    /// * `name` - A static string identifier for this forwarder instance
+    /// * `predicate` - The predicate returning `true` for the messages to forward
+    pub fn new(name: &'static str, predicate: F) -> Self {
+        Self::with_transform(
+            name,
+            FilterTransform {
+                predicate: Arc::new(predicate),
+                dropped: Arc::new(AtomicUsize::new(0)),
+            },
+        )
+    }
+
+    /// Returns the number of messages dropped so far.
+    pub fn dropped_count(&self) -> usize {
+        self.transform().dropped.load(Ordering::Relaxed)
+    }
+
+    /// Returns the counter of dropped messages, which stays readable after the forwarder
+    /// has been moved.
+    pub fn dropped_counter(&self) -> Arc<AtomicUsize> {
+        self.transform().dropped.clone()
+    }
+}
+
+impl<Message, F> StreamTransform<Message> for FilterTransform<F>
+where
+    Message: Send + 'static,
+    F: Fn(&Message) -> bool + Send + Sync + 'static,
+{
+    type Out = Message;
+
+    fn transform(
+        &self,
+        stream: Pin<Box<dyn Stream<Item = Message> + Send + Sync + 'static>>,
+    ) -> Pin<Box<dyn Stream<Item = Message> + Send + Sync + 'static>> {
+        let predicate = self.predicate.clone();
+        let dropped = self.dropped.clone();
+
+        filter_map_stream(stream, move |message| {
+            let keep = predicate(&message);
+            if !keep {
+                dropped.fetch_add(1, Ordering::Relaxed);
+            }
+            keep.then_some(message)
+        })
+    }
+}
@@ -0,0 +1,118 @@
+use std::{
+    marker::PhantomData,
+    pin::Pin,
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+};
+
+use futures::{Stream, StreamExt, stream};
+
+use super::{StreamTransform, TransformForwarder};
+
+/// A forwarder converting the messages it receives, dropping the ones that cannot be
+/// converted.
+///
+/// Messages for which the closure returns `None` are dropped, the number of dropped
+/// messages can be read with [`FilterMapForwarder::dropped_count`], or through the counter
+/// returned by [`FilterMapForwarder::dropped_counter`] once the forwarder has been wrapped in
+/// middleware layers.
+///
+/// # Type Parameters
+/// * `In` - The type of message received by the forwarder
+/// * `Out` - The type of message forwarded to the subscriber
+/// * `F` - The closure converting the messages
+pub type FilterMapForwarder<In, Out, F> = TransformForwarder<In, FilterMapTransform<Out, F>>;
+
+/// The transform of a [`FilterMapForwarder`], converting each message with a closure and
+/// dropping the ones that cannot be converted.
+pub struct FilterMapTransform<Out, F> {
+    /// The closure converting the messages
+    f: Arc<F>,
+    /// The number of messages that could not be converted
+    dropped: Arc<AtomicUsize>,
+    _out: PhantomData<fn() -> Out>,
+}
+
+impl<In, Out, F> FilterMapForwarder<In, Out, F>
+where
+    In: Send + 'static,
+    Out: Send + 'static,
+    F: Fn(In) -> Option<Out> + Send + Sync + 'static,
+{
+    /// Creates a new FilterMapForwarder with the specified name.
+    ///
+    /// # Arguments
+    /// * `name` - A static string identifier for this forwarder instance
+    /// * `f` - The closure converting the received messages, or returning `None` for the
+    ///   messages to drop
+    pub fn new(name: &'static str, f: F) -> Self {
+        Self::with_transform(
+            name,
+            FilterMapTransform {
+                f: Arc::new(f),
+                dropped: Arc::new(AtomicUsize::new(0)),
+                _out: PhantomData,
+            },
+        )
+    }
+
+    /// Returns the number of messages dropped so far.
+    pub fn dropped_count(&self) -> usize {
+        self.transform().dropped.load(Ordering::Relaxed)
+    }
+
+    /// Returns the counter of dropped messages, which stays readable after the forwarder
+    /// has been moved.
+    pub fn dropped_counter(&self) -> Arc<AtomicUsize> {
+        self.transform().dropped.clone()
+    }
+}
+
+impl<In, Out, F> StreamTransform<In> for FilterMapTransform<Out, F>
+where
+    In: Send + 'static,
+    Out: Send + 'static,
+    F: Fn(In) -> Option<Out> + Send + Sync + 'static,
+{
+    type Out = Out;
+
+    fn transform(
+        &self,
+        stream: Pin<Box<dyn Stream<Item = In> + Send + Sync + 'static>>,
+    ) -> Pin<Box<dyn Stream<Item = Out> + Send + Sync + 'static>> {
+        let f = self.f.clone();
+        let dropped = self.dropped.clone();
+
+        filter_map_stream(stream, move |message| {
+            let converted = f(message);
+            if converted.is_none() {
+                dropped.fetch_add(1, Ordering::Relaxed);
+            }
+            converted
+        })
+    }
+}
+
+/// Applies a partial mapping to a message stream.
+///
+/// Unlike [`StreamExt::filter_map`], the resulting stream does not store the messages being
+/// filtered, so it stays `Sync` for messages that are not.
+pub(crate) fn filter_map_stream<In, Out>(
+    stream: Pin<Box<dyn Stream<Item = In> + Send + Sync + 'static>>,
+    f: impl Fn(In) -> Option<Out> + Send + Sync + 'static,
+) -> Pin<Box<dyn Stream<Item = Out> + Send + Sync + 'static>>
+where
+    In: Send + 'static,
+    Out: Send + 'static,
+{
+    Box::pin(stream::unfold((stream, f), |(mut stream, f)| async move {
+        loop {
+            let message = stream.next().await?;
+            if let Some(converted) = f(message) {
+                return Some((converted, (stream, f)));
+            }
+        }
+    }))
+}
@@ -1,7 +1,7 @@
 use std::pin::Pin;
 
-use crate::{Publisher, Result, Subscriber, SubscriberImpl};
-use futures::{FutureExt, Stream, future::BoxFuture, stream};
+use crate::{Publisher, Result, Subscriber, SubscriberImpl, utils::StreamCloser};
+use futures::{FutureExt, Stream, future::BoxFuture};
 
 use super::Forwarder;
 
@@ -22,6 +22,8 @@ where
     subscriber_name: Option<&'static str>,
     /// The internal subscriber implementation used to receive messages
     subscriber: Option<SubscriberImpl<Message>>,
+    /// Ends the forwarded stream once the forwarder is closed
+    closer: StreamCloser,
 }
 
 impl<Message> ForwarderImpl<Message>
@@ -37,6 +39,7 @@ where
             name,
             subscriber_name: None,
             subscriber: Some(SubscriberImpl::new(name)),
+            closer: StreamCloser::new(),
         }
     }
 }
@@ -79,7 +82,7 @@ where
     }
 
     /// Not implemented for LoggingForwarder. Will panic if called.
-    fn receive(&mut self) -> BoxFuture<Message> {
+    fn receive(&mut self) -> BoxFuture<'_, Message> {
         panic!("LoggingForwarder does not implement receive method")
     }
 }
@@ -96,10 +99,27 @@ where
     }
 
     /// Not implemented for LoggingForwarder. Will panic if called.
-    fn publish(&self, _message: Message) -> futures::future::BoxFuture<Result<()>> {
+    fn publish(&self, _message: Message) -> futures::future::BoxFuture<'_, Result<()>> {
         async move { panic!("LoggingForwarder does not implement publish method") }.boxed()
     }
 
+    /// Stops forwarding, the subscriber seeing the end of the stream right away as the
+    /// forwarder does not queue the messages.
+    fn close(&self) {
+        self.closer.close()
+    }
+
+    /// Returns whether the forwarder has been closed, or its subscriber dropped.
+    fn is_closed(&self) -> bool {
+        self.closer.is_closed()
+    }
+
+    /// Waits until the forwarded stream has ended or has been dropped, completing right away
+    /// once closed if no subscriber was bound.
+    fn closed(&self) -> BoxFuture<'_, ()> {
+        self.closer.closed()
+    }
+
     /// Creates a message stream that logs messages as they pass through.
     ///
     /// # Arguments
@@ -123,15 +143,8 @@ where
         };
         self.subscriber_name = Some(subscriber_name);
 
-        let stream = Box::pin(stream::unfold(
-            subscriber,
-            move |mut subscriber| async move {
-                let message = subscriber.receive().await;
-                Some((message, subscriber))
-            },
-        ));
-
-        Ok(stream)
+        // ends once the publishers are dropped, instead of panicking in `receive`
+        Ok(self.closer.wrap(subscriber.into_stream()))
     }
 }
 
@@ -56,7 +56,7 @@ where
     /// * `message` - The message to publish
     ///
     /// Logs the message in the format: "[publisher_name] -> [subscriber_name]: message_debug_format"
-    fn publish(&self, message: Self::Message) -> BoxFuture<Result<()>> {
+    fn publish(&self, message: Self::Message) -> BoxFuture<'_, Result<()>> {
         async move {
             let message_str = format!("{:?}", &message);
             let result = self.forwarder.publish(message).await;
@@ -72,6 +72,18 @@ where
         .boxed()
     }
 
+    fn close(&self) {
+        Publisher::close(&self.forwarder)
+    }
+
+    fn is_closed(&self) -> bool {
+        Publisher::is_closed(&self.forwarder)
+    }
+
+    fn closed(&self) -> BoxFuture<'_, ()> {
+        Publisher::closed(&self.forwarder)
+    }
+
     /// Gets a message stream for the given subscriber name.
     /// Stores the subscriber name for use in debug logging.
     ///
@@ -121,9 +133,17 @@ where
         self.forwarder.subscribe_to(publisher)
     }
 
-    fn receive(&mut self) -> BoxFuture<Self::Message> {
+    fn receive(&mut self) -> BoxFuture<'_, Self::Message> {
         self.forwarder.receive()
     }
+
+    fn try_receive(&mut self) -> Option<Self::Message> {
+        self.forwarder.try_receive()
+    }
+
+    fn next_message(&mut self) -> BoxFuture<'_, Option<Self::Message>> {
+        self.forwarder.next_message()
+    }
 }
 
 impl<F> Forwarder for DebugForwarder<F>
@@ -1,6 +1,6 @@
 use crate::{
     Layer,
-    utils::{IdentityLayer, forwarder::Forwarder},
+    utils::{IdentityLayer, Stack, forwarder::Forwarder},
 };
 
 /// A builder pattern implementation for constructing a forwarder with middleware layers.
@@ -28,10 +28,15 @@ impl ForwarderBuilder {
 impl<L> ForwarderBuilder<L> {
     /// Adds a middleware layer to the builder.
     ///
+    /// The layers are nested in the order they are added: the first layer added is the
+    /// outermost one, and the last layer added directly wraps the forwarder.
+    ///
     /// # Arguments
     /// * `layer` - The middleware layer to add
-    pub fn layer<NewLayer>(self, layer: NewLayer) -> ForwarderBuilder<NewLayer> {
-        ForwarderBuilder { layer }
+    pub fn layer<NewLayer>(self, layer: NewLayer) -> ForwarderBuilder<Stack<NewLayer, L>> {
+        ForwarderBuilder {
+            layer: Stack::new(layer, self.layer),
+        }
     }
 
     /// Wraps a forwarder with the composed layers.
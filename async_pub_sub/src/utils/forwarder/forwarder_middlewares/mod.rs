@@ -1,6 +1,10 @@
 mod forwarder_builder;
 
 mod debug_forwarder;
+#[cfg(feature = "tracing")]
+mod tracing_forwarder;
 
 pub use debug_forwarder::DebuggingForwarderLayer;
 pub use forwarder_builder::ForwarderBuilder;
+#[cfg(feature = "tracing")]
+pub use tracing_forwarder::TracingForwarderLayer;
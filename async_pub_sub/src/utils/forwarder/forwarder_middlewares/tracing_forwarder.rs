@@ -0,0 +1,145 @@
+use std::pin::Pin;
+
+use futures::{FutureExt, Stream, StreamExt, future::BoxFuture};
+
+use crate::{
+    Layer, Publisher, Result, Subscriber, Traced,
+    error::map_undeliverable,
+    utils::{StreamPublisher, forwarder::Forwarder, hand_over, take_over},
+};
+
+/// A forwarder middleware layer opening a tracing span for each forwarded message.
+///
+/// Like [`crate::TracingSubscriberLayer`] and [`crate::TracingPublisherLayer`], the layer
+/// wraps the received messages in a [`Traced`] envelope carrying the span handed over by
+/// their publisher, and unwraps the forwarded messages, handing their span over to their
+/// subscriber. The `forward` span is a child of the span of the envelope, and replaces it in
+/// the envelope. Forwarders converting messages should keep their span, with [`Traced::map`]
+/// for instance.
+pub struct TracingForwarderLayer;
+
+impl<F, Input, Output> Layer<F> for TracingForwarderLayer
+where
+    F: Forwarder + Subscriber<Message = Traced<Input>> + Publisher<Message = Traced<Output>>,
+    Input: Send + 'static,
+    Output: Send + 'static,
+{
+    type LayerType = TracingForwarder<F>;
+
+    fn layer(&self, forwarder: F) -> Self::LayerType {
+        TracingForwarder { forwarder }
+    }
+}
+
+/// A forwarder wrapper exposing a forwarder of [`Traced`] messages as a forwarder of bare
+/// messages, opening a tracing span for each forwarded message.
+pub struct TracingForwarder<F> {
+    /// The underlying forwarder being wrapped
+    forwarder: F,
+}
+
+impl<F, Output> Publisher for TracingForwarder<F>
+where
+    F: Forwarder + Publisher<Message = Traced<Output>>,
+    Output: Send + 'static,
+    Self: Sync,
+{
+    type Message = Output;
+
+    /// Returns the name of the underlying forwarder
+    fn get_name(&self) -> &'static str {
+        Publisher::get_name(&self.forwarder)
+    }
+
+    /// Publishes a message within the current span
+    fn publish(&self, message: Self::Message) -> BoxFuture<'_, Result<()>> {
+        self.forwarder
+            .publish(Traced::new(message))
+            .map(|result| {
+                result.map_err(|err| map_undeliverable(err, Traced::<Output>::into_inner))
+            })
+            .boxed()
+    }
+
+    fn close(&self) {
+        Publisher::close(&self.forwarder)
+    }
+
+    fn is_closed(&self) -> bool {
+        Publisher::is_closed(&self.forwarder)
+    }
+
+    fn closed(&self) -> BoxFuture<'_, ()> {
+        Publisher::closed(&self.forwarder)
+    }
+
+    /// Gets the stream of the forwarded messages, each one forwarded within a `forward` span,
+    /// child of the span of the message.
+    ///
+    /// # Arguments
+    /// * `subscriber_name` - The name of the subscriber requesting the stream
+    fn get_message_stream(
+        &mut self,
+        subscriber_name: &'static str,
+    ) -> Result<Pin<Box<dyn Stream<Item = Self::Message> + Send + Sync + 'static>>> {
+        let forwarder_name = Publisher::get_name(&self.forwarder);
+
+        let stream = self.forwarder.get_message_stream(subscriber_name)?;
+        Ok(Box::pin(stream.map(move |mut message| {
+            let span = tracing::info_span!(
+                parent: message.span(),
+                "forward",
+                forwarder = forwarder_name,
+                subscriber = subscriber_name,
+            );
+            span.in_scope(|| tracing::trace!("forwarding message"));
+            message.set_span(span);
+            hand_over(message)
+        })))
+    }
+}
+
+impl<F, Input> Subscriber for TracingForwarder<F>
+where
+    F: Forwarder + Subscriber<Message = Traced<Input>>,
+    Input: Send + 'static,
+{
+    type Message = Input;
+
+    fn get_name(&self) -> &'static str {
+        Subscriber::get_name(&self.forwarder)
+    }
+
+    fn subscribe_to(
+        &mut self,
+        publisher: &mut dyn Publisher<Message = Self::Message>,
+    ) -> Result<()> {
+        let publisher_name = publisher.get_name();
+        let stream = publisher.get_message_stream(Subscriber::get_name(&self.forwarder))?;
+        let mut publisher = StreamPublisher::new(publisher_name, Box::pin(take_over(stream)));
+        self.forwarder.subscribe_to(&mut publisher)
+    }
+
+    fn receive(&mut self) -> BoxFuture<'_, Self::Message> {
+        self.forwarder.receive().map(Traced::into_inner).boxed()
+    }
+
+    fn try_receive(&mut self) -> Option<Self::Message> {
+        self.forwarder.try_receive().map(Traced::into_inner)
+    }
+
+    fn next_message(&mut self) -> BoxFuture<'_, Option<Self::Message>> {
+        self.forwarder
+            .next_message()
+            .map(|message| message.map(Traced::into_inner))
+            .boxed()
+    }
+}
+
+impl<F, Input, Output> Forwarder for TracingForwarder<F>
+where
+    F: Forwarder + Subscriber<Message = Traced<Input>> + Publisher<Message = Traced<Output>> + Sync,
+    Input: Send + 'static,
+    Output: Send + 'static,
+{
+}
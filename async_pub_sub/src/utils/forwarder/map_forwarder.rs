@@ -0,0 +1,63 @@
+use std::{marker::PhantomData, pin::Pin, sync::Arc};
+
+use futures::{Stream, StreamExt};
+
+use super::{StreamTransform, TransformForwarder};
+
+/// A forwarder converting the messages it receives into messages of another type.
+///
+/// The forwarder subscribes to publishers of `In` messages and publishes the result of the
+/// given closure to its subscriber, so that the output of a component can be adapted to the
+/// input of another one.
+///
+/// # Type Parameters
+/// * `In` - The type of message received by the forwarder
+/// * `Out` - The type of message forwarded to the subscriber
+/// * `F` - The closure converting the messages
+pub type MapForwarder<In, Out, F> = TransformForwarder<In, MapTransform<Out, F>>;
+
+/// The transform of a [`MapForwarder`], converting each message with a closure.
+pub struct MapTransform<Out, F> {
+    /// The closure converting the messages
+    f: Arc<F>,
+    _out: PhantomData<fn() -> Out>,
+}
+
+impl<In, Out, F> MapForwarder<In, Out, F>
+where
+    In: Send + 'static,
+    Out: Send + 'static,
+    F: Fn(In) -> Out + Send + Sync + 'static,
+{
+    /// Creates a new MapForwarder with the specified name.
+    ///
+    /// # Arguments
+    /// * `name` - A static string identifier for this forwarder instance
+    /// * `f` - The closure converting the received messages
+    pub fn new(name: &'static str, f: F) -> Self {
+        Self::with_transform(
+            name,
+            MapTransform {
+                f: Arc::new(f),
+                _out: PhantomData,
+            },
+        )
+    }
+}
+
+impl<In, Out, F> StreamTransform<In> for MapTransform<Out, F>
+where
+    In: Send + 'static,
+    Out: Send + 'static,
+    F: Fn(In) -> Out + Send + Sync + 'static,
+{
+    type Out = Out;
+
+    fn transform(
+        &self,
+        stream: Pin<Box<dyn Stream<Item = In> + Send + Sync + 'static>>,
+    ) -> Pin<Box<dyn Stream<Item = Out> + Send + Sync + 'static>> {
+        let f = self.f.clone();
+        Box::pin(stream.map(move |message| f(message)))
+    }
+}
@@ -1,7 +1,25 @@
+mod batch_forwarder;
+mod debounce_forwarder;
+mod filter_forwarder;
+mod filter_map_forwarder;
 mod forwarder_impl;
 mod forwarder_middlewares;
 mod forwarder_trait;
+mod map_forwarder;
+mod sample_forwarder;
+mod then_forwarder;
+mod transform_forwarder;
 
+pub use batch_forwarder::{BatchForwarder, BatchTransform};
+pub use debounce_forwarder::{DebounceForwarder, DebounceTransform};
+pub use filter_forwarder::{FilterForwarder, FilterTransform};
+pub use filter_map_forwarder::{FilterMapForwarder, FilterMapTransform};
 pub use forwarder_impl::ForwarderImpl;
 pub use forwarder_middlewares::{DebuggingForwarderLayer, ForwarderBuilder};
+#[cfg(feature = "tracing")]
+pub use forwarder_middlewares::TracingForwarderLayer;
 pub use forwarder_trait::Forwarder;
+pub use map_forwarder::{MapForwarder, MapTransform};
+pub use sample_forwarder::{SampleForwarder, SampleTransform};
+pub use then_forwarder::{ThenForwarder, ThenTransform};
+pub use transform_forwarder::{StreamTransform, TransformForwarder};
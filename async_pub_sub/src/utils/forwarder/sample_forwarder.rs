@@ -0,0 +1,89 @@
+use std::{pin::Pin, time::Duration};
+
+use futures::{FutureExt, Stream, StreamExt, select_biased, stream};
+
+use crate::{Timer, utils::sync_stream::sync_stream};
+
+use super::{StreamTransform, TransformForwarder};
+
+/// A forwarder forwarding the latest received message at a fixed interval.
+///
+/// At each tick, the latest message received since the previous tick is forwarded, the
+/// older ones are dropped. Nothing is forwarded if no message was received. When the input
+/// ends, the latest message not forwarded yet is forwarded right away and the stream ends.
+///
+/// # Type Parameters
+/// * `Message` - The type of message being forwarded
+/// * `T` - The timer used to measure the interval
+pub type SampleForwarder<Message, T> = TransformForwarder<Message, SampleTransform<T>>;
+
+/// The transform of a [`SampleForwarder`], forwarding the latest message at each tick.
+pub struct SampleTransform<T> {
+    /// The duration between two ticks
+    interval: Duration,
+    /// The timer used to measure the interval
+    timer: T,
+}
+
+impl<Message, T> SampleForwarder<Message, T>
+where
+    Message: Send + 'static,
+    T: Timer + Clone,
+{
+    /// Creates a new SampleForwarder with the specified name.
+    ///
+    /// # Arguments
+    /// * `name` - A static string identifier for this forwarder instance
+    /// * `interval` - The duration between two ticks, the first tick happening one interval
+    ///   after the subscriber is bound
+    /// * `timer` - The timer of the async runtime
+    pub fn new(name: &'static str, interval: Duration, timer: T) -> Self {
+        Self::with_transform(name, SampleTransform { interval, timer })
+    }
+}
+
+impl<Message, T> StreamTransform<Message> for SampleTransform<T>
+where
+    Message: Send + 'static,
+    T: Timer + Clone,
+{
+    type Out = Message;
+
+    fn transform(
+        &self,
+        stream: Pin<Box<dyn Stream<Item = Message> + Send + Sync + 'static>>,
+    ) -> Pin<Box<dyn Stream<Item = Message> + Send + Sync + 'static>> {
+        let input = stream.fuse();
+        let next_tick = self.timer.now() + self.interval;
+        let state = Some((input, self.timer.clone(), self.interval, next_tick));
+
+        sync_stream(stream::unfold(state, |state| async move {
+            // the state is dropped once the input has ended
+            let (mut input, timer, interval, mut next_tick) = state?;
+            let mut latest = None;
+
+            loop {
+                let mut tick = timer
+                    .sleep(next_tick.saturating_duration_since(timer.now()))
+                    .fuse();
+
+                loop {
+                    select_biased! {
+                        message = input.next() => match message {
+                            Some(message) => latest = Some(message),
+                            // the pending message is forwarded right away, as there is no
+                            // newer message to wait for
+                            None => return latest.map(|message| (message, None)),
+                        },
+                        () = tick => break,
+                    }
+                }
+
+                next_tick += interval;
+                if let Some(message) = latest {
+                    return Some((message, Some((input, timer, interval, next_tick))));
+                }
+            }
+        }))
+    }
+}
@@ -0,0 +1,66 @@
+use std::{future::Future, marker::PhantomData, pin::Pin, sync::Arc};
+
+use futures::{Stream, StreamExt};
+
+use super::{StreamTransform, TransformForwarder};
+
+/// A forwarder converting the messages it receives with an asynchronous closure.
+///
+/// This is the asynchronous counterpart of [`MapForwarder`](crate::MapForwarder): each
+/// message is converted by awaiting the future returned by the closure before being published
+/// to the subscriber. Messages are converted one at a time, in the order they are received.
+///
+/// # Type Parameters
+/// * `In` - The type of message received by the forwarder
+/// * `Out` - The type of message forwarded to the subscriber
+/// * `F` - The closure converting the messages
+pub type ThenForwarder<In, Out, F> = TransformForwarder<In, ThenTransform<Out, F>>;
+
+/// The transform of a [`ThenForwarder`], converting each message with an asynchronous
+/// closure.
+pub struct ThenTransform<Out, F> {
+    /// The closure converting the messages
+    f: Arc<F>,
+    _out: PhantomData<fn() -> Out>,
+}
+
+impl<In, Out, F, Fut> ThenForwarder<In, Out, F>
+where
+    In: Send + 'static,
+    Out: Send + 'static,
+    F: Fn(In) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Out> + Send + Sync + 'static,
+{
+    /// Creates a new ThenForwarder with the specified name.
+    ///
+    /// # Arguments
+    /// * `name` - A static string identifier for this forwarder instance
+    /// * `f` - The asynchronous closure converting the received messages
+    pub fn new(name: &'static str, f: F) -> Self {
+        Self::with_transform(
+            name,
+            ThenTransform {
+                f: Arc::new(f),
+                _out: PhantomData,
+            },
+        )
+    }
+}
+
+impl<In, Out, F, Fut> StreamTransform<In> for ThenTransform<Out, F>
+where
+    In: Send + 'static,
+    Out: Send + 'static,
+    F: Fn(In) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Out> + Send + Sync + 'static,
+{
+    type Out = Out;
+
+    fn transform(
+        &self,
+        stream: Pin<Box<dyn Stream<Item = In> + Send + Sync + 'static>>,
+    ) -> Pin<Box<dyn Stream<Item = Out> + Send + Sync + 'static>> {
+        let f = self.f.clone();
+        Box::pin(stream.then(move |message| f(message)))
+    }
+}
@@ -0,0 +1,141 @@
+use std::pin::Pin;
+
+use futures::{FutureExt, Stream, future::BoxFuture};
+
+use crate::{ForwarderImpl, Publisher, Result, Subscriber};
+
+use super::Forwarder;
+
+type MessageStream<Message> = Pin<Box<dyn Stream<Item = Message> + Send + Sync + 'static>>;
+
+/// The operation applied by a [`TransformForwarder`] to the stream of the messages it receives.
+///
+/// # Type Parameters
+/// * `In` - The type of message received by the forwarder
+pub trait StreamTransform<In>: Send + Sync + 'static {
+    /// The type of message forwarded to the subscriber
+    type Out: Send + 'static;
+
+    /// Transforms the stream of the received messages into the stream handed over to the
+    /// subscriber.
+    ///
+    /// # Arguments
+    /// * `stream` - The stream of the received messages
+    fn transform(&self, stream: MessageStream<In>) -> MessageStream<Self::Out>;
+}
+
+/// A forwarder applying a [`StreamTransform`] to the messages it receives before forwarding
+/// them to its subscriber.
+///
+/// The forwarding itself, including the binding and the closing, is handled by a
+/// [`ForwarderImpl`]. The forwarders converting, filtering or shaping the messages, like
+/// [`MapForwarder`](crate::MapForwarder), are transform forwarders with a given transform.
+///
+/// # Type Parameters
+/// * `In` - The type of message received by the forwarder
+/// * `T` - The transform applied to the received messages
+pub struct TransformForwarder<In, T>
+where
+    In: Send + 'static,
+{
+    /// The forwarder receiving the messages before they are transformed
+    forwarder: ForwarderImpl<In>,
+    /// The transform applied to the received messages
+    transform: T,
+}
+
+impl<In, T> TransformForwarder<In, T>
+where
+    In: Send + 'static,
+    T: StreamTransform<In>,
+{
+    /// Creates a new TransformForwarder with the specified name.
+    ///
+    /// # Arguments
+    /// * `name` - A static string identifier for this forwarder instance
+    /// * `transform` - The transform applied to the received messages
+    pub fn with_transform(name: &'static str, transform: T) -> Self {
+        Self {
+            forwarder: ForwarderImpl::new(name),
+            transform,
+        }
+    }
+
+    /// Returns the transform applied to the received messages.
+    pub fn transform(&self) -> &T {
+        &self.transform
+    }
+}
+
+impl<In, T> Subscriber for TransformForwarder<In, T>
+where
+    In: Send + 'static,
+    T: StreamTransform<In>,
+{
+    type Message = In;
+
+    fn get_name(&self) -> &'static str {
+        Subscriber::get_name(&self.forwarder)
+    }
+
+    fn subscribe_to(&mut self, publisher: &mut dyn Publisher<Message = In>) -> Result<()> {
+        self.forwarder.subscribe_to(publisher)
+    }
+
+    /// Not implemented for TransformForwarder. Will panic if called.
+    fn receive(&mut self) -> BoxFuture<'_, In> {
+        panic!(
+            "{} forwarder does not implement receive method",
+            Subscriber::get_name(&self.forwarder)
+        )
+    }
+}
+
+impl<In, T> Publisher for TransformForwarder<In, T>
+where
+    In: Send + 'static,
+    T: StreamTransform<In>,
+{
+    type Message = T::Out;
+
+    fn get_name(&self) -> &'static str {
+        Publisher::get_name(&self.forwarder)
+    }
+
+    /// Not implemented for TransformForwarder. Will panic if called.
+    fn publish(&self, _message: T::Out) -> BoxFuture<'_, Result<()>> {
+        let name = Publisher::get_name(&self.forwarder);
+        async move { panic!("{} forwarder does not implement publish method", name) }.boxed()
+    }
+
+    fn close(&self) {
+        Publisher::close(&self.forwarder)
+    }
+
+    fn is_closed(&self) -> bool {
+        Publisher::is_closed(&self.forwarder)
+    }
+
+    fn closed(&self) -> BoxFuture<'_, ()> {
+        Publisher::closed(&self.forwarder)
+    }
+
+    /// Creates a stream of the transformed messages.
+    ///
+    /// # Arguments
+    /// * `subscriber_name` - The name of the subscriber that will receive messages
+    fn get_message_stream(
+        &mut self,
+        subscriber_name: &'static str,
+    ) -> Result<MessageStream<T::Out>> {
+        let stream = self.forwarder.get_message_stream(subscriber_name)?;
+        Ok(self.transform.transform(stream))
+    }
+}
+
+impl<In, T> Forwarder for TransformForwarder<In, T>
+where
+    In: Send + 'static,
+    T: StreamTransform<In>,
+{
+}
@@ -0,0 +1,276 @@
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+    time::{Duration, Instant},
+};
+
+use crate::Timer;
+
+/// Summary statistics of a series of durations.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DurationStats {
+    /// The number of recorded durations
+    pub count: u64,
+    /// The sum of the recorded durations
+    pub total: Duration,
+    /// The longest recorded duration
+    pub max: Duration,
+}
+
+impl DurationStats {
+    /// Returns the mean of the recorded durations, if any.
+    pub fn mean(&self) -> Option<Duration> {
+        let count = u32::try_from(self.count).unwrap_or(u32::MAX);
+        (count > 0).then(|| self.total / count)
+    }
+
+    fn record(&mut self, duration: Duration) {
+        self.count += 1;
+        self.total += duration;
+        self.max = self.max.max(duration);
+    }
+}
+
+/// The metrics of the messages sent by a publisher to a subscriber.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ChannelMetrics {
+    /// The name of the publisher
+    pub publisher: &'static str,
+    /// The name of the subscriber, once bound
+    pub subscriber: Option<&'static str>,
+    /// The number of messages successfully published
+    pub published: u64,
+    /// The number of messages that failed to be published
+    pub failed: u64,
+    /// The time spent publishing the messages, waiting for space in the queue included
+    pub publish_wait: DurationStats,
+    /// The number of messages received by the subscriber
+    pub received: u64,
+    /// The time between the publication of the messages and their delivery to the subscriber
+    pub latency: DurationStats,
+    /// The number of messages published and not delivered yet
+    pub queue_depth: usize,
+}
+
+/// A registry of the metrics recorded by [`crate::MetricsPublisherLayer`] and
+/// [`crate::MetricsSubscriberLayer`].
+///
+/// The metrics are recorded per channel, a channel being identified by the names of its
+/// publisher and of its subscriber. The publisher layer records the published messages, the
+/// publish wait time, the queue depth and the latency, measured when the subscriber takes the
+/// message out of the queue. The subscriber layer records the received messages.
+///
+/// With the `metrics` feature, the metrics are also reported to the `metrics` crate
+/// recorder, labelled by `publisher` and `subscriber`.
+#[derive(Clone)]
+pub struct MetricsRegistry {
+    timer: Arc<dyn Timer>,
+    channels: Arc<Mutex<Vec<ChannelState>>>,
+}
+
+/// A channel's metrics, along with the publication instants of its queued messages.
+struct ChannelState {
+    metrics: ChannelMetrics,
+    published_at: VecDeque<Instant>,
+    /// The delivery instants of the messages taken out of the queue before their publication
+    /// was recorded
+    delivered_early: VecDeque<Instant>,
+}
+
+impl MetricsRegistry {
+    /// Creates a new registry.
+    ///
+    /// # Arguments
+    /// * `timer` - The timer of the async runtime, used to timestamp the messages
+    pub fn new(timer: impl Timer) -> Self {
+        Self {
+            timer: Arc::new(timer),
+            channels: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    /// Returns the current metrics of all the channels.
+    pub fn snapshot(&self) -> Vec<ChannelMetrics> {
+        self.channels()
+            .iter()
+            .map(|channel| channel.metrics.clone())
+            .collect()
+    }
+
+    /// Returns the current metrics of the channel between the given publisher and subscriber.
+    pub fn channel(&self, publisher: &str, subscriber: &str) -> Option<ChannelMetrics> {
+        self.channels()
+            .iter()
+            .find(|channel| {
+                channel.metrics.publisher == publisher
+                    && channel.metrics.subscriber == Some(subscriber)
+            })
+            .map(|channel| channel.metrics.clone())
+    }
+
+    /// Registers the channel of a publisher, not bound to a subscriber yet, and returns its
+    /// index.
+    pub(crate) fn register_publisher(&self, publisher: &'static str) -> usize {
+        let mut channels = self.channels();
+        channels.push(ChannelState::new(publisher, None));
+        channels.len() - 1
+    }
+
+    /// Sets the subscriber of a channel.
+    pub(crate) fn bind(&self, index: usize, subscriber: &'static str) {
+        self.channels()[index].metrics.subscriber = Some(subscriber);
+    }
+
+    /// Returns the index of the channel between the given publisher and subscriber,
+    /// registering it if needed.
+    pub(crate) fn channel_index(&self, publisher: &'static str, subscriber: &'static str) -> usize {
+        let mut channels = self.channels();
+        let existing = channels.iter().position(|channel| {
+            channel.metrics.publisher == publisher && channel.metrics.subscriber == Some(subscriber)
+        });
+
+        existing.unwrap_or_else(|| {
+            channels.push(ChannelState::new(publisher, Some(subscriber)));
+            channels.len() - 1
+        })
+    }
+
+    /// Returns the current instant, the start of a publication.
+    pub(crate) fn now(&self) -> Instant {
+        self.timer.now()
+    }
+
+    /// Records the end of a publication, the message being queued only if it succeeded.
+    ///
+    /// Nothing is recorded before the publication completes, so that a cancelled publication
+    /// leaves no trace and concurrent publications do not mix their records.
+    pub(crate) fn publish_completed(&self, index: usize, started_at: Instant, success: bool) {
+        let now = self.timer.now();
+        let wait = now.saturating_duration_since(started_at);
+        let mut channels = self.channels();
+        let channel = &mut channels[index];
+        channel.metrics.publish_wait.record(wait);
+        if success {
+            channel.metrics.published += 1;
+            // the subscriber may have taken the message before the publication completed
+            match channel.delivered_early.pop_front() {
+                Some(delivered_at) => channel.record_latency(delivered_at, started_at),
+                None => channel.published_at.push_back(now),
+            }
+            channel.metrics.queue_depth = channel.published_at.len();
+        } else {
+            channel.metrics.failed += 1;
+        }
+
+        #[cfg(feature = "metrics")]
+        {
+            let (publisher, subscriber) = channel.labels();
+            let name = if success {
+                "async_pub_sub_messages_published_total"
+            } else {
+                "async_pub_sub_messages_failed_total"
+            };
+            ::metrics::counter!(name, "publisher" => publisher, "subscriber" => subscriber)
+                .increment(1);
+            ::metrics::histogram!(
+                "async_pub_sub_publish_wait_seconds",
+                "publisher" => publisher,
+                "subscriber" => subscriber
+            )
+            .record(wait.as_secs_f64());
+            channel.report_queue_depth();
+        }
+    }
+
+    /// Records the delivery of a message to the subscriber.
+    pub(crate) fn delivered(&self, index: usize) {
+        let now = self.timer.now();
+        let mut channels = self.channels();
+        let channel = &mut channels[index];
+        let Some(published_at) = channel.published_at.pop_front() else {
+            channel.delivered_early.push_back(now);
+            return;
+        };
+        channel.record_latency(now, published_at);
+        channel.metrics.queue_depth = channel.published_at.len();
+
+        #[cfg(feature = "metrics")]
+        channel.report_queue_depth();
+    }
+
+    /// Records the reception of a message by the subscriber.
+    pub(crate) fn received(&self, index: usize) {
+        let mut channels = self.channels();
+        let channel = &mut channels[index];
+        channel.metrics.received += 1;
+
+        #[cfg(feature = "metrics")]
+        {
+            let (publisher, subscriber) = channel.labels();
+            ::metrics::counter!(
+                "async_pub_sub_messages_received_total",
+                "publisher" => publisher,
+                "subscriber" => subscriber
+            )
+            .increment(1);
+        }
+    }
+
+    fn channels(&self) -> MutexGuard<'_, Vec<ChannelState>> {
+        self.channels.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl ChannelState {
+    fn new(publisher: &'static str, subscriber: Option<&'static str>) -> Self {
+        Self {
+            metrics: ChannelMetrics {
+                publisher,
+                subscriber,
+                published: 0,
+                failed: 0,
+                publish_wait: DurationStats::default(),
+                received: 0,
+                latency: DurationStats::default(),
+                queue_depth: 0,
+            },
+            published_at: VecDeque::new(),
+            delivered_early: VecDeque::new(),
+        }
+    }
+
+    fn record_latency(&mut self, delivered_at: Instant, published_at: Instant) {
+        let latency = delivered_at.saturating_duration_since(published_at);
+        self.metrics.latency.record(latency);
+
+        #[cfg(feature = "metrics")]
+        {
+            let (publisher, subscriber) = self.labels();
+            ::metrics::histogram!(
+                "async_pub_sub_latency_seconds",
+                "publisher" => publisher,
+                "subscriber" => subscriber
+            )
+            .record(latency.as_secs_f64());
+        }
+    }
+
+    #[cfg(feature = "metrics")]
+    fn labels(&self) -> (&'static str, &'static str) {
+        (
+            self.metrics.publisher,
+            self.metrics.subscriber.unwrap_or("unbound"),
+        )
+    }
+
+    #[cfg(feature = "metrics")]
+    fn report_queue_depth(&self) {
+        let (publisher, subscriber) = self.labels();
+        ::metrics::gauge!(
+            "async_pub_sub_queue_depth",
+            "publisher" => publisher,
+            "subscriber" => subscriber
+        )
+        .set(self.metrics.queue_depth as f64);
+    }
+}
@@ -38,6 +38,73 @@ impl<T> Layer<T> for IdentityLayer {
     }
 }
 
+/// Layer composing two layers, tower style.
+///
+/// `Stack<Inner, Outer>` first wraps the component with the `Inner` layer, then wraps the
+/// result with the `Outer` layer. The builders use it to nest the layers added with their
+/// `layer` method, the first layer added being the outermost one.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Stack<Inner, Outer> {
+    inner: Inner,
+    outer: Outer,
+}
+
+impl<Inner, Outer> Stack<Inner, Outer> {
+    /// Creates a new stack of layers.
+    ///
+    /// # Arguments
+    /// * `inner` - The layer applied first, closest to the wrapped component
+    /// * `outer` - The layer applied last, wrapping the result of the inner layer
+    pub fn new(inner: Inner, outer: Outer) -> Self {
+        Self { inner, outer }
+    }
+}
+
+impl<T, Inner, Outer> Layer<T> for Stack<Inner, Outer>
+where
+    Inner: Layer<T>,
+    Outer: Layer<Inner::LayerType>,
+{
+    type LayerType = Outer::LayerType;
+
+    fn layer(&self, inner: T) -> Self::LayerType {
+        self.outer.layer(self.inner.layer(inner))
+    }
+}
+
+/// Layer built from a closure, see [`layer_fn`].
+#[derive(Clone, Copy, Debug)]
+pub struct LayerFn<F> {
+    f: F,
+}
+
+/// Creates a layer from a closure wrapping a component.
+///
+/// # Arguments
+/// * `f` - The closure called with the component to wrap
+///
+/// # Example
+/// ```
+/// use async_pub_sub::{Layer, LoggingPublisherLayer, PublisherImpl, layer_fn};
+///
+/// let layer = layer_fn(|publisher: PublisherImpl<i32>| LoggingPublisherLayer.layer(publisher));
+/// let publisher = layer.layer(PublisherImpl::new("publisher", 1));
+/// ```
+pub fn layer_fn<F>(f: F) -> LayerFn<F> {
+    LayerFn { f }
+}
+
+impl<F, T, Out> Layer<T> for LayerFn<F>
+where
+    F: Fn(T) -> Out,
+{
+    type LayerType = Out;
+
+    fn layer(&self, inner: T) -> Self::LayerType {
+        (self.f)(inner)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -48,4 +115,13 @@ mod tests {
         let layered = Layer::layer(&IdentityLayer::new(), value);
         assert_eq!(layered, value);
     }
+
+    #[test]
+    fn stack_applies_inner_layer_first() {
+        let stack = Stack::new(
+            layer_fn(|value: i32| value + 1),
+            layer_fn(|value: i32| value * 2),
+        );
+        assert_eq!(stack.layer(1), 4);
+    }
 }
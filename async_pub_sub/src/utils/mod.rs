@@ -1,5 +1,39 @@
+mod bus;
+mod either;
+mod envelope;
 mod forwarder;
+mod metrics_registry;
 mod middleware;
+mod router;
+mod stream_closer;
+mod stream_publisher;
+mod sync_stream;
+mod timer;
+mod topic_bus;
+mod topology;
+#[cfg(feature = "tracing")]
+mod traced;
 
-pub use forwarder::{DebuggingForwarderLayer, Forwarder, ForwarderBuilder, ForwarderImpl};
-pub use middleware::{IdentityLayer, Layer};
+pub use forwarder::{
+    BatchForwarder, BatchTransform, DebounceForwarder, DebounceTransform, DebuggingForwarderLayer,
+    FilterForwarder, FilterMapForwarder, FilterMapTransform, FilterTransform, Forwarder,
+    ForwarderBuilder, ForwarderImpl, MapForwarder, MapTransform, SampleForwarder, SampleTransform,
+    StreamTransform, ThenForwarder, ThenTransform, TransformForwarder,
+};
+#[cfg(feature = "tracing")]
+pub use forwarder::TracingForwarderLayer;
+pub use bus::{Bus, BusPublisher};
+pub use either::Either;
+pub use envelope::{Envelope, Metadata};
+pub use metrics_registry::{ChannelMetrics, DurationStats, MetricsRegistry};
+pub use middleware::{IdentityLayer, Layer, LayerFn, Stack, layer_fn};
+pub use router::{Router, RouterOutput};
+pub(crate) use stream_closer::StreamCloser;
+pub(crate) use stream_publisher::StreamPublisher;
+pub use timer::Timer;
+pub use topic_bus::{TopicBus, TopicMessage, TopicPublisher};
+pub use topology::{Subscription, Topology};
+#[cfg(feature = "tracing")]
+pub use traced::Traced;
+#[cfg(feature = "tracing")]
+pub(crate) use traced::{hand_over, take_over};
@@ -0,0 +1,273 @@
+use std::{collections::HashMap, hash::Hash, pin::Pin};
+
+use futures::{FutureExt, Stream, future::BoxFuture};
+
+use crate::{Publisher, PublisherImpl, Result, Subscriber, SubscriberImpl};
+
+/// A component splitting a message stream between several publishers depending on the
+/// content of the messages.
+///
+/// The router subscribes to publishers like any subscriber. Each received message is given to
+/// the routing function, and published on the output registered for the returned key, or on
+/// the default output if no output matches. Each output has its own buffer, and is bound to a
+/// subscriber like any publisher, for instance with the `route!` macro. A full output buffer
+/// pauses the router until the subscriber of that output catches up, messages sent to an
+/// output that is not bound to a subscriber are dropped.
+///
+/// # Type Parameters
+/// * `Message` - The type of message being routed
+/// * `Key` - The type of key returned by the routing function
+///
+/// # Example
+/// ```
+/// use async_pub_sub::{Publisher, PublisherImpl, Router, Subscriber, SubscriberImpl};
+///
+/// # #[tokio::main]
+/// # async fn main() -> async_pub_sub::Result<()> {
+/// let mut publisher = PublisherImpl::new("publisher", 1);
+/// let mut router = Router::new("router", 1, |value: &i32| *value % 2 == 0);
+/// router.add_output(true, "even", 1)?;
+/// let mut even = SubscriberImpl::new("even_subscriber");
+/// let mut others = SubscriberImpl::new("others_subscriber");
+///
+/// router.subscribe_to(&mut publisher)?;
+/// even.subscribe_to(router.output(&true)?)?;
+/// others.subscribe_to(router.default_output())?;
+/// tokio::spawn(async move { router.run().await });
+///
+/// publisher.publish(2).await?;
+/// publisher.publish(3).await?;
+/// assert_eq!(even.receive().await, 2);
+/// assert_eq!(others.receive().await, 3);
+/// # Ok(())
+/// # }
+/// ```
+pub struct Router<Message, Key>
+where
+    Message: Send + 'static,
+{
+    /// The name identifier for this router instance
+    name: &'static str,
+    /// The internal subscriber implementation used to receive messages
+    subscriber: SubscriberImpl<Message>,
+    /// The function selecting the output of a message
+    routing_fn: Box<dyn Fn(&Message) -> Key + Send + Sync>,
+    /// The outputs registered for each key
+    outputs: HashMap<Key, RouterOutput<Message>>,
+    /// The output receiving the messages not matching any registered key
+    default_output: RouterOutput<Message>,
+}
+
+impl<Message, Key> Router<Message, Key>
+where
+    Message: Send + Sync + 'static,
+    Key: Eq + Hash + std::fmt::Debug + Send + Sync + 'static,
+{
+    /// Creates a new Router with the specified name.
+    ///
+    /// # Arguments
+    /// * `name` - A static string identifier for this router, also used for its default output
+    /// * `buffer_size` - The size of the message buffer of the default output
+    /// * `routing_fn` - The function returning the key of the output of a message
+    pub fn new(
+        name: &'static str,
+        buffer_size: usize,
+        routing_fn: impl Fn(&Message) -> Key + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            name,
+            subscriber: SubscriberImpl::new(name),
+            routing_fn: Box::new(routing_fn),
+            outputs: HashMap::new(),
+            default_output: RouterOutput::new(name, buffer_size),
+        }
+    }
+
+    /// Registers a new output.
+    ///
+    /// # Arguments
+    /// * `key` - The key returned by the routing function for the messages of this output
+    /// * `name` - A static string identifier for the output publisher
+    /// * `buffer_size` - The size of the message buffer of the output
+    ///
+    /// # Returns
+    /// An error if an output is already registered for this key
+    pub fn add_output(&mut self, key: Key, name: &'static str, buffer_size: usize) -> Result<()> {
+        if self.outputs.contains_key(&key) {
+            return Err(format!(
+                "{} router already has an output for the {:?} key",
+                self.name, key
+            )
+            .into());
+        }
+
+        self.outputs
+            .insert(key, RouterOutput::new(name, buffer_size));
+        Ok(())
+    }
+
+    /// Returns the output registered for a key, to bind it to a subscriber.
+    ///
+    /// # Returns
+    /// An error if no output is registered for this key
+    pub fn output(&mut self, key: &Key) -> Result<&mut RouterOutput<Message>> {
+        let name = self.name;
+        self.outputs
+            .get_mut(key)
+            .ok_or_else(|| format!("{name} router has no output for the {key:?} key").into())
+    }
+
+    /// Returns the output receiving the messages not matching any registered key, to bind
+    /// it to a subscriber.
+    pub fn default_output(&mut self) -> &mut RouterOutput<Message> {
+        &mut self.default_output
+    }
+
+    /// Routes the received messages to the outputs, until all the publishers the router
+    /// subscribed to are closed or dropped.
+    ///
+    /// The outputs are then closed, so that their subscribers see the end of the stream once
+    /// they have received the routed messages.
+    pub async fn run(&mut self) {
+        while let Some(message) = self.subscriber.next_message().await {
+            let key = (self.routing_fn)(&message);
+            let output = self.outputs.get(&key).unwrap_or(&self.default_output);
+            output.forward(self.name, message).await;
+        }
+
+        for output in self.outputs.values() {
+            output.close();
+        }
+        self.default_output.close();
+    }
+}
+
+impl<Message, Key> Subscriber for Router<Message, Key>
+where
+    Message: Send + Sync + 'static,
+    Key: Eq + Hash + std::fmt::Debug + Send + Sync + 'static,
+{
+    type Message = Message;
+
+    /// Returns the name of this router instance.
+    fn get_name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Subscribes this router to a publisher.
+    fn subscribe_to(&mut self, publisher: &mut dyn Publisher<Message = Message>) -> Result<()> {
+        self.subscriber.subscribe_to(publisher)
+    }
+
+    /// Not implemented for Router, the received messages are handled by [`Router::run`].
+    /// Will panic if called.
+    fn receive(&mut self) -> BoxFuture<'_, Message> {
+        panic!("Router does not implement receive method, use run instead")
+    }
+}
+
+/// An output of a [`Router`], publishing the messages routed to it.
+///
+/// It is also used by the splitters generated by the `SplitPublisher` derive macro.
+pub struct RouterOutput<Message>
+where
+    Message: Send + 'static,
+{
+    /// The name identifier for this output
+    name: &'static str,
+    /// The publisher of the routed messages
+    publisher: PublisherImpl<Message>,
+    /// Whether a subscriber has been bound to this output
+    bound: bool,
+}
+
+impl<Message> RouterOutput<Message>
+where
+    Message: Send + 'static,
+{
+    /// Creates a new output.
+    ///
+    /// # Arguments
+    /// * `name` - A static string identifier for the output publisher
+    /// * `buffer_size` - The size of the message buffer of the output
+    pub fn new(name: &'static str, buffer_size: usize) -> Self {
+        Self {
+            name,
+            publisher: PublisherImpl::new(name, buffer_size),
+            bound: false,
+        }
+    }
+
+    /// Publishes a routed message, or drops it if no subscriber is bound to this output.
+    ///
+    /// Failures are logged rather than returned, so that a faulty output does not stop the
+    /// routing of the other messages.
+    ///
+    /// # Arguments
+    /// * `router_name` - The name of the component routing the message, used in the logs
+    /// * `message` - The message to publish
+    pub async fn forward(&self, router_name: &'static str, message: Message) {
+        if !self.bound {
+            log::warn!(
+                "[{}] no subscriber bound to the {} output, dropping message",
+                router_name,
+                self.name
+            );
+            return;
+        }
+
+        if let Err(err) = self.publisher.publish(message).await {
+            log::warn!(
+                "[{}] failed to route message to the {} output (err: {})",
+                router_name,
+                self.name,
+                err
+            );
+        }
+    }
+
+    /// Closes this output, its subscriber seeing the end of the stream once it has received
+    /// the messages already routed.
+    pub fn close(&self) {
+        self.publisher.close()
+    }
+}
+
+impl<Message> Publisher for RouterOutput<Message>
+where
+    Message: Send + Sync + 'static,
+{
+    type Message = Message;
+
+    /// Returns the name of this output.
+    fn get_name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Publishes a message on this output, bypassing the routing function.
+    fn publish(&self, message: Message) -> BoxFuture<'_, Result<()>> {
+        Publisher::publish(&self.publisher, message).boxed()
+    }
+
+    fn close(&self) {
+        RouterOutput::close(self)
+    }
+
+    fn is_closed(&self) -> bool {
+        self.publisher.is_closed()
+    }
+
+    fn closed(&self) -> BoxFuture<'_, ()> {
+        self.publisher.closed().boxed()
+    }
+
+    /// Binds a subscriber to this output and returns the message stream.
+    fn get_message_stream(
+        &mut self,
+        subscriber_name: &'static str,
+    ) -> Result<Pin<Box<dyn Stream<Item = Message> + Send + Sync + 'static>>> {
+        let stream = self.publisher.get_message_stream(subscriber_name)?;
+        self.bound = true;
+        Ok(stream)
+    }
+}
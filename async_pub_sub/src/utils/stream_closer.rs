@@ -0,0 +1,99 @@
+use std::{
+    pin::Pin,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, Ordering},
+    },
+    task::Poll,
+};
+
+use futures::{
+    FutureExt, Stream, StreamExt,
+    channel::oneshot,
+    future::{BoxFuture, Shared},
+    stream,
+    task::AtomicWaker,
+};
+
+/// The closing state of a publisher handing over a single message stream, without a queue of
+/// its own.
+///
+/// Closing ends the handed over stream right away, the messages not being queued by the
+/// publisher. The publisher is drained once the stream has ended or has been dropped, or as
+/// soon as it is closed if the stream was never handed over.
+pub(crate) struct StreamCloser {
+    is_closed: Arc<AtomicBool>,
+    waker: Arc<AtomicWaker>,
+    drained: Shared<oneshot::Receiver<()>>,
+    /// Moved into the handed over stream, dropping it completes `drained`
+    drained_sender: Mutex<Option<oneshot::Sender<()>>>,
+}
+
+impl StreamCloser {
+    pub(crate) fn new() -> Self {
+        let (drained_sender, drained) = oneshot::channel();
+        Self {
+            is_closed: Arc::default(),
+            waker: Arc::default(),
+            drained: drained.shared(),
+            drained_sender: Mutex::new(Some(drained_sender)),
+        }
+    }
+
+    /// Ends the handed over stream.
+    pub(crate) fn close(&self) {
+        self.is_closed.store(true, Ordering::SeqCst);
+        self.waker.wake();
+        self.drained_sender
+            .lock()
+            .expect("stream closer lock poisoned")
+            .take();
+    }
+
+    /// Returns whether the publisher has been closed, or its stream dropped.
+    pub(crate) fn is_closed(&self) -> bool {
+        self.is_closed.load(Ordering::SeqCst) || self.drained.peek().is_some()
+    }
+
+    /// Waits until the handed over stream has ended or has been dropped.
+    pub(crate) fn closed(&self) -> BoxFuture<'static, ()> {
+        self.drained.clone().map(|_| ()).boxed()
+    }
+
+    /// Wraps the stream handed over by the publisher, so that it ends once closed.
+    pub(crate) fn wrap<Item>(
+        &self,
+        stream: Pin<Box<dyn Stream<Item = Item> + Send + Sync + 'static>>,
+    ) -> Pin<Box<dyn Stream<Item = Item> + Send + Sync + 'static>>
+    where
+        Item: 'static,
+    {
+        let is_closed = self.is_closed.clone();
+        let waker = self.waker.clone();
+        let mut drained_sender = self
+            .drained_sender
+            .lock()
+            .expect("stream closer lock poisoned")
+            .take();
+        let mut stream = Some(stream);
+
+        Box::pin(stream::poll_fn(move |cx| {
+            waker.register(cx.waker());
+            let item = match stream.as_mut() {
+                Some(_) if is_closed.load(Ordering::SeqCst) => None,
+                Some(stream) => match stream.poll_next_unpin(cx) {
+                    Poll::Ready(item) => item,
+                    Poll::Pending => return Poll::Pending,
+                },
+                None => None,
+            };
+
+            if item.is_none() {
+                stream = None;
+                // completes `drained`
+                drop(drained_sender.take());
+            }
+            Poll::Ready(item)
+        }))
+    }
+}
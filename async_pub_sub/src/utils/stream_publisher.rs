@@ -0,0 +1,75 @@
+use std::pin::Pin;
+
+use futures::{FutureExt, Stream, future::BoxFuture};
+
+use crate::{Publisher, Result, utils::StreamCloser};
+
+/// A publisher handing over an already created message stream.
+///
+/// Subscriber middlewares transforming the streams of the publishers they subscribe to use it
+/// to hand the transformed stream to the wrapped subscriber.
+pub(crate) struct StreamPublisher<Message> {
+    name: &'static str,
+    stream: Option<Pin<Box<dyn Stream<Item = Message> + Send + Sync + 'static>>>,
+    closer: StreamCloser,
+}
+
+impl<Message> StreamPublisher<Message> {
+    /// Creates a new publisher handing over the given stream.
+    ///
+    /// # Arguments
+    /// * `name` - The name of the publisher the stream comes from
+    /// * `stream` - The stream to hand over
+    pub(crate) fn new(
+        name: &'static str,
+        stream: Pin<Box<dyn Stream<Item = Message> + Send + Sync + 'static>>,
+    ) -> Self {
+        Self {
+            name,
+            stream: Some(stream),
+            closer: StreamCloser::new(),
+        }
+    }
+}
+
+impl<Message> Publisher for StreamPublisher<Message>
+where
+    Message: Send + 'static,
+{
+    type Message = Message;
+
+    fn get_name(&self) -> &'static str {
+        self.name
+    }
+
+    fn publish(&self, _message: Self::Message) -> BoxFuture<'_, Result<()>> {
+        let name = self.name;
+        async move { Err(format!("{name} publisher only hands over its message stream").into()) }
+            .boxed()
+    }
+
+    fn close(&self) {
+        self.closer.close()
+    }
+
+    fn is_closed(&self) -> bool {
+        self.closer.is_closed()
+    }
+
+    fn closed(&self) -> BoxFuture<'_, ()> {
+        self.closer.closed()
+    }
+
+    fn get_message_stream(
+        &mut self,
+        subscriber_name: &'static str,
+    ) -> Result<Pin<Box<dyn Stream<Item = Self::Message> + Send + Sync + 'static>>> {
+        let stream = self.stream.take().ok_or_else(|| {
+            format!(
+                "{} publisher stream was already handed to {}",
+                self.name, subscriber_name
+            )
+        })?;
+        Ok(self.closer.wrap(stream))
+    }
+}
@@ -0,0 +1,40 @@
+use std::{
+    pin::Pin,
+    sync::{Mutex, PoisonError},
+    task::{Context, Poll},
+};
+
+use futures::Stream;
+
+/// A stream wrapper making a `Send` stream `Sync`.
+///
+/// Message streams must be `Sync`, which streams holding futures such as timers are usually
+/// not. The wrapped stream is only ever accessed through a mutable reference, so the mutex is
+/// never actually locked.
+struct SyncStream<S>(Mutex<Pin<Box<S>>>);
+
+impl<S> Stream for SyncStream<S>
+where
+    S: Stream,
+{
+    type Item = S::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.get_mut()
+            .0
+            .get_mut()
+            .unwrap_or_else(PoisonError::into_inner)
+            .as_mut()
+            .poll_next(cx)
+    }
+}
+
+/// Boxes a `Send` stream into a message stream.
+pub(crate) fn sync_stream<Item>(
+    stream: impl Stream<Item = Item> + Send + 'static,
+) -> Pin<Box<dyn Stream<Item = Item> + Send + Sync + 'static>>
+where
+    Item: 'static,
+{
+    Box::pin(SyncStream(Mutex::new(Box::pin(stream))))
+}
@@ -0,0 +1,19 @@
+use std::time::{Duration, Instant};
+
+use futures::future::BoxFuture;
+
+/// A trait abstracting the clock of the async runtime.
+///
+/// The time-based middlewares and forwarders rely on this trait rather than on a given
+/// runtime, so that they can be used with any executor. `tokio_implementations` provides an
+/// implementation based on `tokio::time`, which also works with tokio's paused clock.
+pub trait Timer: Send + Sync + 'static {
+    /// Returns the current instant, as seen by the runtime.
+    fn now(&self) -> Instant;
+
+    /// Returns a future completing after the given duration.
+    ///
+    /// # Arguments
+    /// * `duration` - The duration to wait for
+    fn sleep(&self, duration: Duration) -> BoxFuture<'static, ()>;
+}
@@ -0,0 +1,456 @@
+use std::{
+    any::{Any, TypeId},
+    pin::Pin,
+    sync::{Arc, Mutex},
+};
+
+use futures::{
+    FutureExt, Stream, StreamExt,
+    channel::{mpsc, oneshot},
+    future::{self, BoxFuture, Shared},
+};
+
+use super::StreamPublisher;
+use crate::{PublishError, Publisher, Result, SubscriberImpl, SubscriberWrapper, Topology};
+
+/// A message published on a topic of a [`TopicBus`], along with the topic.
+///
+/// # Type Parameters
+/// * `Message` - The type of the published message
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TopicMessage<Message> {
+    topic: &'static str,
+    message: Message,
+}
+
+impl<Message> TopicMessage<Message> {
+    /// Returns the topic the message was published on.
+    pub fn topic(&self) -> &'static str {
+        self.topic
+    }
+
+    /// Returns the published message.
+    pub fn message(&self) -> &Message {
+        &self.message
+    }
+
+    /// Returns the published message, dropping the topic.
+    pub fn into_inner(self) -> Message {
+        self.message
+    }
+}
+
+/// The sending half of a subscription, whatever its message type.
+trait SubscriptionSender: Send + Sync {
+    fn is_closed(&self) -> bool;
+
+    fn as_any(&self) -> &dyn Any;
+}
+
+impl<Message> SubscriptionSender for mpsc::Sender<TopicMessage<Message>>
+where
+    Message: Send + 'static,
+{
+    fn is_closed(&self) -> bool {
+        mpsc::Sender::is_closed(self)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+/// A subscription of a [`TopicBus`], receiving the messages of a given type published on the
+/// topics matching its filter.
+struct TopicSubscription {
+    filter: &'static str,
+    message_type: TypeId,
+    sender: Box<dyn SubscriptionSender>,
+}
+
+/// A dynamic bus routing messages by hierarchical topics, with MQTT-like wildcards.
+///
+/// Publishers publish on topics made of levels separated by `/`, like `sensors/room1/temp`,
+/// and subscribers subscribe with topic filters, in which `+` matches exactly one level and
+/// `#`, as the last level, matches any number of remaining levels, including none:
+/// `sensors/+/temp` matches `sensors/room1/temp` and `sensors/#` matches `sensors` as well
+/// as `sensors/room1/temp`. As in MQTT, the topics starting with `$` are not matched by a
+/// wildcard at their first level.
+///
+/// A subscription only receives the messages of its own type, each subscriber getting a
+/// clone of the messages published after its subscription. The subscriptions are made
+/// through the [`Publisher`] and [`Subscriber`](crate::Subscriber) traits, so that a
+/// [`SubscriberImpl`] can merge several topic filters and the subscriber layers still apply.
+/// A subscriber with overlapping filters receives a message once per matching filter.
+///
+/// # Example
+///
+/// ```
+/// use async_pub_sub::{Publisher, TopicBus};
+///
+/// # futures::executor::block_on(async {
+/// let bus = TopicBus::new(10);
+/// let mut temperatures = bus.subscriber::<f32>("thermostat", "sensors/+/temp").unwrap();
+///
+/// let room1 = bus.publisher::<f32>("sensors/room1/temp").unwrap();
+/// let room1_humidity = bus.publisher::<f32>("sensors/room1/humidity").unwrap();
+///
+/// room1_humidity.publish(40.0).await.unwrap();
+/// room1.publish(21.5).await.unwrap();
+///
+/// assert_eq!(temperatures.receive().await, 21.5);
+/// # });
+/// ```
+#[derive(Clone)]
+pub struct TopicBus {
+    buffer_size: usize,
+    subscriptions: Arc<Mutex<Vec<TopicSubscription>>>,
+}
+
+impl TopicBus {
+    /// Creates a new bus without subscriptions.
+    ///
+    /// # Arguments
+    /// * `buffer_size` - The size of the message buffer of each subscription
+    pub fn new(buffer_size: usize) -> Self {
+        Self {
+            buffer_size,
+            subscriptions: Arc::default(),
+        }
+    }
+
+    /// Returns a publisher of messages on the given topic.
+    ///
+    /// The topic is also the name of the publisher.
+    ///
+    /// # Arguments
+    /// * `topic` - The topic the messages are published on, without wildcards
+    ///
+    /// # Returns
+    /// An error if the topic is empty or contains wildcards
+    pub fn publisher<Message>(&self, topic: &'static str) -> Result<TopicPublisher<Message>>
+    where
+        Message: Clone + Send + Sync + 'static,
+    {
+        validate_topic(topic)?;
+
+        let (in_flight, handed_over) = oneshot::channel();
+        Ok(TopicPublisher {
+            topic,
+            bus: self.clone(),
+            state: Arc::new(PublisherState {
+                in_flight: Mutex::new(Some(Arc::new(in_flight))),
+                handed_over: handed_over.shared(),
+            }),
+            message_type: std::marker::PhantomData,
+        })
+    }
+
+    /// Subscribes a subscriber to the messages published on the topics matching the filter.
+    ///
+    /// The subscription is reported to the [`crate::Topology`], the filter standing for the
+    /// publisher.
+    ///
+    /// # Arguments
+    /// * `filter` - The topic filter, which can contain wildcards
+    /// * `subscriber` - The subscriber receiving the messages
+    ///
+    /// # Returns
+    /// An error if the filter is invalid, or if the subscriber cannot subscribe
+    pub fn subscribe<Message>(
+        &self,
+        filter: &'static str,
+        subscriber: &mut impl SubscriberWrapper<Message>,
+    ) -> Result<()>
+    where
+        Message: Clone + Send + Sync + 'static,
+    {
+        let stream = self.subscription_stream::<Message>(filter)?;
+        let stream = stream.map(TopicMessage::into_inner);
+        subscriber.subscribe_to(&mut StreamPublisher::new(filter, Box::pin(stream)))?;
+        Topology::record::<Message>(filter, subscriber.get_name());
+        Ok(())
+    }
+
+    /// Subscribes a subscriber to the messages published on the topics matching the filter,
+    /// each message being received along with its topic.
+    ///
+    /// The subscription is reported to the [`crate::Topology`], the filter standing for the
+    /// publisher.
+    ///
+    /// # Arguments
+    /// * `filter` - The topic filter, which can contain wildcards
+    /// * `subscriber` - The subscriber receiving the messages and their topics
+    ///
+    /// # Returns
+    /// An error if the filter is invalid, or if the subscriber cannot subscribe
+    pub fn subscribe_with_topic<Message>(
+        &self,
+        filter: &'static str,
+        subscriber: &mut impl SubscriberWrapper<TopicMessage<Message>>,
+    ) -> Result<()>
+    where
+        Message: Clone + Send + Sync + 'static,
+    {
+        let stream = self.subscription_stream::<Message>(filter)?;
+        subscriber.subscribe_to(&mut StreamPublisher::new(filter, stream))?;
+        Topology::record::<TopicMessage<Message>>(filter, subscriber.get_name());
+        Ok(())
+    }
+
+    /// Creates a subscriber receiving the messages published on the topics matching the
+    /// filter.
+    ///
+    /// More filters can be added to the subscriber with [`TopicBus::subscribe`].
+    ///
+    /// # Arguments
+    /// * `name` - The name of the subscriber
+    /// * `filter` - The topic filter, which can contain wildcards
+    ///
+    /// # Returns
+    /// An error if the filter is invalid
+    pub fn subscriber<Message>(
+        &self,
+        name: &'static str,
+        filter: &'static str,
+    ) -> Result<SubscriberImpl<Message>>
+    where
+        Message: Clone + Send + Sync + 'static,
+    {
+        let mut subscriber = SubscriberImpl::new(name);
+        self.subscribe(filter, &mut subscriber)?;
+        Ok(subscriber)
+    }
+
+    /// Returns whether the topic filter matches the topic, according to the MQTT rules.
+    ///
+    /// # Arguments
+    /// * `filter` - A valid topic filter, which can contain wildcards
+    /// * `topic` - A valid topic, without wildcards
+    pub fn matches(filter: &str, topic: &str) -> bool {
+        // the wildcards do not match the first level of the topics starting with '$'
+        if topic.starts_with('$') && (filter.starts_with('+') || filter.starts_with('#')) {
+            return false;
+        }
+
+        let mut filter_levels = filter.split('/');
+        let mut topic_levels = topic.split('/');
+        loop {
+            match (filter_levels.next(), topic_levels.next()) {
+                (Some("#"), _) | (None, None) => return true,
+                (Some("+"), Some(_)) => {}
+                (Some(filter_level), Some(topic_level)) if filter_level == topic_level => {}
+                _ => return false,
+            }
+        }
+    }
+
+    /// Registers a subscription and returns the stream of its messages.
+    fn subscription_stream<Message>(
+        &self,
+        filter: &'static str,
+    ) -> Result<Pin<Box<dyn Stream<Item = TopicMessage<Message>> + Send + Sync + 'static>>>
+    where
+        Message: Send + 'static,
+    {
+        validate_filter(filter)?;
+
+        let (sender, receiver) = mpsc::channel::<TopicMessage<Message>>(self.buffer_size);
+        self.subscriptions
+            .lock()
+            .expect("topic bus lock poisoned")
+            .push(TopicSubscription {
+                filter,
+                message_type: TypeId::of::<Message>(),
+                sender: Box::new(sender),
+            });
+
+        Ok(Box::pin(receiver))
+    }
+
+    /// Returns the senders of the subscriptions to the messages of the given type published
+    /// on the topic, forgetting the subscriptions that have been dropped.
+    fn senders<Message>(&self, topic: &str) -> Vec<mpsc::Sender<TopicMessage<Message>>>
+    where
+        Message: Send + 'static,
+    {
+        let mut subscriptions = self.subscriptions.lock().expect("topic bus lock poisoned");
+        subscriptions.retain(|subscription| !subscription.sender.is_closed());
+
+        subscriptions
+            .iter()
+            .filter(|subscription| {
+                subscription.message_type == TypeId::of::<Message>()
+                    && Self::matches(subscription.filter, topic)
+            })
+            .filter_map(|subscription| {
+                subscription
+                    .sender
+                    .as_any()
+                    .downcast_ref::<mpsc::Sender<TopicMessage<Message>>>()
+                    .cloned()
+            })
+            .collect()
+    }
+}
+
+impl Default for TopicBus {
+    /// Creates a new bus buffering 16 messages per subscription.
+    fn default() -> Self {
+        Self::new(16)
+    }
+}
+
+/// The closing state of a [`TopicPublisher`], shared by its clones.
+struct PublisherState {
+    /// Held by the publish calls in flight, taken once closed
+    in_flight: Mutex<Option<Arc<oneshot::Sender<()>>>>,
+    /// Completes once closed and all the publish calls in flight are done
+    handed_over: Shared<oneshot::Receiver<()>>,
+}
+
+/// A publisher of messages on a topic of a [`TopicBus`].
+///
+/// Binding a subscriber to it subscribes the subscriber to its exact topic.
+///
+/// Closing the publisher rejects the messages it publishes afterwards, as well as the ones of
+/// its clones. The other publishers of the topic are not affected, so the subscriptions stay
+/// open.
+pub struct TopicPublisher<Message> {
+    topic: &'static str,
+    bus: TopicBus,
+    state: Arc<PublisherState>,
+    message_type: std::marker::PhantomData<fn() -> Message>,
+}
+
+impl<Message> TopicPublisher<Message> {
+    /// Returns the topic the messages are published on.
+    pub fn topic(&self) -> &'static str {
+        self.topic
+    }
+}
+
+impl<Message> Clone for TopicPublisher<Message> {
+    fn clone(&self) -> Self {
+        Self {
+            topic: self.topic,
+            bus: self.bus.clone(),
+            state: self.state.clone(),
+            message_type: std::marker::PhantomData,
+        }
+    }
+}
+
+impl<Message> Publisher for TopicPublisher<Message>
+where
+    Message: Clone + Send + Sync + 'static,
+{
+    type Message = Message;
+
+    /// Returns the topic of the publisher.
+    fn get_name(&self) -> &'static str {
+        self.topic
+    }
+
+    /// Publishes a clone of the message to each subscription matching the topic.
+    ///
+    /// Publishing on a topic without subscribers succeeds, the message being dropped. A
+    /// closed publisher rejects the message with a [`PublishError`].
+    fn publish(&self, message: Message) -> BoxFuture<'_, Result<()>> {
+        let in_flight = self
+            .state
+            .in_flight
+            .lock()
+            .expect("topic publisher lock poisoned")
+            .clone();
+        let senders = self.bus.senders::<Message>(self.topic);
+        let topic = self.topic;
+
+        async move {
+            let Some(_in_flight) = in_flight else {
+                return Err(PublishError::new(topic, "the publisher is closed", message).into());
+            };
+
+            for mut sender in senders {
+                if future::poll_fn(|cx| sender.poll_ready(cx)).await.is_ok() {
+                    let message = TopicMessage {
+                        topic,
+                        message: message.clone(),
+                    };
+                    // a subscriber dropped in the meantime is forgotten on the next publish
+                    let _ = sender.try_send(message);
+                }
+            }
+            Ok(())
+        }
+        .boxed()
+    }
+
+    /// Closes the publisher and its clones, the messages published afterwards being rejected.
+    fn close(&self) {
+        self.state
+            .in_flight
+            .lock()
+            .expect("topic publisher lock poisoned")
+            .take();
+    }
+
+    /// Returns whether the publisher, or one of its clones, has been closed.
+    fn is_closed(&self) -> bool {
+        self.state
+            .in_flight
+            .lock()
+            .expect("topic publisher lock poisoned")
+            .is_none()
+    }
+
+    /// Waits until the publisher has been closed and the messages published before have been
+    /// handed to the subscriptions.
+    fn closed(&self) -> BoxFuture<'_, ()> {
+        self.state.handed_over.clone().map(|_| ()).boxed()
+    }
+
+    /// Subscribes to the exact topic of the publisher.
+    fn get_message_stream(
+        &mut self,
+        _subscriber_name: &'static str,
+    ) -> Result<Pin<Box<dyn Stream<Item = Message> + Send + Sync + 'static>>> {
+        let stream = self.bus.subscription_stream::<Message>(self.topic)?;
+        Ok(Box::pin(stream.map(TopicMessage::into_inner)))
+    }
+}
+
+/// Checks that a topic is not empty and does not contain wildcards.
+fn validate_topic(topic: &str) -> Result<()> {
+    if topic.is_empty() {
+        return Err("a topic cannot be empty".into());
+    }
+    if topic.contains(['+', '#']) {
+        return Err(format!("topic `{topic}` cannot contain the '+' and '#' wildcards").into());
+    }
+    Ok(())
+}
+
+/// Checks that a topic filter is not empty and that its wildcards take whole levels, `#`
+/// being the last one.
+fn validate_filter(filter: &str) -> Result<()> {
+    if filter.is_empty() {
+        return Err("a topic filter cannot be empty".into());
+    }
+
+    let level_count = filter.split('/').count();
+    for (index, level) in filter.split('/').enumerate() {
+        if level.contains('+') && level != "+" {
+            return Err(
+                format!("topic filter `{filter}` is invalid, '+' must take a whole level").into(),
+            );
+        }
+        if level.contains('#') && (level != "#" || index != level_count - 1) {
+            return Err(format!(
+                "topic filter `{filter}` is invalid, '#' must take the whole last level"
+            )
+            .into());
+        }
+    }
+    Ok(())
+}
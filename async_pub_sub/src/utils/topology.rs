@@ -0,0 +1,257 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Write,
+    sync::Mutex,
+};
+
+/// The subscriptions reported and not forgotten since the start of the process, or since the
+/// last reset, with the number of subscribers holding each of them.
+static SUBSCRIPTIONS: Mutex<BTreeMap<Subscription, usize>> = Mutex::new(BTreeMap::new());
+
+/// A subscription of a subscriber to a publisher, as reported to the [`Topology`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Subscription {
+    publisher: &'static str,
+    subscriber: &'static str,
+    message_type: &'static str,
+}
+
+impl Subscription {
+    /// Returns the name of the publisher.
+    pub fn publisher(&self) -> &'static str {
+        self.publisher
+    }
+
+    /// Returns the name of the subscriber.
+    pub fn subscriber(&self) -> &'static str {
+        self.subscriber
+    }
+
+    /// Returns the type name of the messages received by the subscriber.
+    pub fn message_type(&self) -> &'static str {
+        self.message_type
+    }
+}
+
+/// A snapshot of the publish-subscribe graph of the process.
+///
+/// The subscribers report every subscription they make, with the names of the publisher and
+/// of the subscriber and the type of the received messages. The topology can then be queried
+/// at runtime, or exported as a Graphviz DOT graph or as JSON.
+///
+/// Custom subscribers not built on top of [`crate::SubscriberImpl`] report their subscriptions
+/// with [`Topology::record`], and forget them with [`Topology::forget`] when they are dropped.
+///
+/// The registry is global to the process: a [`crate::SubscriberImpl`] forgets its subscriptions
+/// when it is dropped, so that the graph only holds the live subscriptions, and
+/// [`Topology::reset`] clears the registry before wiring a separate graph. The subscriptions
+/// are identified by the names of their publisher and subscriber: the subscriptions of
+/// subscribers sharing the same name are counted, and the edge remains until the last of
+/// them is dropped.
+///
+/// # Example
+///
+/// ```
+/// use async_pub_sub::{PublisherImpl, SubscriberImpl, Topology};
+///
+/// let mut publisher = PublisherImpl::<i32>::new("timer_service", 1);
+/// let mut subscriber = SubscriberImpl::new("cache_service");
+/// subscriber.subscribe_to(&mut publisher).unwrap();
+///
+/// let topology = Topology::current();
+/// assert_eq!(topology.subscribers_of("timer_service"), vec!["cache_service"]);
+/// assert!(topology.to_dot().contains(r#""timer_service" -> "cache_service" [label="i32"];"#));
+///
+/// // the subscription is forgotten with its subscriber
+/// drop(subscriber);
+/// assert!(Topology::current().subscribers_of("timer_service").is_empty());
+///
+/// // a separate graph starts from an empty registry
+/// Topology::record::<i32>("timer_service", "logger_service");
+/// Topology::reset();
+/// assert!(Topology::current().subscriptions().is_empty());
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Topology {
+    subscriptions: Vec<Subscription>,
+}
+
+impl Topology {
+    /// Returns the subscriptions reported and not forgotten since the start of the process, or
+    /// since the last reset.
+    pub fn current() -> Self {
+        SUBSCRIPTIONS
+            .lock()
+            .expect("topology lock poisoned")
+            .keys()
+            .copied()
+            .collect()
+    }
+
+    /// Forgets all the reported subscriptions, e.g. before wiring a separate graph.
+    pub fn reset() {
+        SUBSCRIPTIONS
+            .lock()
+            .expect("topology lock poisoned")
+            .clear();
+    }
+
+    /// Reports the subscription of a subscriber to a publisher.
+    ///
+    /// A subscription reported several times, e.g. by subscribers sharing the same name, is
+    /// counted, and stays in the topology until it has been forgotten as many times.
+    ///
+    /// # Type Parameters
+    /// * `Message` - The type of the messages received by the subscriber
+    ///
+    /// # Arguments
+    /// * `publisher` - The name of the publisher
+    /// * `subscriber` - The name of the subscriber
+    pub fn record<Message>(publisher: &'static str, subscriber: &'static str) {
+        SUBSCRIPTIONS
+            .lock()
+            .expect("topology lock poisoned")
+            .entry(Subscription {
+                publisher,
+                subscriber,
+                message_type: std::any::type_name::<Message>(),
+            })
+            .and_modify(|count| *count += 1)
+            .or_insert(1);
+    }
+
+    /// Forgets the subscription of a subscriber to a publisher, once the subscriber is gone.
+    ///
+    /// # Type Parameters
+    /// * `Message` - The type of the messages received by the subscriber
+    ///
+    /// # Arguments
+    /// * `publisher` - The name of the publisher
+    /// * `subscriber` - The name of the subscriber
+    pub fn forget<Message>(publisher: &'static str, subscriber: &'static str) {
+        let subscription = Subscription {
+            publisher,
+            subscriber,
+            message_type: std::any::type_name::<Message>(),
+        };
+        let mut subscriptions = SUBSCRIPTIONS.lock().expect("topology lock poisoned");
+        if let Some(count) = subscriptions.get_mut(&subscription) {
+            *count -= 1;
+            if *count == 0 {
+                subscriptions.remove(&subscription);
+            }
+        }
+    }
+
+    /// Returns the subscriptions of the topology, sorted by publisher and subscriber names.
+    pub fn subscriptions(&self) -> &[Subscription] {
+        &self.subscriptions
+    }
+
+    /// Returns the names of the subscribers of the given publisher.
+    pub fn subscribers_of(&self, publisher: &str) -> Vec<&'static str> {
+        self.subscriptions
+            .iter()
+            .filter(|subscription| subscription.publisher == publisher)
+            .map(|subscription| subscription.subscriber)
+            .collect()
+    }
+
+    /// Returns the names of the publishers the given subscriber is subscribed to.
+    pub fn publishers_of(&self, subscriber: &str) -> Vec<&'static str> {
+        self.subscriptions
+            .iter()
+            .filter(|subscription| subscription.subscriber == subscriber)
+            .map(|subscription| subscription.publisher)
+            .collect()
+    }
+
+    /// Returns the names of the publishers and subscribers of the topology, sorted.
+    pub fn nodes(&self) -> Vec<&'static str> {
+        self.subscriptions
+            .iter()
+            .flat_map(|subscription| [subscription.publisher, subscription.subscriber])
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect()
+    }
+
+    /// Exports the topology as a Graphviz DOT directed graph, the edges being labelled with
+    /// the message types.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph topology {\n");
+        for node in self.nodes() {
+            let _ = writeln!(dot, "    {};", dot_string(node));
+        }
+        for subscription in &self.subscriptions {
+            let _ = writeln!(
+                dot,
+                "    {} -> {} [label={}];",
+                dot_string(subscription.publisher),
+                dot_string(subscription.subscriber),
+                dot_string(subscription.message_type),
+            );
+        }
+        dot.push('}');
+        dot
+    }
+
+    /// Exports the topology as a JSON object holding the `nodes` names and the `edges`, each
+    /// edge having a `publisher`, a `subscriber` and a `message_type`.
+    pub fn to_json(&self) -> String {
+        let nodes = self
+            .nodes()
+            .into_iter()
+            .map(json_string)
+            .collect::<Vec<_>>()
+            .join(",");
+        let edges = self
+            .subscriptions
+            .iter()
+            .map(|subscription| {
+                format!(
+                    r#"{{"publisher":{},"subscriber":{},"message_type":{}}}"#,
+                    json_string(subscription.publisher),
+                    json_string(subscription.subscriber),
+                    json_string(subscription.message_type),
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(",");
+        format!(r#"{{"nodes":[{nodes}],"edges":[{edges}]}}"#)
+    }
+}
+
+impl FromIterator<Subscription> for Topology {
+    fn from_iter<I: IntoIterator<Item = Subscription>>(iter: I) -> Self {
+        let subscriptions = iter.into_iter().collect::<BTreeSet<_>>();
+        Self {
+            subscriptions: subscriptions.into_iter().collect(),
+        }
+    }
+}
+
+/// Quotes a string as a DOT identifier.
+fn dot_string(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Quotes a string as a JSON string.
+fn json_string(value: &str) -> String {
+    let mut json = String::from("\"");
+    for character in value.chars() {
+        match character {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            character if character.is_control() => {
+                let _ = write!(json, "\\u{:04x}", character as u32);
+            }
+            character => json.push(character),
+        }
+    }
+    json.push('"');
+    json
+}
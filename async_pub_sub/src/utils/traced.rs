@@ -0,0 +1,123 @@
+use std::{cell::RefCell, pin::Pin};
+
+use futures::{Stream, StreamExt};
+use tracing::Span;
+
+/// A message envelope carrying the tracing span of the message.
+///
+/// The tracing middlewares ([`crate::TracingPublisherLayer`],
+/// [`crate::TracingForwarderLayer`] and [`crate::TracingSubscriberLayer`]) wrap the messages
+/// in this envelope at the edges of their middleware stacks, and open a child span of the
+/// envelope's span at each hop, so that all the hops of a message belong to the same trace.
+///
+/// # Example
+///
+/// ```
+/// use async_pub_sub::Traced;
+///
+/// let span = tracing::info_span!("request");
+/// let message = span.in_scope(|| Traced::new(42));
+///
+/// // the message is handled within its span
+/// let doubled = message.in_scope(|value| value * 2);
+/// assert_eq!(doubled, 84);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Traced<Message> {
+    message: Message,
+    span: Span,
+}
+
+impl<Message> Traced<Message> {
+    /// Wraps a message in an envelope carrying the current span.
+    pub fn new(message: Message) -> Self {
+        Self::with_span(message, Span::current())
+    }
+
+    /// Wraps a message in an envelope carrying the given span.
+    pub fn with_span(message: Message, span: Span) -> Self {
+        Self { message, span }
+    }
+
+    /// Returns the span of the message.
+    pub fn span(&self) -> &Span {
+        &self.span
+    }
+
+    /// Returns the message.
+    pub fn message(&self) -> &Message {
+        &self.message
+    }
+
+    /// Returns the message, mutably.
+    pub fn message_mut(&mut self) -> &mut Message {
+        &mut self.message
+    }
+
+    /// Returns the message, dropping its span.
+    pub fn into_inner(self) -> Message {
+        self.message
+    }
+
+    /// Returns the message and its span.
+    pub fn into_parts(self) -> (Message, Span) {
+        (self.message, self.span)
+    }
+
+    /// Converts the message, keeping its span.
+    pub fn map<Output>(self, f: impl FnOnce(Message) -> Output) -> Traced<Output> {
+        Traced {
+            message: self.span.in_scope(|| f(self.message)),
+            span: self.span,
+        }
+    }
+
+    /// Handles the message within its span.
+    pub fn in_scope<Output>(self, f: impl FnOnce(Message) -> Output) -> Output {
+        self.span.in_scope(|| f(self.message))
+    }
+
+    /// Replaces the span of the message.
+    pub(crate) fn set_span(&mut self, span: Span) {
+        self.span = span;
+    }
+}
+
+thread_local! {
+    /// The slot receiving the span of the message unwrapped by a tracing publisher, while a
+    /// tracing subscriber polls the stream of that publisher.
+    static HANDED_OVER: RefCell<Option<Option<Span>>> = const { RefCell::new(None) };
+}
+
+/// Unwraps a message at the edge of a publisher, handing its span over to the subscriber
+/// polling the stream, if that subscriber wraps the messages again with [`take_over`].
+pub(crate) fn hand_over<Message>(message: Traced<Message>) -> Message {
+    let (message, span) = message.into_parts();
+    HANDED_OVER.with(|slot| {
+        if let Some(slot) = slot.borrow_mut().as_mut() {
+            *slot = Some(span);
+        }
+    });
+    message
+}
+
+/// Wraps the messages of a publisher stream at the edge of a subscriber, with the span handed
+/// over by the publisher, or with the current span if the publisher did not hand one over.
+///
+/// The span is handed over while the stream is polled, so a message held back by a forwarder
+/// in between may be wrapped with the span of a later message.
+pub(crate) fn take_over<Message>(
+    mut stream: Pin<Box<dyn Stream<Item = Message> + Send + Sync + 'static>>,
+) -> impl Stream<Item = Traced<Message>> + Send + Sync + 'static
+where
+    Message: 'static,
+{
+    futures::stream::poll_fn(move |cx| {
+        let previous = HANDED_OVER.with(|slot| slot.replace(Some(None)));
+        let poll = stream.poll_next_unpin(cx);
+        let span = HANDED_OVER.with(|slot| slot.replace(previous)).flatten();
+        poll.map(|message| {
+            message.map(|message| Traced::with_span(message, span.unwrap_or_else(Span::current)))
+        })
+    })
+}
@@ -1,3 +1,5 @@
+#![allow(mismatched_lifetime_syntaxes)]
+
 use std::{fmt::Debug, pin::Pin};
 
 use async_pub_sub::{Publisher, PublisherImpl, Result, SubscriberImpl};
@@ -1,5 +1,7 @@
 // TODO: rework the test
 
+#![allow(mismatched_lifetime_syntaxes)]
+
 use std::pin::Pin;
 
 use async_pub_sub::{Publisher, PublisherImpl, Result, Subscriber, SubscriberImpl};
@@ -1,3 +1,5 @@
+#![allow(mismatched_lifetime_syntaxes)]
+
 use std::fmt::Display;
 
 use async_pub_sub::{Publisher, PublisherImpl, Request, Result, Subscriber, SubscriberImpl};
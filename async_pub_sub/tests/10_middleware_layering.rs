@@ -1,3 +1,5 @@
+#![allow(mismatched_lifetime_syntaxes)]
+
 use std::{fmt::Display, pin::Pin};
 
 use async_pub_sub::{Layer, Publisher, PublisherImpl, Result, SubscriberImpl};
@@ -0,0 +1,74 @@
+use async_pub_sub::{
+    LoggingPublisherLayer, PublisherBuilder, PublisherImpl, Request, Result, SubscriberImpl,
+};
+
+#[allow(dead_code)]
+mod interface {
+    use async_pub_sub_macros::rpc_interface;
+    #[rpc_interface]
+    pub trait RpcInterface {
+        async fn add(&self, left: i32, right: i32) -> i32;
+        async fn prefix_with_bar(&self, string: String) -> String;
+        async fn reset(&mut self);
+    }
+}
+
+use interface::{RpcInterface, RpcInterfaceMessage, RpcInterfaceMethod};
+
+#[test]
+fn test_rpc_message_introspection() {
+    let (request, _response) = Request::new((1, 2));
+    let add = RpcInterfaceMessage::Add(request);
+    let (request, _response) = Request::new("foo".to_string());
+    let prefix_with_bar = RpcInterfaceMessage::PrefixWithBar(request);
+    let (request, _response) = Request::new(());
+    let reset = RpcInterfaceMessage::Reset(request);
+
+    assert_eq!(add.method(), RpcInterfaceMethod::Add);
+    assert_eq!(add.method_name(), "add");
+    assert_eq!(prefix_with_bar.method_name(), "prefix_with_bar");
+    assert_eq!(reset.method_name(), "reset");
+
+    assert_eq!(
+        RpcInterfaceMessage::METHODS,
+        &["add", "prefix_with_bar", "reset"]
+    );
+    assert_eq!(
+        RpcInterfaceMethod::ALL
+            .iter()
+            .map(RpcInterfaceMethod::name)
+            .collect::<Vec<_>>(),
+        RpcInterfaceMessage::METHODS
+    );
+
+    assert_eq!(add.to_string(), "add(1, 2)");
+    assert_eq!(prefix_with_bar.to_string(), "prefix_with_bar(\"foo\")");
+    assert_eq!(reset.to_string(), "reset()");
+    assert_eq!(
+        RpcInterfaceMethod::PrefixWithBar.to_string(),
+        "prefix_with_bar"
+    );
+}
+
+#[test_log::test(tokio::test)]
+async fn test_rpc_message_logging() -> Result<()> {
+    let publisher = PublisherBuilder::new()
+        .layer(LoggingPublisherLayer)
+        .publisher(PublisherImpl::new("rpc_client", 1));
+    let mut rpc_client = interface::RpcInterfaceClient::new(publisher);
+    let mut subscriber = SubscriberImpl::new("rpc_server");
+
+    subscriber.subscribe_to(&mut rpc_client)?;
+
+    tokio::spawn(async move {
+        let RpcInterfaceMessage::Add(request) = subscriber.receive().await else {
+            panic!("unexpected request");
+        };
+        let (left, right) = request.content;
+        request.respond(left + right);
+    });
+
+    assert_eq!(rpc_client.add(40, 2).await, 42);
+
+    Ok(())
+}
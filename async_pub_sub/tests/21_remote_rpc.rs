@@ -0,0 +1,314 @@
+#![cfg(feature = "serde")]
+
+use std::{
+    pin::Pin,
+    sync::atomic::{AtomicBool, Ordering},
+    time::{Duration, Instant},
+};
+
+use async_pub_sub::{
+    Publisher, PublisherImpl, RemoteRpcClient, RemoteRpcMessage, RemoteRpcServer, Request, Result,
+    Subscriber, SubscriberImpl, Timer, bytes::Bytes,
+};
+use futures::{FutureExt, Stream, future::BoxFuture};
+
+mod interface {
+    use async_pub_sub_macros::rpc_interface;
+    #[rpc_interface(Debug, serde)]
+    pub trait RpcInterface {
+        async fn add(&self, left: i32, right: i32) -> i32;
+        async fn store_data(&mut self, data: Vec<u8>);
+        async fn get_data(&self) -> Vec<u8>;
+    }
+}
+
+mod server {
+    use super::RpcInterface;
+    use async_pub_sub::Subscriber;
+    use async_pub_sub_macros::DeriveSubscriber;
+
+    #[derive(DeriveSubscriber)]
+    pub struct RpcServer<S>
+    where
+        S: Subscriber,
+    {
+        pub subscriber: S,
+        pub data: Vec<u8>,
+    }
+
+    impl<S> RpcInterface for RpcServer<S>
+    where
+        S: Subscriber,
+    {
+        async fn add(&self, left: i32, right: i32) -> i32 {
+            left + right
+        }
+
+        async fn store_data(&mut self, data: Vec<u8>) {
+            self.data = data;
+        }
+
+        async fn get_data(&self) -> Vec<u8> {
+            self.data.clone()
+        }
+    }
+}
+
+use interface::RpcInterfaceServer;
+use interface::{RpcInterface, RpcInterfaceCall, RpcInterfaceMessage, RpcInterfaceReply};
+
+#[test]
+fn test_rpc_message_call_conversion() {
+    let (request, response) = Request::new((1, 2));
+    let request = request.with_correlation_id(7);
+    let id = request.metadata().id();
+    let (call, reply_sender) = RpcInterfaceMessage::Add(request).into_call();
+
+    // the call carries the metadata of the request and is correlated with its id
+    assert!(matches!(
+        &call,
+        RpcInterfaceCall::Add {
+            metadata,
+            args: (1, 2)
+        } if metadata.correlation_id() == Some(7)
+    ));
+    assert_eq!(RpcInterfaceMessage::call_correlation_id(&call), id);
+
+    // the rebuilt request carries the same metadata
+    let (message, _reply) = RpcInterfaceMessage::from_call(call);
+    let RpcInterfaceMessage::Add(request) = message else {
+        panic!("the call should be rebuilt as an add request");
+    };
+    assert_eq!(request.metadata().id(), id);
+    assert_eq!(request.metadata().correlation_id(), Some(7));
+
+    let reply = RpcInterfaceReply::Add {
+        correlation_id: id,
+        response: 3,
+    };
+    assert_eq!(RpcInterfaceMessage::reply_correlation_id(&reply), id);
+
+    reply_sender(reply).unwrap();
+    assert_eq!(futures::executor::block_on(response).unwrap(), 3);
+}
+
+#[test]
+fn test_rpc_message_unexpected_reply() {
+    let (request, _response) = Request::new(());
+    let (_call, reply_sender) = RpcInterfaceMessage::GetData(request).into_call();
+
+    let reply = RpcInterfaceReply::Add {
+        correlation_id: 0,
+        response: 3,
+    };
+    assert!(reply_sender(reply).is_err());
+}
+
+#[test_log::test(tokio::test)]
+async fn test_remote_rpc() -> Result<()> {
+    // -- Setup & Fixtures
+    // The byte transport between both "processes", one publisher per direction
+    let mut calls_transport = PublisherImpl::<Bytes>::new("calls_transport", 10);
+    let mut replies_transport = PublisherImpl::<Bytes>::new("replies_transport", 10);
+
+    let mut calls_subscriber = SubscriberImpl::new("calls_subscriber");
+    calls_subscriber.subscribe_to(&mut calls_transport)?;
+    let mut replies_subscriber = SubscriberImpl::new("replies_subscriber");
+    replies_subscriber.subscribe_to(&mut replies_transport)?;
+
+    // client side
+    let mut rpc_client = interface::RpcInterfaceClient::new(PublisherImpl::new("rpc_client", 1));
+    let mut remote_client =
+        RemoteRpcClient::new("remote_client", calls_transport, replies_subscriber);
+    remote_client.subscribe_to(&mut rpc_client)?;
+
+    // server side
+    let mut remote_server =
+        RemoteRpcServer::new("remote_server", 1, calls_subscriber, replies_transport);
+    let mut rpc_server = server::RpcServer {
+        subscriber: SubscriberImpl::new("rpc_server"),
+        data: Vec::new(),
+    };
+    rpc_server.subscribe_to(&mut remote_server)?;
+
+    tokio::spawn(async move { remote_client.run().await.unwrap() });
+    tokio::spawn(async move { remote_server.run().await.unwrap() });
+    tokio::spawn(async move { rpc_server.run().await });
+
+    // -- Exec & Check
+    assert_eq!(rpc_client.add(40, 2).await, 42);
+
+    rpc_client.store_data(vec![1, 2, 3]).await;
+    assert_eq!(rpc_client.get_data().await, vec![1, 2, 3]);
+
+    let (first, second) = tokio::join!(rpc_client.add(1, 1), rpc_client.add(2, 2));
+    assert_eq!((first, second), (2, 4));
+
+    Ok(())
+}
+
+#[cfg(feature = "msgpack")]
+#[test_log::test(tokio::test)]
+async fn test_remote_rpc_with_codec() -> Result<()> {
+    use async_pub_sub::MessagePackCodec;
+
+    // -- Setup & Fixtures
+    let mut calls_transport = PublisherImpl::<Bytes>::new("calls_transport", 10);
+    let mut replies_transport = PublisherImpl::<Bytes>::new("replies_transport", 10);
+
+    let mut calls_subscriber = SubscriberImpl::new("calls_subscriber");
+    calls_subscriber.subscribe_to(&mut calls_transport)?;
+    let mut replies_subscriber = SubscriberImpl::new("replies_subscriber");
+    replies_subscriber.subscribe_to(&mut replies_transport)?;
+
+    // both ends encode the calls and replies with MessagePack instead of JSON
+    let mut rpc_client = interface::RpcInterfaceClient::new(PublisherImpl::new("rpc_client", 1));
+    let mut remote_client =
+        RemoteRpcClient::new("remote_client", calls_transport, replies_subscriber)
+            .with_codec(MessagePackCodec);
+    remote_client.subscribe_to(&mut rpc_client)?;
+
+    let mut remote_server =
+        RemoteRpcServer::new("remote_server", 1, calls_subscriber, replies_transport)
+            .with_codec(MessagePackCodec);
+    let mut rpc_server = server::RpcServer {
+        subscriber: SubscriberImpl::new("rpc_server"),
+        data: Vec::new(),
+    };
+    rpc_server.subscribe_to(&mut remote_server)?;
+
+    tokio::spawn(async move { remote_client.run().await.unwrap() });
+    tokio::spawn(async move { remote_server.run().await.unwrap() });
+    tokio::spawn(async move { rpc_server.run().await });
+
+    // -- Exec & Check
+    assert_eq!(rpc_client.add(40, 2).await, 42);
+    rpc_client.store_data(vec![4, 5, 6]).await;
+    assert_eq!(rpc_client.get_data().await, vec![4, 5, 6]);
+
+    Ok(())
+}
+
+struct TestTimer;
+
+impl Timer for TestTimer {
+    fn now(&self) -> Instant {
+        tokio::time::Instant::now().into_std()
+    }
+
+    fn sleep(&self, duration: Duration) -> BoxFuture<'static, ()> {
+        tokio::time::sleep(duration).boxed()
+    }
+}
+
+/// A transport publisher failing to publish its first message.
+struct FlakyPublisher {
+    publisher: PublisherImpl<Bytes>,
+    failed: AtomicBool,
+}
+
+impl Publisher for FlakyPublisher {
+    type Message = Bytes;
+
+    fn get_name(&self) -> &'static str {
+        Publisher::get_name(&self.publisher)
+    }
+
+    fn publish(&self, message: Bytes) -> BoxFuture<'_, Result<()>> {
+        if !self.failed.swap(true, Ordering::SeqCst) {
+            return async { Err("transport unavailable".into()) }.boxed();
+        }
+        Publisher::publish(&self.publisher, message)
+    }
+
+    fn get_message_stream(
+        &mut self,
+        subscriber_name: &'static str,
+    ) -> Result<Pin<Box<dyn Stream<Item = Bytes> + Send + Sync + 'static>>> {
+        self.publisher.get_message_stream(subscriber_name)
+    }
+}
+
+#[test_log::test(tokio::test)]
+async fn test_remote_rpc_failed_publish() -> Result<()> {
+    // -- Setup & Fixtures
+    let mut calls_transport = FlakyPublisher {
+        publisher: PublisherImpl::new("calls_transport", 10),
+        failed: AtomicBool::new(false),
+    };
+    let mut replies_transport = PublisherImpl::<Bytes>::new("replies_transport", 10);
+
+    let mut calls_subscriber = SubscriberImpl::new("calls_subscriber");
+    calls_subscriber.subscribe_to(&mut calls_transport)?;
+    let mut replies_subscriber = SubscriberImpl::new("replies_subscriber");
+    replies_subscriber.subscribe_to(&mut replies_transport)?;
+
+    let mut requests = PublisherImpl::<RpcInterfaceMessage>::new("requests", 1);
+    let mut remote_client =
+        RemoteRpcClient::new("remote_client", calls_transport, replies_subscriber);
+    remote_client.subscribe_to(&mut requests)?;
+
+    let mut remote_server =
+        RemoteRpcServer::new("remote_server", 1, calls_subscriber, replies_transport);
+    let mut rpc_server = server::RpcServer {
+        subscriber: SubscriberImpl::new("rpc_server"),
+        data: Vec::new(),
+    };
+    rpc_server.subscribe_to(&mut remote_server)?;
+
+    tokio::spawn(async move { remote_client.run().await.unwrap() });
+    tokio::spawn(async move { remote_server.run().await.unwrap() });
+    tokio::spawn(async move { rpc_server.run().await });
+
+    // -- Exec
+    let (request, failed_response) = Request::new((1, 1));
+    requests.publish(RpcInterfaceMessage::Add(request)).await?;
+    let (request, response) = Request::new((40, 2));
+    requests.publish(RpcInterfaceMessage::Add(request)).await?;
+
+    // -- Check
+    // only the call that could not be published is dropped
+    assert!(failed_response.await.is_err());
+    assert_eq!(response.await?, 42);
+
+    Ok(())
+}
+
+#[test_log::test(tokio::test(start_paused = true))]
+async fn test_remote_rpc_call_timeout() -> Result<()> {
+    // -- Setup & Fixtures
+    // the calls are never answered
+    let mut calls_transport = PublisherImpl::<Bytes>::new("calls_transport", 10);
+    let mut replies_transport = PublisherImpl::<Bytes>::new("replies_transport", 10);
+
+    let mut calls_subscriber = SubscriberImpl::new("calls_subscriber");
+    calls_subscriber.subscribe_to(&mut calls_transport)?;
+    let mut replies_subscriber = SubscriberImpl::new("replies_subscriber");
+    replies_subscriber.subscribe_to(&mut replies_transport)?;
+
+    let mut requests = PublisherImpl::<RpcInterfaceMessage>::new("requests", 1);
+    let mut remote_client =
+        RemoteRpcClient::new("remote_client", calls_transport, replies_subscriber)
+            .with_call_timeout(Duration::from_secs(1), TestTimer);
+    remote_client.subscribe_to(&mut requests)?;
+
+    let run = tokio::spawn(async move { remote_client.run().await.is_ok() });
+
+    // -- Exec
+    let (request, response) = Request::new((40, 2));
+    requests.publish(RpcInterfaceMessage::Add(request)).await?;
+
+    // -- Check
+    // the pending call is dropped once timed out
+    let started = tokio::time::Instant::now();
+    assert!(response.await.is_err());
+    assert_eq!(started.elapsed(), Duration::from_secs(1));
+
+    // the remote client stops once the requests have ended and no call is pending
+    drop(requests);
+    assert!(tokio::time::timeout(Duration::from_secs(1), run).await??);
+
+    drop((calls_subscriber, replies_transport));
+
+    Ok(())
+}
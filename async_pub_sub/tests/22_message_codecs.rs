@@ -0,0 +1,103 @@
+#![cfg(feature = "serde")]
+
+use async_pub_sub::{
+    Codec, DecodingSubscriberLayer, EncodingPublisherLayer, JsonCodec, Publisher, PublisherBuilder,
+    PublisherImpl, Result, Subscriber, SubscriberBuilder, SubscriberImpl, bytes::Bytes,
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Measure {
+    sensor: String,
+    value: f64,
+}
+
+fn measure(value: f64) -> Measure {
+    Measure {
+        sensor: "temperature".to_string(),
+        value,
+    }
+}
+
+fn check_round_trip(codec: impl Codec<Measure>) -> Result<()> {
+    let bytes = codec.encode(&measure(1.5))?;
+    assert_eq!(codec.decode(&bytes)?, measure(1.5));
+    assert!(codec.decode(b"\xff\xff").is_err());
+    Ok(())
+}
+
+#[test]
+fn test_json_codec() -> Result<()> {
+    check_round_trip(JsonCodec)
+}
+
+#[cfg(feature = "postcard")]
+#[test]
+fn test_postcard_codec() -> Result<()> {
+    check_round_trip(async_pub_sub::PostcardCodec)
+}
+
+#[cfg(feature = "msgpack")]
+#[test]
+fn test_message_pack_codec() -> Result<()> {
+    check_round_trip(async_pub_sub::MessagePackCodec)
+}
+
+#[tokio::test]
+async fn test_encoding_publisher_layer() -> Result<()> {
+    // -- Setup & Fixtures
+    let mut publisher = PublisherBuilder::new()
+        .layer(EncodingPublisherLayer::new(JsonCodec))
+        .publisher(PublisherImpl::<Measure>::new("publisher", 10));
+    let mut subscriber = SubscriberImpl::<Bytes>::new("subscriber");
+    subscriber.subscribe_to(&mut publisher)?;
+
+    // -- Exec
+    publisher.publish(JsonCodec.encode(&measure(1.0))?).await?;
+    let invalid_message = publisher.publish(Bytes::from_static(b"not json")).await;
+
+    // -- Check
+    assert!(invalid_message.is_err());
+    let received: Measure = JsonCodec.decode(&subscriber.receive().await)?;
+    assert_eq!(received, measure(1.0));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_decoding_subscriber_layer() -> Result<()> {
+    // -- Setup & Fixtures
+    let mut publisher = PublisherImpl::<Measure>::new("publisher", 10);
+    let mut subscriber = SubscriberBuilder::new()
+        .layer(DecodingSubscriberLayer::<Measure, _>::new(JsonCodec))
+        .subscriber(SubscriberImpl::<Bytes>::new("subscriber"));
+    subscriber.subscribe_to(&mut publisher)?;
+
+    // -- Exec
+    publisher.publish(measure(2.0)).await?;
+
+    // -- Check
+    assert_eq!(subscriber.receive().await, measure(2.0));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_decoding_subscriber_skips_invalid_messages() -> Result<()> {
+    // -- Setup & Fixtures
+    let mut publisher = PublisherImpl::<Bytes>::new("publisher", 10);
+    let mut bytes_subscriber = SubscriberImpl::<Bytes>::new("subscriber");
+    bytes_subscriber.subscribe_to(&mut publisher)?;
+    let mut subscriber = SubscriberBuilder::new()
+        .layer(DecodingSubscriberLayer::<Measure, _>::new(JsonCodec))
+        .subscriber(bytes_subscriber);
+
+    // -- Exec
+    publisher.publish(Bytes::from_static(b"not json")).await?;
+    publisher.publish(JsonCodec.encode(&measure(3.0))?).await?;
+
+    // -- Check
+    assert_eq!(subscriber.receive().await, measure(3.0));
+
+    Ok(())
+}
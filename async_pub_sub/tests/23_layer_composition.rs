@@ -0,0 +1,199 @@
+use std::{
+    pin::Pin,
+    sync::{Arc, Mutex},
+};
+
+use async_pub_sub::{
+    DebuggingForwarderLayer, DebuggingSubscriberLayer, Either, ForwarderBuilder, ForwarderImpl,
+    Layer, LoggingPublisherLayer, LoggingSubscriberLayer, Publisher, PublisherBuilder,
+    PublisherImpl, Result, Subscriber, SubscriberBuilder, SubscriberImpl, layer_fn,
+};
+use futures::{FutureExt, Stream, future::BoxFuture};
+
+/// Layer recording the order in which the publishers it creates are traversed.
+#[derive(Clone)]
+struct RecordingPublisherLayer {
+    tag: &'static str,
+    records: Arc<Mutex<Vec<&'static str>>>,
+}
+
+impl<P: Publisher> Layer<P> for RecordingPublisherLayer {
+    type LayerType = RecordingPublisher<P>;
+
+    fn layer(&self, publisher: P) -> Self::LayerType {
+        RecordingPublisher {
+            layer: self.clone(),
+            publisher,
+        }
+    }
+}
+
+struct RecordingPublisher<P> {
+    layer: RecordingPublisherLayer,
+    publisher: P,
+}
+
+impl<P> Publisher for RecordingPublisher<P>
+where
+    P: Publisher + Sync,
+{
+    type Message = P::Message;
+
+    fn get_name(&self) -> &'static str {
+        self.publisher.get_name()
+    }
+
+    fn publish(&self, message: Self::Message) -> BoxFuture<'_, Result<()>> {
+        async move {
+            self.layer.records.lock().unwrap().push(self.layer.tag);
+            self.publisher.publish(message).await
+        }
+        .boxed()
+    }
+
+    fn get_message_stream(
+        &mut self,
+        subscriber_name: &'static str,
+    ) -> Result<Pin<Box<dyn Stream<Item = Self::Message> + Send + Sync + 'static>>> {
+        self.publisher.get_message_stream(subscriber_name)
+    }
+}
+
+fn recording_layer(
+    tag: &'static str,
+    records: &Arc<Mutex<Vec<&'static str>>>,
+) -> RecordingPublisherLayer {
+    RecordingPublisherLayer {
+        tag,
+        records: records.clone(),
+    }
+}
+
+#[tokio::test]
+async fn test_publisher_builder_stacks_layers() -> Result<()> {
+    // -- Setup & Fixtures
+    let records = Arc::new(Mutex::new(Vec::new()));
+    let mut publisher = PublisherBuilder::new()
+        .layer(recording_layer("first", &records))
+        .layer(recording_layer("second", &records))
+        .layer(LoggingPublisherLayer)
+        .publisher(PublisherImpl::new("publisher", 1));
+    let mut subscriber = SubscriberImpl::new("subscriber");
+    subscriber.subscribe_to(&mut publisher)?;
+
+    // -- Exec
+    publisher.publish(42).await?;
+
+    // -- Check
+    assert_eq!(subscriber.receive().await, 42);
+    assert_eq!(*records.lock().unwrap(), vec!["first", "second"]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_option_layer() -> Result<()> {
+    // -- Setup & Fixtures
+    let records = Arc::new(Mutex::new(Vec::new()));
+    let mut enabled = PublisherBuilder::new()
+        .layer(Some(recording_layer("enabled", &records)))
+        .publisher(PublisherImpl::new("enabled", 1));
+    let mut disabled = PublisherBuilder::new()
+        .layer(None::<RecordingPublisherLayer>)
+        .publisher(PublisherImpl::new("disabled", 1));
+    let mut subscriber = SubscriberImpl::new("subscriber");
+    subscriber.subscribe_to(&mut enabled)?;
+    subscriber.subscribe_to(&mut disabled)?;
+
+    // -- Exec
+    enabled.publish(1).await?;
+    disabled.publish(2).await?;
+
+    // -- Check
+    assert!(matches!(enabled, Either::Left(_)));
+    assert!(matches!(disabled, Either::Right(_)));
+    let mut received = vec![subscriber.receive().await, subscriber.receive().await];
+    received.sort();
+    assert_eq!(received, vec![1, 2]);
+    assert_eq!(*records.lock().unwrap(), vec!["enabled"]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_either_layer() -> Result<()> {
+    // -- Setup & Fixtures
+    let records = Arc::new(Mutex::new(Vec::new()));
+    let layer = |verbose: bool| {
+        if verbose {
+            Either::Left(recording_layer("verbose", &records))
+        } else {
+            Either::Right(recording_layer("quiet", &records))
+        }
+    };
+    let mut publisher = PublisherBuilder::new()
+        .layer(layer(false))
+        .publisher(PublisherImpl::new("publisher", 1));
+    let mut subscriber = SubscriberBuilder::new()
+        .layer(LoggingSubscriberLayer)
+        .layer(DebuggingSubscriberLayer)
+        .subscriber(SubscriberImpl::new("subscriber"));
+    subscriber.subscribe_to(&mut publisher)?;
+
+    // -- Exec
+    publisher.publish(3).await?;
+
+    // -- Check
+    assert_eq!(subscriber.receive().await, 3);
+    assert_eq!(*records.lock().unwrap(), vec!["quiet"]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_layer_fn() -> Result<()> {
+    // -- Setup & Fixtures
+    let records = Arc::new(Mutex::new(Vec::new()));
+    let layer = recording_layer("closure", &records);
+    let mut publisher = PublisherBuilder::new()
+        .layer(layer_fn(move |publisher| layer.layer(publisher)))
+        .publisher(PublisherImpl::new("publisher", 1));
+    let mut forwarder = ForwarderBuilder::new()
+        .layer(DebuggingForwarderLayer)
+        .layer(Some(DebuggingForwarderLayer))
+        .forwarder(ForwarderImpl::new("forwarder"));
+    let mut subscriber = SubscriberImpl::new("subscriber");
+    forwarder.subscribe_to(&mut publisher)?;
+    subscriber.subscribe_to(&mut forwarder)?;
+
+    // -- Exec
+    publisher.publish(4).await?;
+
+    // -- Check
+    assert_eq!(subscriber.receive().await, 4);
+    assert_eq!(*records.lock().unwrap(), vec!["closure"]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_try_receive_through_layers() -> Result<()> {
+    // -- Setup & Fixtures
+    let mut publisher = PublisherImpl::new("publisher", 10);
+    let mut subscriber = SubscriberBuilder::new()
+        .layer(LoggingSubscriberLayer)
+        .layer(DebuggingSubscriberLayer)
+        .subscriber(SubscriberImpl::new("subscriber"));
+    subscriber.subscribe_to(&mut publisher)?;
+
+    publisher.publish(1).await?;
+    drop(publisher);
+
+    // -- Exec & Check
+    assert_eq!(subscriber.try_receive(), Some(1));
+    // the stream has ended, the layers must not poll it again through `receive`
+    assert_eq!(subscriber.try_receive(), None);
+    assert_eq!(subscriber.try_receive(), None);
+
+    Ok(())
+}
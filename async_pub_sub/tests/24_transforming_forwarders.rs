@@ -0,0 +1,77 @@
+use async_pub_sub::{
+    DebuggingForwarderLayer, ForwarderBuilder, MapForwarder, PublisherImpl, Result, SubscriberImpl,
+    ThenForwarder, macros::routes,
+};
+
+#[derive(Debug, PartialEq)]
+struct Celsius(f64);
+
+#[derive(Debug, PartialEq)]
+struct Fahrenheit(f64);
+
+#[tokio::test]
+async fn test_map_forwarder() -> Result<()> {
+    // -- Setup & Fixtures
+    let mut publisher = PublisherImpl::new("thermometer", 10);
+    let mut forwarder =
+        MapForwarder::new("converter", |Celsius(value)| Fahrenheit(value * 1.8 + 32.0));
+    let mut subscriber = SubscriberImpl::new("display");
+
+    routes! {
+        publisher -> forwarder,
+        forwarder -> subscriber,
+    }?;
+
+    // -- Exec
+    publisher.publish(Celsius(100.0)).await?;
+    publisher.publish(Celsius(-40.0)).await?;
+
+    // -- Check
+    assert_eq!(subscriber.receive().await, Fahrenheit(212.0));
+    assert_eq!(subscriber.receive().await, Fahrenheit(-40.0));
+
+    Ok(())
+}
+
+#[test_log::test(tokio::test)]
+async fn test_then_forwarder_with_layer() -> Result<()> {
+    // -- Setup & Fixtures
+    let mut publisher = PublisherImpl::new("publisher", 10);
+    let mut forwarder = ForwarderBuilder::new()
+        .layer(DebuggingForwarderLayer)
+        .forwarder(ThenForwarder::new("stringifier", |value: i32| async move {
+            tokio::task::yield_now().await;
+            format!("value: {value}")
+        }));
+    let mut subscriber = SubscriberImpl::new("subscriber");
+
+    routes! {
+        publisher -> forwarder,
+        forwarder -> subscriber,
+    }?;
+
+    // -- Exec
+    publisher.publish(1).await?;
+    publisher.publish(2).await?;
+
+    // -- Check
+    assert_eq!(subscriber.receive().await, "value: 1");
+    assert_eq!(subscriber.receive().await, "value: 2");
+
+    Ok(())
+}
+
+#[test]
+fn test_map_forwarder_single_subscriber() {
+    // -- Setup & Fixtures
+    let mut forwarder = MapForwarder::new("forwarder", |value: i32| value.to_string());
+    let mut first_subscriber = SubscriberImpl::new("first_subscriber");
+    let mut second_subscriber = SubscriberImpl::new("second_subscriber");
+
+    // -- Exec
+    first_subscriber.subscribe_to(&mut forwarder).unwrap();
+    let result = second_subscriber.subscribe_to(&mut forwarder);
+
+    // -- Check
+    assert!(result.is_err());
+}
@@ -0,0 +1,88 @@
+use std::sync::atomic::Ordering;
+
+use async_pub_sub::{
+    DebuggingForwarderLayer, FilterForwarder, FilterMapForwarder, ForwarderBuilder, PublisherImpl,
+    Result, Subscriber, SubscriberImpl, macros::routes,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+enum Event {
+    Measure { sensor: u32, value: f64 },
+    Error(String),
+}
+
+#[test_log::test(tokio::test)]
+async fn test_filter_forwarder() -> Result<()> {
+    // -- Setup & Fixtures
+    let mut publisher = PublisherImpl::new("publisher", 10);
+    let filter = FilterForwarder::new("errors_only", |event: &Event| {
+        matches!(event, Event::Error(_))
+    });
+    let dropped = filter.dropped_counter();
+    let mut forwarder = ForwarderBuilder::new()
+        .layer(DebuggingForwarderLayer)
+        .forwarder(filter);
+    let mut subscriber = SubscriberImpl::new("subscriber");
+
+    routes! {
+        publisher -> forwarder,
+        forwarder -> subscriber,
+    }?;
+
+    // -- Exec
+    publisher
+        .publish(Event::Measure {
+            sensor: 1,
+            value: 1.0,
+        })
+        .await?;
+    publisher
+        .publish(Event::Error("overheat".to_string()))
+        .await?;
+
+    // -- Check
+    assert_eq!(
+        subscriber.receive().await,
+        Event::Error("overheat".to_string())
+    );
+    assert_eq!(dropped.load(Ordering::Relaxed), 1);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_filter_map_forwarder() -> Result<()> {
+    // -- Setup & Fixtures
+    let mut publisher = PublisherImpl::new("publisher", 10);
+    let mut forwarder = FilterMapForwarder::new("sensor_2", |event: Event| match event {
+        Event::Measure { sensor: 2, value } => Some(value),
+        _ => None,
+    });
+    let mut subscriber = SubscriberImpl::new("subscriber");
+
+    forwarder.subscribe_to(&mut publisher)?;
+    subscriber.subscribe_to(&mut forwarder)?;
+
+    // -- Exec
+    publisher
+        .publish(Event::Measure {
+            sensor: 1,
+            value: 1.0,
+        })
+        .await?;
+    publisher
+        .publish(Event::Error("overheat".to_string()))
+        .await?;
+    publisher
+        .publish(Event::Measure {
+            sensor: 2,
+            value: 2.0,
+        })
+        .await?;
+
+    // -- Check
+    assert_eq!(subscriber.receive().await, 2.0);
+    assert_eq!(forwarder.dropped_count(), 2);
+
+    Ok(())
+}
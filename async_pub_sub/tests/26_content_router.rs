@@ -0,0 +1,132 @@
+use async_pub_sub::{
+    PublisherImpl, Result, Router, SubscriberImpl,
+    macros::{route, routes},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Kind {
+    Measure,
+    Error,
+}
+
+#[derive(Debug, PartialEq)]
+enum Event {
+    Measure(f64),
+    Error(&'static str),
+    Heartbeat,
+}
+
+fn kind(event: &Event) -> Option<Kind> {
+    match event {
+        Event::Measure(_) => Some(Kind::Measure),
+        Event::Error(_) => Some(Kind::Error),
+        Event::Heartbeat => None,
+    }
+}
+
+#[test_log::test(tokio::test)]
+async fn test_router_splits_stream() -> Result<()> {
+    // -- Setup & Fixtures
+    let mut publisher = PublisherImpl::new("events", 10);
+    let mut router = Router::new("router", 10, kind);
+    router.add_output(Some(Kind::Measure), "measures", 10)?;
+    router.add_output(Some(Kind::Error), "errors", 10)?;
+
+    let mut measures = SubscriberImpl::new("measures_subscriber");
+    let mut errors = SubscriberImpl::new("errors_subscriber");
+    let mut others = SubscriberImpl::new("others_subscriber");
+
+    route!(publisher -> router)?;
+    routes! {
+        router.output(&Some(Kind::Measure))? -> measures,
+        router.output(&Some(Kind::Error))? -> errors,
+        router.default_output() -> others,
+    }?;
+    tokio::spawn(async move { router.run().await });
+
+    // -- Exec
+    publisher.publish(Event::Measure(1.0)).await?;
+    publisher.publish(Event::Heartbeat).await?;
+    publisher.publish(Event::Error("overheat")).await?;
+    publisher.publish(Event::Measure(2.0)).await?;
+
+    // -- Check
+    assert_eq!(measures.receive().await, Event::Measure(1.0));
+    assert_eq!(measures.receive().await, Event::Measure(2.0));
+    assert_eq!(errors.receive().await, Event::Error("overheat"));
+    assert_eq!(others.receive().await, Event::Heartbeat);
+
+    Ok(())
+}
+
+#[test_log::test(tokio::test)]
+async fn test_router_stops_at_the_end_of_the_stream() -> Result<()> {
+    // -- Setup & Fixtures
+    let mut publisher = PublisherImpl::new("events", 10);
+    let mut router = Router::new("router", 10, kind);
+    router.add_output(Some(Kind::Measure), "measures", 10)?;
+
+    let mut measures = SubscriberImpl::new("measures_subscriber");
+    let mut others = SubscriberImpl::new("others_subscriber");
+
+    route!(publisher -> router)?;
+    routes! {
+        router.output(&Some(Kind::Measure))? -> measures,
+        router.default_output() -> others,
+    }?;
+    let run = tokio::spawn(async move { router.run().await });
+
+    // -- Exec
+    publisher.publish(Event::Measure(1.0)).await?;
+    publisher.publish(Event::Heartbeat).await?;
+    drop(publisher);
+
+    // -- Check
+    tokio::time::timeout(std::time::Duration::from_secs(1), run).await??;
+
+    // the outputs are closed once the routed messages are received
+    assert_eq!(measures.next_message().await, Some(Event::Measure(1.0)));
+    assert_eq!(measures.next_message().await, None);
+    assert_eq!(others.next_message().await, Some(Event::Heartbeat));
+    assert_eq!(others.next_message().await, None);
+
+    Ok(())
+}
+
+#[test_log::test(tokio::test)]
+async fn test_router_drops_unbound_outputs() -> Result<()> {
+    // -- Setup & Fixtures
+    let mut publisher = PublisherImpl::new("events", 10);
+    let mut router = Router::new("router", 10, kind);
+    router.add_output(Some(Kind::Measure), "measures", 1)?;
+    router.add_output(Some(Kind::Error), "errors", 1)?;
+    let mut errors = SubscriberImpl::new("errors_subscriber");
+
+    route!(publisher -> router)?;
+    route!(router.output(&Some(Kind::Error))? -> errors)?;
+    tokio::spawn(async move { router.run().await });
+
+    // -- Exec
+    for value in 0..5 {
+        publisher.publish(Event::Measure(value as f64)).await?;
+        publisher.publish(Event::Heartbeat).await?;
+    }
+    publisher.publish(Event::Error("overheat")).await?;
+
+    // -- Check
+    assert_eq!(errors.receive().await, Event::Error("overheat"));
+
+    Ok(())
+}
+
+#[test]
+fn test_router_outputs() {
+    // -- Setup & Fixtures
+    let mut router = Router::<Event, _>::new("router", 1, kind);
+
+    // -- Exec & Check
+    assert!(router.add_output(Some(Kind::Error), "errors", 1).is_ok());
+    assert!(router.add_output(Some(Kind::Error), "errors", 1).is_err());
+    assert!(router.output(&Some(Kind::Error)).is_ok());
+    assert!(router.output(&Some(Kind::Measure)).is_err());
+}
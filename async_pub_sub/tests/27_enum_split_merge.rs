@@ -0,0 +1,136 @@
+use async_pub_sub::{
+    Publisher, PublisherImpl, Result, Subscriber, SubscriberImpl,
+    macros::{MergeSubscriber, SplitPublisher, routes},
+};
+
+#[derive(Debug, Clone, PartialEq, SplitPublisher, MergeSubscriber)]
+enum Event {
+    Measure(f64),
+    SensorError(String),
+    Heartbeat,
+}
+
+#[test_log::test(tokio::test)]
+async fn test_split_publisher() -> Result<()> {
+    // -- Setup & Fixtures
+    let mut publisher = PublisherImpl::new("events", 10);
+    let mut splitter = EventSplitter::new("splitter", 10);
+    let mut measures = SubscriberImpl::<f64>::new("measures");
+    let mut errors = SubscriberImpl::<String>::new("errors");
+
+    routes! {
+        publisher -> splitter,
+        splitter.measure -> measures,
+        splitter.sensor_error -> errors,
+    }?;
+    tokio::spawn(async move { splitter.run().await });
+
+    // -- Exec
+    publisher.publish(Event::Measure(1.0)).await?;
+    publisher.publish(Event::Heartbeat).await?;
+    publisher
+        .publish(Event::SensorError("disconnected".to_string()))
+        .await?;
+    publisher.publish(Event::Measure(2.0)).await?;
+
+    // -- Check
+    assert_eq!(measures.receive().await, 1.0);
+    assert_eq!(measures.receive().await, 2.0);
+    assert_eq!(errors.receive().await, "disconnected");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_merge_subscriber() -> Result<()> {
+    // -- Setup & Fixtures
+    let mut measures = PublisherImpl::new("measures", 10);
+    let mut heartbeats = PublisherImpl::new("heartbeats", 10);
+    let mut merger = EventMerger::new("merger");
+    let mut subscriber = SubscriberImpl::new("events");
+
+    routes! {
+        measures -> merger.measure,
+        heartbeats -> merger.heartbeat,
+        merger -> subscriber,
+    }?;
+
+    // -- Exec
+    measures.publish(1.0).await?;
+    let first_event = subscriber.receive().await;
+    heartbeats.publish(()).await?;
+    let second_event = subscriber.receive().await;
+
+    // -- Check
+    assert_eq!(first_event, Event::Measure(1.0));
+    assert_eq!(second_event, Event::Heartbeat);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_split_then_merge() -> Result<()> {
+    // -- Setup & Fixtures
+    let mut publisher = PublisherImpl::new("events", 10);
+    let mut splitter = EventSplitter::new("splitter", 10);
+    let mut merger = EventMerger::new("merger");
+    let mut subscriber = SubscriberImpl::new("events");
+
+    routes! {
+        publisher -> splitter,
+        splitter.sensor_error -> merger.sensor_error,
+        merger -> subscriber,
+    }?;
+    tokio::spawn(async move { splitter.run().await });
+
+    // -- Exec
+    publisher.publish(Event::Measure(1.0)).await?;
+    publisher
+        .publish(Event::SensorError("disconnected".to_string()))
+        .await?;
+
+    // -- Check
+    assert_eq!(
+        subscriber.receive().await,
+        Event::SensorError("disconnected".to_string())
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_split_publisher_stops_at_the_end_of_the_stream() -> Result<()> {
+    // -- Setup & Fixtures
+    let mut publisher = PublisherImpl::new("events", 10);
+    let mut splitter = EventSplitter::new("splitter", 10);
+    let mut measures = SubscriberImpl::<f64>::new("measures");
+
+    routes! {
+        publisher -> splitter,
+        splitter.measure -> measures,
+    }?;
+    // the variant publishers are named after the splitter
+    assert_eq!(Publisher::get_name(&splitter.measure), "splitter::Measure");
+    let run = tokio::spawn(async move { splitter.run().await });
+
+    // -- Exec
+    publisher.publish(Event::Measure(1.0)).await?;
+    drop(publisher);
+
+    // -- Check
+    tokio::time::timeout(std::time::Duration::from_secs(1), run).await??;
+    assert_eq!(measures.next_message().await, Some(1.0));
+    assert_eq!(measures.next_message().await, None);
+
+    Ok(())
+}
+
+#[test]
+fn test_merge_subscriber_names() {
+    let merger = EventMerger::new("merger");
+
+    assert_eq!(
+        Subscriber::get_name(&merger.sensor_error),
+        "merger::SensorError"
+    );
+}
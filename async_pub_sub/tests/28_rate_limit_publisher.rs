@@ -0,0 +1,104 @@
+use std::time::{Duration, Instant};
+
+use async_pub_sub::{
+    Publisher, PublisherBuilder, PublisherImpl, RateLimitPolicy, RateLimitPublisherLayer, Result,
+    SubscriberImpl, Timer,
+};
+use futures::{FutureExt, future::BoxFuture};
+
+/// Timer following tokio's clock, so that the tests can pause it.
+#[derive(Clone)]
+struct TestTimer;
+
+impl Timer for TestTimer {
+    fn now(&self) -> Instant {
+        tokio::time::Instant::now().into_std()
+    }
+
+    fn sleep(&self, duration: Duration) -> BoxFuture<'static, ()> {
+        tokio::time::sleep(duration).boxed()
+    }
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_rate_limit_delay() -> Result<()> {
+    // -- Setup & Fixtures
+    let mut publisher = PublisherBuilder::new()
+        .layer(RateLimitPublisherLayer::new(
+            10.0,
+            2,
+            RateLimitPolicy::Delay,
+            TestTimer,
+        ))
+        .publisher(PublisherImpl::new("publisher", 10));
+    let mut subscriber = SubscriberImpl::new("subscriber");
+    subscriber.subscribe_to(&mut publisher)?;
+    let start = tokio::time::Instant::now();
+
+    // -- Exec
+    for value in 0..4 {
+        publisher.publish(value).await?;
+    }
+
+    // -- Check
+    // the first two messages use the burst, the next ones are spaced by 100ms
+    assert_eq!(start.elapsed(), Duration::from_millis(200));
+    for value in 0..4 {
+        assert_eq!(subscriber.receive().await, value);
+    }
+
+    Ok(())
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_rate_limit_drop() -> Result<()> {
+    // -- Setup & Fixtures
+    let mut publisher = PublisherBuilder::new()
+        .layer(RateLimitPublisherLayer::new(
+            10.0,
+            1,
+            RateLimitPolicy::Drop,
+            TestTimer,
+        ))
+        .publisher(PublisherImpl::new("publisher", 10));
+    let mut subscriber = SubscriberImpl::new("subscriber");
+    subscriber.subscribe_to(&mut publisher)?;
+
+    // -- Exec
+    publisher.publish(1).await?;
+    publisher.publish(2).await?;
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    publisher.publish(3).await?;
+
+    // -- Check
+    assert_eq!(subscriber.receive().await, 1);
+    assert_eq!(subscriber.receive().await, 3);
+
+    Ok(())
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_rate_limit_reject() -> Result<()> {
+    // -- Setup & Fixtures
+    let mut publisher = PublisherBuilder::new()
+        .layer(RateLimitPublisherLayer::new(
+            10.0,
+            1,
+            RateLimitPolicy::Reject,
+            TestTimer,
+        ))
+        .publisher(PublisherImpl::new("publisher", 10));
+    let mut subscriber = SubscriberImpl::new("subscriber");
+    subscriber.subscribe_to(&mut publisher)?;
+
+    // -- Exec
+    let first = publisher.publish(1).await;
+    let second = publisher.publish(2).await;
+
+    // -- Check
+    assert!(first.is_ok());
+    assert!(second.is_err());
+    assert_eq!(subscriber.receive().await, 1);
+
+    Ok(())
+}
@@ -0,0 +1,216 @@
+use std::time::{Duration, Instant};
+
+use async_pub_sub::{
+    BatchForwarder, DebounceForwarder, PublisherImpl, Result, SampleForwarder, SubscriberImpl,
+    Timer, macros::routes,
+};
+use futures::{FutureExt, future::BoxFuture};
+
+/// Timer following tokio's clock, so that the tests can pause it.
+#[derive(Clone)]
+struct TestTimer;
+
+impl Timer for TestTimer {
+    fn now(&self) -> Instant {
+        tokio::time::Instant::now().into_std()
+    }
+
+    fn sleep(&self, duration: Duration) -> BoxFuture<'static, ()> {
+        tokio::time::sleep(duration).boxed()
+    }
+}
+
+/// Publishes the given values, waiting for the given period before each of them.
+///
+/// The publisher is kept alive afterwards, so that the forwarders do not flush their
+/// pending messages.
+fn publish_periodically(publisher: PublisherImpl<i32>, values: Vec<i32>, period: Duration) {
+    tokio::spawn(async move {
+        for value in values {
+            tokio::time::sleep(period).await;
+            publisher.publish(value).await.unwrap();
+        }
+        std::future::pending::<()>().await;
+    });
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_debounce_forwarder() -> Result<()> {
+    // -- Setup & Fixtures
+    let mut publisher = PublisherImpl::new("publisher", 10);
+    let mut forwarder = DebounceForwarder::new("forwarder", Duration::from_millis(50), TestTimer);
+    let mut subscriber = SubscriberImpl::new("subscriber");
+
+    routes! {
+        publisher -> forwarder,
+        forwarder -> subscriber,
+    }?;
+    let start = tokio::time::Instant::now();
+
+    // -- Exec
+    publish_periodically(publisher, vec![1, 2, 3], Duration::from_millis(10));
+
+    // -- Check
+    // the burst ends at 30ms, its last message is forwarded after the quiet period
+    assert_eq!(subscriber.receive().await, 3);
+    assert_eq!(start.elapsed(), Duration::from_millis(80));
+
+    Ok(())
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_debounce_forwarder_separate_bursts() -> Result<()> {
+    // -- Setup & Fixtures
+    let mut publisher = PublisherImpl::new("publisher", 10);
+    let mut forwarder = DebounceForwarder::new("forwarder", Duration::from_millis(50), TestTimer);
+    let mut subscriber = SubscriberImpl::new("subscriber");
+
+    routes! {
+        publisher -> forwarder,
+        forwarder -> subscriber,
+    }?;
+
+    // -- Exec
+    // messages spaced by more than the quiet period are all forwarded
+    publish_periodically(publisher, vec![1, 2, 3], Duration::from_millis(100));
+
+    // -- Check
+    for value in 1..=3 {
+        assert_eq!(subscriber.receive().await, value);
+    }
+
+    Ok(())
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_sample_forwarder() -> Result<()> {
+    // -- Setup & Fixtures
+    let mut publisher = PublisherImpl::new("publisher", 10);
+    let mut forwarder = SampleForwarder::new("forwarder", Duration::from_millis(100), TestTimer);
+    let mut subscriber = SubscriberImpl::new("subscriber");
+
+    routes! {
+        publisher -> forwarder,
+        forwarder -> subscriber,
+    }?;
+    let start = tokio::time::Instant::now();
+
+    // -- Exec
+    // one message every 30ms, from 30ms to 300ms
+    publish_periodically(publisher, (1..=10).collect(), Duration::from_millis(30));
+
+    // -- Check
+    // the latest message is forwarded every 100ms
+    assert_eq!(subscriber.receive().await, 3);
+    assert_eq!(start.elapsed(), Duration::from_millis(100));
+    assert_eq!(subscriber.receive().await, 6);
+    assert_eq!(start.elapsed(), Duration::from_millis(200));
+
+    Ok(())
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_sample_forwarder_skips_empty_intervals() -> Result<()> {
+    // -- Setup & Fixtures
+    let mut publisher = PublisherImpl::new("publisher", 10);
+    let mut forwarder = SampleForwarder::new("forwarder", Duration::from_millis(100), TestTimer);
+    let mut subscriber = SubscriberImpl::new("subscriber");
+
+    routes! {
+        publisher -> forwarder,
+        forwarder -> subscriber,
+    }?;
+    let start = tokio::time::Instant::now();
+
+    // -- Exec
+    publish_periodically(publisher, vec![1, 2], Duration::from_millis(230));
+
+    // -- Check
+    // nothing is forwarded at 100ms, 200ms and 400ms
+    assert_eq!(subscriber.receive().await, 1);
+    assert_eq!(start.elapsed(), Duration::from_millis(300));
+    assert_eq!(subscriber.receive().await, 2);
+    assert_eq!(start.elapsed(), Duration::from_millis(500));
+
+    Ok(())
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_sample_forwarder_ends_with_its_input() -> Result<()> {
+    // -- Setup & Fixtures
+    let mut publisher = PublisherImpl::new("publisher", 10);
+    let mut forwarder = SampleForwarder::new("forwarder", Duration::from_millis(100), TestTimer);
+    let mut subscriber = SubscriberImpl::new("subscriber");
+
+    routes! {
+        publisher -> forwarder,
+        forwarder -> subscriber,
+    }?;
+    let start = tokio::time::Instant::now();
+
+    // -- Exec
+    publisher.publish(1).await?;
+    publisher.publish(2).await?;
+    drop(publisher);
+
+    // -- Check
+    // the pending message is forwarded without waiting for the tick, then the stream ends
+    assert_eq!(subscriber.next_message().await, Some(2));
+    assert_eq!(subscriber.next_message().await, None);
+    assert_eq!(start.elapsed(), Duration::ZERO);
+
+    Ok(())
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_batch_forwarder() -> Result<()> {
+    // -- Setup & Fixtures
+    let mut publisher = PublisherImpl::new("publisher", 10);
+    let mut forwarder = BatchForwarder::new("forwarder", 3, Duration::from_millis(100), TestTimer);
+    let mut subscriber = SubscriberImpl::new("subscriber");
+
+    routes! {
+        publisher -> forwarder,
+        forwarder -> subscriber,
+    }?;
+    let start = tokio::time::Instant::now();
+
+    // -- Exec
+    for value in 1..=4 {
+        publisher.publish(value).await?;
+    }
+
+    // -- Check
+    // a full batch is forwarded immediately
+    assert_eq!(subscriber.receive().await, vec![1, 2, 3]);
+    assert_eq!(start.elapsed(), Duration::ZERO);
+    // an incomplete batch is forwarded after the maximum delay
+    assert_eq!(subscriber.receive().await, vec![4]);
+    assert_eq!(start.elapsed(), Duration::from_millis(100));
+
+    Ok(())
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_batch_forwarder_flushes_on_end_of_input() -> Result<()> {
+    // -- Setup & Fixtures
+    let mut publisher = PublisherImpl::new("publisher", 10);
+    let mut forwarder = BatchForwarder::new("forwarder", 3, Duration::from_secs(1), TestTimer);
+    let mut subscriber = SubscriberImpl::new("subscriber");
+
+    routes! {
+        publisher -> forwarder,
+        forwarder -> subscriber,
+    }?;
+    let start = tokio::time::Instant::now();
+
+    // -- Exec
+    publisher.publish(1).await?;
+    drop(publisher);
+
+    // -- Check
+    assert_eq!(subscriber.receive().await, vec![1]);
+    assert_eq!(start.elapsed(), Duration::ZERO);
+
+    Ok(())
+}
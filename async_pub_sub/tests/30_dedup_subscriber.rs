@@ -0,0 +1,149 @@
+use std::{
+    sync::atomic::Ordering,
+    time::{Duration, Instant},
+};
+
+use async_pub_sub::{
+    DedupSubscriberLayer, Envelope, PublisherImpl, Result, Subscriber, SubscriberBuilder,
+    SubscriberImpl, Timer,
+};
+use futures::{FutureExt, future::BoxFuture};
+
+/// Timer following tokio's clock, so that the tests can pause it.
+#[derive(Clone)]
+struct TestTimer;
+
+impl Timer for TestTimer {
+    fn now(&self) -> Instant {
+        tokio::time::Instant::now().into_std()
+    }
+
+    fn sleep(&self, duration: Duration) -> BoxFuture<'static, ()> {
+        tokio::time::sleep(duration).boxed()
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct Record {
+    id: u32,
+    value: &'static str,
+}
+
+fn record(id: u32, value: &'static str) -> Record {
+    Record { id, value }
+}
+
+#[tokio::test]
+async fn test_dedup_subscriber() -> Result<()> {
+    // -- Setup & Fixtures
+    let mut publisher = PublisherImpl::new("publisher", 10);
+    let layer = DedupSubscriberLayer::new(10, |record: &Record| record.id);
+    let suppressed = layer.suppressed_counter();
+    let mut subscriber = SubscriberBuilder::new()
+        .layer(layer)
+        .subscriber(SubscriberImpl::new("subscriber"));
+    subscriber.subscribe_to(&mut publisher)?;
+
+    // -- Exec
+    publisher.publish(record(1, "first")).await?;
+    publisher.publish(record(1, "first resent")).await?;
+    publisher.publish(record(2, "second")).await?;
+    publisher.publish(record(1, "first resent again")).await?;
+    publisher.publish(record(3, "third")).await?;
+
+    // -- Check
+    assert_eq!(subscriber.receive().await, record(1, "first"));
+    assert_eq!(subscriber.receive().await, record(2, "second"));
+    assert_eq!(subscriber.receive().await, record(3, "third"));
+    assert_eq!(suppressed.load(Ordering::Relaxed), 2);
+    assert_eq!(subscriber.suppressed_count(), 2);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_dedup_subscriber_lru_window() -> Result<()> {
+    // -- Setup & Fixtures
+    let mut publisher = PublisherImpl::new("publisher", 10);
+    let mut subscriber = SubscriberBuilder::new()
+        .layer(DedupSubscriberLayer::new(2, |record: &Record| record.id))
+        .subscriber(SubscriberImpl::new("subscriber"));
+    subscriber.subscribe_to(&mut publisher)?;
+
+    // -- Exec
+    publisher.publish(record(1, "first")).await?;
+    publisher.publish(record(2, "second")).await?;
+    // seeing 1 again makes 2 the least recently seen key
+    publisher.publish(record(1, "first resent")).await?;
+    publisher.publish(record(3, "third")).await?;
+    // 2 has been evicted from the window by 3
+    publisher.publish(record(2, "second resent")).await?;
+    publisher.publish(record(1, "first resent again")).await?;
+
+    // -- Check
+    assert_eq!(subscriber.receive().await, record(1, "first"));
+    assert_eq!(subscriber.receive().await, record(2, "second"));
+    assert_eq!(subscriber.receive().await, record(3, "third"));
+    assert_eq!(subscriber.receive().await, record(2, "second resent"));
+    assert_eq!(subscriber.suppressed_count(), 1);
+
+    Ok(())
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_dedup_subscriber_ttl() -> Result<()> {
+    // -- Setup & Fixtures
+    let mut publisher = PublisherImpl::new("publisher", 10);
+    let mut subscriber = SubscriberBuilder::new()
+        .layer(
+            DedupSubscriberLayer::new(10, |record: &Record| record.id)
+                .with_ttl(Duration::from_millis(100), TestTimer),
+        )
+        .subscriber(SubscriberImpl::new("subscriber"));
+    subscriber.subscribe_to(&mut publisher)?;
+
+    // -- Exec
+    tokio::spawn(async move {
+        for value in ["first", "resent within the ttl", "resent after the ttl"] {
+            publisher.publish(record(1, value)).await.unwrap();
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+    });
+
+    // -- Check
+    assert_eq!(subscriber.receive().await, record(1, "first"));
+    assert_eq!(
+        subscriber.receive().await,
+        record(1, "resent after the ttl")
+    );
+    assert_eq!(subscriber.suppressed_count(), 1);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_dedup_subscriber_by_message_id() -> Result<()> {
+    // -- Setup & Fixtures
+    let mut publisher = PublisherImpl::new("publisher", 10);
+    let mut subscriber = SubscriberBuilder::new()
+        .layer(DedupSubscriberLayer::by_message_id(10))
+        .subscriber(SubscriberImpl::new("subscriber"));
+    subscriber.subscribe_to(&mut publisher)?;
+
+    // -- Exec
+    // a resent envelope keeps the id of its metadata, a new envelope gets a fresh one
+    let first = Envelope::new("value");
+    publisher.publish(first.clone()).await?;
+    publisher.publish(first.clone()).await?;
+    publisher.publish(Envelope::new("value")).await?;
+
+    // -- Check
+    assert_eq!(subscriber.receive().await, first);
+    assert_ne!(
+        subscriber.receive().await.metadata().id(),
+        first.metadata().id()
+    );
+    assert_eq!(subscriber.suppressed_count(), 1);
+
+    Ok(())
+}
@@ -0,0 +1,138 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use async_pub_sub::{PublisherImpl, Result, RetryPolicy, Subscriber, SubscriberImpl, Timer};
+use futures::{FutureExt, future::BoxFuture};
+
+/// Timer following tokio's clock, so that the tests can pause it.
+#[derive(Clone)]
+struct TestTimer;
+
+impl Timer for TestTimer {
+    fn now(&self) -> Instant {
+        tokio::time::Instant::now().into_std()
+    }
+
+    fn sleep(&self, duration: Duration) -> BoxFuture<'static, ()> {
+        tokio::time::sleep(duration).boxed()
+    }
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_retry_with_exponential_backoff() -> Result<()> {
+    // -- Setup & Fixtures
+    let mut publisher = PublisherImpl::new("publisher", 10);
+    let mut subscriber = SubscriberImpl::new("subscriber");
+    subscriber.subscribe_to(&mut publisher)?;
+
+    let start = tokio::time::Instant::now();
+    let attempts = Arc::new(Mutex::new(Vec::new()));
+    let handler_attempts = attempts.clone();
+    let handler = async move |message: &i32| -> Result<()> {
+        let mut attempts = handler_attempts.lock().unwrap();
+        attempts.push((*message, start.elapsed()));
+        if attempts.len() < 4 {
+            return Err("handler not ready".into());
+        }
+        Ok(())
+    };
+    let policy = RetryPolicy::new(5, Duration::from_millis(100), TestTimer)
+        .with_max_backoff(Duration::from_millis(300));
+
+    // -- Exec
+    tokio::spawn(async move { subscriber.for_each_with_retry(handler, policy).await });
+    publisher.publish(42).await?;
+    tokio::time::sleep(Duration::from_secs(1)).await;
+
+    // -- Check
+    // the backoff doubles after each failure, up to the maximum backoff
+    assert_eq!(
+        *attempts.lock().unwrap(),
+        vec![
+            (42, Duration::ZERO),
+            (42, Duration::from_millis(100)),
+            (42, Duration::from_millis(300)),
+            (42, Duration::from_millis(600)),
+        ]
+    );
+
+    Ok(())
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_retry_dead_letter() -> Result<()> {
+    // -- Setup & Fixtures
+    let mut publisher = PublisherImpl::new("publisher", 10);
+    let mut subscriber = SubscriberImpl::new("subscriber");
+    subscriber.subscribe_to(&mut publisher)?;
+
+    let mut dead_letter = PublisherImpl::new("dead_letter", 10);
+    let mut diagnostics = SubscriberImpl::new("diagnostics");
+    diagnostics.subscribe_to(&mut dead_letter)?;
+
+    let handled = Arc::new(Mutex::new(Vec::new()));
+    let handler_handled = handled.clone();
+    let handler = async move |message: &i32| -> Result<()> {
+        if *message < 0 {
+            return Err(format!("cannot handle {message}").into());
+        }
+        handler_handled.lock().unwrap().push(*message);
+        Ok(())
+    };
+    let policy =
+        RetryPolicy::new(3, Duration::from_millis(10), TestTimer).with_dead_letter(dead_letter);
+
+    // -- Exec
+    tokio::spawn(async move { subscriber.for_each_with_retry(handler, policy).await });
+    for message in [1, -2, 3, -4] {
+        publisher.publish(message).await?;
+    }
+
+    // -- Check
+    assert_eq!(diagnostics.receive().await, -2);
+    assert_eq!(diagnostics.receive().await, -4);
+    assert_eq!(*handled.lock().unwrap(), vec![1, 3]);
+
+    Ok(())
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_retry_ends_with_the_publishers() -> Result<()> {
+    // -- Setup & Fixtures
+    let mut publisher = PublisherImpl::new("publisher", 10);
+    let mut subscriber = SubscriberImpl::new("subscriber");
+    subscriber.subscribe_to(&mut publisher)?;
+
+    let handled = Arc::new(Mutex::new(Vec::new()));
+    let handler_handled = handled.clone();
+    let mut failed = false;
+    let handler = async move |message: &i32| -> Result<()> {
+        // the second message fails once
+        if *message == 2 && !failed {
+            failed = true;
+            return Err("handler not ready".into());
+        }
+        handler_handled.lock().unwrap().push(*message);
+        Ok(())
+    };
+    let policy = RetryPolicy::new(3, Duration::from_millis(100), TestTimer);
+
+    for message in 1..=3 {
+        publisher.publish(message).await?;
+    }
+    publisher.close();
+
+    // -- Exec
+    tokio::time::timeout(
+        Duration::from_secs(1),
+        subscriber.for_each_with_retry(handler, policy),
+    )
+    .await?;
+
+    // -- Check
+    assert_eq!(*handled.lock().unwrap(), vec![1, 2, 3]);
+
+    Ok(())
+}
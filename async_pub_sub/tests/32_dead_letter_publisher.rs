@@ -0,0 +1,151 @@
+use std::time::{Duration, Instant};
+
+use async_pub_sub::{
+    DeadLetterPublisherLayer, Envelope, EnvelopePublisherLayer, Layer, PublishError, Publisher,
+    PublisherBuilder, PublisherImpl, RateLimitPolicy, RateLimitPublisherLayer, Result,
+    SubscriberImpl, Timer,
+};
+use futures::{FutureExt, future::BoxFuture};
+
+/// Timer following tokio's clock, so that the tests can pause it.
+#[derive(Clone)]
+struct TestTimer;
+
+impl Timer for TestTimer {
+    fn now(&self) -> Instant {
+        tokio::time::Instant::now().into_std()
+    }
+
+    fn sleep(&self, duration: Duration) -> BoxFuture<'static, ()> {
+        tokio::time::sleep(duration).boxed()
+    }
+}
+
+#[tokio::test]
+async fn test_publish_error_carries_message() -> Result<()> {
+    // -- Setup & Fixtures
+    let mut publisher = PublisherImpl::new("publisher", 10);
+    let mut subscriber = SubscriberImpl::new("subscriber");
+    subscriber.subscribe_to(&mut publisher)?;
+    drop(subscriber);
+
+    // -- Exec
+    let error = Publisher::publish(&publisher, "lost".to_string())
+        .await
+        .unwrap_err();
+
+    // -- Check
+    let error = error.downcast::<PublishError<String>>().unwrap();
+    assert_eq!(error.publisher_name(), "publisher");
+    assert_eq!(error.into_message(), "lost");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_dead_letter_closed_subscriber() -> Result<()> {
+    // -- Setup & Fixtures
+    let mut dead_letter = PublisherImpl::new("dead_letter", 10);
+    let mut diagnostics = SubscriberImpl::new("diagnostics");
+    diagnostics.subscribe_to(&mut dead_letter)?;
+
+    let mut publisher =
+        DeadLetterPublisherLayer::new(dead_letter).layer(PublisherImpl::new("publisher", 10));
+    let mut subscriber = SubscriberImpl::new("subscriber");
+    subscriber.subscribe_to(&mut publisher)?;
+
+    // -- Exec
+    publisher.publish(1).await?;
+    drop(subscriber);
+    publisher.publish(2).await?;
+
+    // -- Check
+    assert_eq!(diagnostics.receive().await, 2);
+
+    Ok(())
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_dead_letter_rate_limit_rejections() -> Result<()> {
+    // -- Setup & Fixtures
+    let mut dead_letter = PublisherImpl::new("dead_letter", 10);
+    let mut diagnostics = SubscriberImpl::new("diagnostics");
+    diagnostics.subscribe_to(&mut dead_letter)?;
+
+    let mut publisher = PublisherBuilder::new()
+        .layer(DeadLetterPublisherLayer::new(dead_letter))
+        .layer(RateLimitPublisherLayer::new(
+            1.0,
+            1,
+            RateLimitPolicy::Reject,
+            TestTimer,
+        ))
+        .publisher(PublisherImpl::new("publisher", 10));
+    let mut subscriber = SubscriberImpl::new("subscriber");
+    subscriber.subscribe_to(&mut publisher)?;
+
+    // -- Exec
+    for value in 1..=3 {
+        publisher.publish(value).await?;
+    }
+
+    // -- Check
+    assert_eq!(subscriber.receive().await, 1);
+    assert_eq!(diagnostics.receive().await, 2);
+    assert_eq!(diagnostics.receive().await, 3);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_dead_letter_through_envelope_layer() -> Result<()> {
+    // -- Setup & Fixtures
+    let mut dead_letter = PublisherImpl::new("dead_letter", 10);
+    let mut diagnostics = SubscriberImpl::new("diagnostics");
+    diagnostics.subscribe_to(&mut dead_letter)?;
+
+    // the inner publisher fails with envelopes, the dead-letter publisher gets bare messages
+    let mut publisher = PublisherBuilder::new()
+        .layer(DeadLetterPublisherLayer::new(dead_letter))
+        .layer(EnvelopePublisherLayer)
+        .publisher(PublisherImpl::<Envelope<i32>>::new("publisher", 10));
+    let mut subscriber = SubscriberImpl::<i32>::new("subscriber");
+    subscriber.subscribe_to(&mut publisher)?;
+    drop(subscriber);
+
+    // -- Exec
+    publisher.publish(7).await?;
+
+    // -- Check
+    assert_eq!(diagnostics.receive().await, 7);
+
+    Ok(())
+}
+
+#[cfg(feature = "serde")]
+#[tokio::test]
+async fn test_dead_letter_through_encoding_layer() -> Result<()> {
+    use async_pub_sub::{EncodingPublisherLayer, JsonCodec, bytes::Bytes};
+
+    // -- Setup & Fixtures
+    let mut dead_letter = PublisherImpl::<Bytes>::new("dead_letter", 10);
+    let mut diagnostics = SubscriberImpl::new("diagnostics");
+    diagnostics.subscribe_to(&mut dead_letter)?;
+
+    // the inner publisher fails with decoded messages, the dead-letter publisher gets the bytes
+    let mut publisher = PublisherBuilder::new()
+        .layer(DeadLetterPublisherLayer::new(dead_letter))
+        .layer(EncodingPublisherLayer::new(JsonCodec))
+        .publisher(PublisherImpl::<i32>::new("publisher", 10));
+    let mut subscriber = SubscriberImpl::new("subscriber");
+    subscriber.subscribe_to(&mut publisher)?;
+    drop(subscriber);
+
+    // -- Exec
+    publisher.publish(Bytes::from_static(b"7")).await?;
+
+    // -- Check
+    assert_eq!(diagnostics.receive().await, Bytes::from_static(b"7"));
+
+    Ok(())
+}
@@ -0,0 +1,104 @@
+#![cfg(feature = "tracing")]
+
+use std::sync::{Arc, Mutex};
+
+use async_pub_sub::{
+    ForwarderBuilder, MapForwarder, Publisher, PublisherBuilder, PublisherImpl, Result, Subscriber,
+    SubscriberBuilder, SubscriberImpl, Traced, TracingForwarderLayer, TracingPublisherLayer,
+    TracingSubscriberLayer, macros::routes,
+};
+use tracing::{Instrument, span};
+use tracing_subscriber::{Registry, layer::Context, prelude::*, registry::LookupSpan};
+
+/// The name of a span along with the name of its parent.
+type SpanRecord = (&'static str, Option<&'static str>);
+
+/// Records the name of each new span along with the name of its parent.
+#[derive(Clone, Default)]
+struct SpanRecorder {
+    spans: Arc<Mutex<Vec<SpanRecord>>>,
+}
+
+impl<S> tracing_subscriber::Layer<S> for SpanRecorder
+where
+    S: tracing::Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, _attrs: &span::Attributes<'_>, id: &span::Id, ctx: Context<'_, S>) {
+        let span = ctx.span(id).expect("the span should exist");
+        let parent = span.parent().map(|parent| parent.name());
+        self.spans.lock().unwrap().push((span.name(), parent));
+    }
+}
+
+#[tokio::test]
+async fn test_tracing_through_routes() -> Result<()> {
+    // -- Setup & Fixtures
+    let recorder = SpanRecorder::default();
+    let _guard = tracing::subscriber::set_default(Registry::default().with(recorder.clone()));
+
+    let mut publisher = PublisherBuilder::new()
+        .layer(TracingPublisherLayer)
+        .publisher(PublisherImpl::<Traced<i32>>::new("publisher", 10));
+    let mut forwarder = ForwarderBuilder::new()
+        .layer(TracingForwarderLayer)
+        .forwarder(MapForwarder::new("forwarder", |message: Traced<i32>| {
+            message.map(|value| value.to_string())
+        }));
+    let mut subscriber = SubscriberBuilder::new()
+        .layer(TracingSubscriberLayer)
+        .subscriber(SubscriberImpl::<Traced<String>>::new("subscriber"));
+
+    routes! {
+        publisher -> forwarder,
+        forwarder -> subscriber,
+    }?;
+
+    // -- Exec
+    // the layers wrap and unwrap the messages, the spans are handed over from hop to hop
+    let root = tracing::info_span!("request");
+    publisher.publish(42).instrument(root).await?;
+    let received = subscriber.receive_traced().await;
+
+    // -- Check
+    assert_eq!(received.span().metadata().unwrap().name(), "receive");
+    assert_eq!(received.into_inner(), "42");
+    assert_eq!(
+        *recorder.spans.lock().unwrap(),
+        vec![
+            ("request", None),
+            ("publish", Some("request")),
+            ("forward", Some("publish")),
+            ("receive", Some("forward")),
+        ]
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_tracing_layers_expose_bare_messages() -> Result<()> {
+    // -- Setup & Fixtures
+    let recorder = SpanRecorder::default();
+    let _guard = tracing::subscriber::set_default(Registry::default().with(recorder.clone()));
+
+    let mut publisher = PublisherBuilder::new()
+        .layer(TracingPublisherLayer)
+        .publisher(PublisherImpl::<Traced<i32>>::new("publisher", 10));
+    let mut subscriber = SubscriberBuilder::new()
+        .layer(TracingSubscriberLayer)
+        .subscriber(SubscriberImpl::<Traced<i32>>::new("subscriber"));
+    subscriber.subscribe_to(&mut publisher)?;
+
+    // -- Exec
+    publisher.publish(42).await?;
+    let received: i32 = subscriber.receive().await;
+
+    // -- Check
+    assert_eq!(received, 42);
+    assert_eq!(
+        *recorder.spans.lock().unwrap(),
+        vec![("publish", None), ("receive", Some("publish"))]
+    );
+
+    Ok(())
+}
@@ -0,0 +1,230 @@
+use std::time::{Duration, Instant};
+
+use async_pub_sub::{
+    DurationStats, MetricsPublisherLayer, MetricsRegistry, MetricsSubscriberLayer, Publisher,
+    PublisherBuilder, PublisherImpl, RateLimitPolicy, RateLimitPublisherLayer, Result, Subscriber,
+    SubscriberBuilder, SubscriberImpl, Timer,
+};
+use futures::{FutureExt, future::BoxFuture};
+
+/// Timer following tokio's clock, so that the tests can pause it.
+#[derive(Clone)]
+struct TestTimer;
+
+impl Timer for TestTimer {
+    fn now(&self) -> Instant {
+        tokio::time::Instant::now().into_std()
+    }
+
+    fn sleep(&self, duration: Duration) -> BoxFuture<'static, ()> {
+        tokio::time::sleep(duration).boxed()
+    }
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_metrics_layers() -> Result<()> {
+    // -- Setup & Fixtures
+    let registry = MetricsRegistry::new(TestTimer);
+    let mut publisher = PublisherBuilder::new()
+        .layer(MetricsPublisherLayer::new(registry.clone()))
+        .publisher(PublisherImpl::new("publisher", 10));
+    let mut subscriber = SubscriberBuilder::new()
+        .layer(MetricsSubscriberLayer::new(registry.clone()))
+        .subscriber(SubscriberImpl::new("subscriber"));
+    subscriber.subscribe_to(&mut publisher)?;
+
+    // -- Exec
+    for value in 0..3 {
+        publisher.publish(value).await?;
+    }
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert_eq!(subscriber.receive().await, 0);
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert_eq!(subscriber.receive().await, 1);
+
+    // -- Check
+    let metrics = registry
+        .channel("publisher", "subscriber")
+        .expect("the channel should be registered");
+    assert_eq!(registry.snapshot(), vec![metrics.clone()]);
+    assert_eq!(metrics.published, 3);
+    assert_eq!(metrics.failed, 0);
+    assert_eq!(metrics.received, 2);
+    assert_eq!(metrics.queue_depth, 1);
+    assert_eq!(
+        metrics.publish_wait,
+        DurationStats {
+            count: 3,
+            total: Duration::ZERO,
+            max: Duration::ZERO,
+        }
+    );
+    assert_eq!(
+        metrics.latency,
+        DurationStats {
+            count: 2,
+            total: Duration::from_millis(300),
+            max: Duration::from_millis(200),
+        }
+    );
+    assert_eq!(metrics.latency.mean(), Some(Duration::from_millis(150)));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_metrics_failed_publish() -> Result<()> {
+    // -- Setup & Fixtures
+    let registry = MetricsRegistry::new(TestTimer);
+    let mut publisher = PublisherBuilder::new()
+        .layer(MetricsPublisherLayer::new(registry.clone()))
+        .publisher(PublisherImpl::new("publisher", 10));
+    let mut subscriber = SubscriberImpl::new("subscriber");
+    subscriber.subscribe_to(&mut publisher)?;
+    drop(subscriber);
+
+    // -- Exec
+    assert!(publisher.publish(1).await.is_err());
+
+    // -- Check
+    let metrics = registry.channel("publisher", "subscriber").unwrap();
+    assert_eq!(metrics.published, 0);
+    assert_eq!(metrics.failed, 1);
+    assert_eq!(metrics.queue_depth, 0);
+
+    Ok(())
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_metrics_cancelled_publish() -> Result<()> {
+    // -- Setup & Fixtures
+    let registry = MetricsRegistry::new(TestTimer);
+    let mut publisher = PublisherBuilder::new()
+        .layer(MetricsPublisherLayer::new(registry.clone()))
+        .layer(RateLimitPublisherLayer::new(
+            1.0,
+            1,
+            RateLimitPolicy::Delay,
+            TestTimer,
+        ))
+        .publisher(PublisherImpl::new("publisher", 10));
+    let mut subscriber = SubscriberBuilder::new()
+        .layer(MetricsSubscriberLayer::new(registry.clone()))
+        .subscriber(SubscriberImpl::new("subscriber"));
+    subscriber.subscribe_to(&mut publisher)?;
+
+    // -- Exec
+    publisher.publish(1).await?;
+    // the second publication waits for the rate limit and is cancelled
+    let cancelled = tokio::time::timeout(Duration::from_millis(10), publisher.publish(2)).await;
+    assert!(cancelled.is_err());
+    tokio::time::sleep(Duration::from_millis(90)).await;
+    assert_eq!(subscriber.receive().await, 1);
+
+    // -- Check
+    let metrics = registry.channel("publisher", "subscriber").unwrap();
+    assert_eq!(metrics.published, 1);
+    assert_eq!(metrics.failed, 0);
+    assert_eq!(metrics.queue_depth, 0);
+    assert_eq!(
+        metrics.latency,
+        DurationStats {
+            count: 1,
+            total: Duration::from_millis(100),
+            max: Duration::from_millis(100),
+        }
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_metrics_concurrent_publish() -> Result<()> {
+    // -- Setup & Fixtures
+    let registry = MetricsRegistry::new(TestTimer);
+    let mut publisher = PublisherBuilder::new()
+        .layer(MetricsPublisherLayer::new(registry.clone()))
+        .publisher(PublisherImpl::new("publisher", 10));
+    let mut subscriber = SubscriberBuilder::new()
+        .layer(MetricsSubscriberLayer::new(registry.clone()))
+        .subscriber(SubscriberImpl::new("subscriber"));
+    subscriber.subscribe_to(&mut publisher)?;
+
+    // -- Exec
+    let (first, second, received) =
+        tokio::join!(publisher.publish(1), publisher.publish(2), async {
+            (subscriber.receive().await, subscriber.receive().await)
+        });
+    first?;
+    second?;
+
+    // -- Check
+    assert_eq!(received, (1, 2));
+    let metrics = registry.channel("publisher", "subscriber").unwrap();
+    assert_eq!(metrics.published, 2);
+    assert_eq!(metrics.received, 2);
+    assert_eq!(metrics.latency.count, 2);
+    assert_eq!(metrics.queue_depth, 0);
+
+    Ok(())
+}
+
+#[cfg(feature = "metrics")]
+#[tokio::test]
+async fn test_metrics_crate_integration() -> Result<()> {
+    use metrics_util::{
+        MetricKind,
+        debugging::{DebugValue, DebuggingRecorder},
+    };
+
+    // -- Setup & Fixtures
+    let recorder = DebuggingRecorder::new();
+    let snapshotter = recorder.snapshotter();
+    recorder.install()?;
+
+    let registry = MetricsRegistry::new(TestTimer);
+    let mut publisher = PublisherBuilder::new()
+        .layer(MetricsPublisherLayer::new(registry.clone()))
+        .publisher(PublisherImpl::new("publisher", 10));
+    let mut subscriber = SubscriberBuilder::new()
+        .layer(MetricsSubscriberLayer::new(registry.clone()))
+        .subscriber(SubscriberImpl::new("subscriber"));
+    subscriber.subscribe_to(&mut publisher)?;
+
+    // -- Exec
+    publisher.publish(1).await?;
+    publisher.publish(2).await?;
+    subscriber.receive().await;
+
+    // -- Check
+    // taking a snapshot resets the counters
+    let snapshot = snapshotter.snapshot().into_vec();
+    let counter = |name: &str| {
+        snapshot
+            .iter()
+            .find(|(key, ..)| key.kind() == MetricKind::Counter && key.key().name() == name)
+            .map(|(key, _, _, value)| {
+                let labels: Vec<_> = key
+                    .key()
+                    .labels()
+                    .map(|label| (label.key().to_string(), label.value().to_string()))
+                    .collect();
+                (labels, value)
+            })
+    };
+
+    let labels = vec![
+        ("publisher".to_string(), "publisher".to_string()),
+        ("subscriber".to_string(), "subscriber".to_string()),
+    ];
+    assert_eq!(
+        counter("async_pub_sub_messages_published_total"),
+        Some((labels.clone(), &DebugValue::Counter(2)))
+    );
+    assert_eq!(
+        counter("async_pub_sub_messages_received_total"),
+        Some((labels, &DebugValue::Counter(1)))
+    );
+
+    Ok(())
+}
@@ -0,0 +1,144 @@
+use async_pub_sub::{
+    DeadLetterPublisherLayer, DedupSubscriberLayer, Envelope, EnvelopePublisherLayer,
+    EnvelopeSubscriberLayer, Publisher, PublisherBuilder, PublisherImpl, Request, Result,
+    Subscriber, SubscriberBuilder, SubscriberImpl,
+};
+
+#[test]
+fn test_envelope_metadata() {
+    let first = Envelope::new(1).with_header("tenant", "acme");
+    let second = Envelope::new(2);
+
+    assert_ne!(first.metadata().id(), second.metadata().id());
+    assert_eq!(first.metadata().source(), None);
+    assert_eq!(first.metadata().header("tenant"), Some("acme"));
+    assert_eq!(first.metadata().header("unknown"), None);
+
+    let mapped = first.clone().map(|value| value.to_string());
+    assert_eq!(mapped.metadata(), first.metadata());
+    assert_eq!(mapped.into_inner(), "1");
+}
+
+#[test]
+fn test_envelope_reply_correlation() {
+    let request = Envelope::new("ping");
+    let reply = request.reply("pong");
+    let follow_up = reply.reply("ping again");
+
+    // the whole exchange shares the correlation id of the first message
+    let correlation_id = Some(request.metadata().id());
+    assert_eq!(reply.metadata().correlation_id(), correlation_id);
+    assert_eq!(follow_up.metadata().correlation_id(), correlation_id);
+    assert_ne!(reply.metadata().id(), request.metadata().id());
+}
+
+#[test]
+fn test_request_correlation_id() {
+    let message = Envelope::new("event");
+    let (request, _response) = Request::<_, ()>::with_metadata(1, message.metadata().reply());
+    assert_eq!(
+        request.metadata().correlation_id(),
+        Some(message.metadata().id())
+    );
+
+    let (request, _response) = Request::<_, ()>::new(2);
+    let request = request.with_correlation_id(7);
+    assert_eq!(request.metadata().correlation_id(), Some(7));
+}
+
+#[tokio::test]
+async fn test_envelope_publisher_edges() -> Result<()> {
+    // -- Setup & Fixtures
+    let mut dead_letter = PublisherImpl::new("dead_letter", 10);
+    let mut diagnostics = SubscriberImpl::<Envelope<i32>>::new("diagnostics");
+    diagnostics.subscribe_to(&mut dead_letter)?;
+
+    let mut publisher = PublisherBuilder::new()
+        .layer(EnvelopePublisherLayer)
+        .layer(DeadLetterPublisherLayer::new(dead_letter))
+        .publisher(PublisherImpl::new("publisher", 10));
+    let mut subscriber = SubscriberImpl::new("subscriber");
+    subscriber.subscribe_to(&mut publisher)?;
+
+    // -- Exec
+    publisher.publish(1).await?;
+    drop(subscriber);
+    publisher.publish(2).await?;
+
+    // -- Check
+    // the middlewares between the edges handle the envelopes
+    let undeliverable = diagnostics.receive().await;
+    assert_eq!(undeliverable.metadata().source(), Some("publisher"));
+    assert_eq!(undeliverable.into_inner(), 2);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_envelope_publisher_unwraps_messages() -> Result<()> {
+    // -- Setup & Fixtures
+    let mut publisher = PublisherBuilder::new()
+        .layer(EnvelopePublisherLayer)
+        .publisher(PublisherImpl::<Envelope<String>>::new("publisher", 10));
+    let mut subscriber = SubscriberImpl::new("subscriber");
+    subscriber.subscribe_to(&mut publisher)?;
+
+    // -- Exec
+    publisher.publish("hello".to_string()).await?;
+
+    // -- Check
+    assert_eq!(subscriber.receive().await, "hello");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_envelope_subscriber_edges() -> Result<()> {
+    // -- Setup & Fixtures
+    let mut first_publisher = PublisherImpl::new("first_publisher", 10);
+    let mut second_publisher = PublisherImpl::new("second_publisher", 10);
+
+    // duplicates are detected per source publisher
+    let mut subscriber = SubscriberBuilder::new()
+        .layer(EnvelopeSubscriberLayer)
+        .layer(DedupSubscriberLayer::new(10, |envelope: &Envelope<i32>| {
+            (
+                envelope.metadata().source().map(str::to_owned),
+                *envelope.message(),
+            )
+        }))
+        .subscriber(SubscriberImpl::new("subscriber"));
+    subscriber.subscribe_to(&mut first_publisher)?;
+    subscriber.subscribe_to(&mut second_publisher)?;
+
+    // -- Exec
+    first_publisher.publish(1).await?;
+    first_publisher.publish(1).await?;
+    second_publisher.publish(1).await?;
+    first_publisher.publish(2).await?;
+
+    // -- Check
+    let mut received = Vec::new();
+    for _ in 0..3 {
+        received.push(subscriber.receive().await);
+    }
+    received.sort();
+    assert_eq!(received, vec![1, 1, 2]);
+
+    Ok(())
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_envelope_serialization() -> Result<()> {
+    use async_pub_sub::{Codec, JsonCodec};
+
+    let mut envelope = Envelope::new(42).with_correlation_id(3);
+    envelope.metadata_mut().set_source("publisher");
+
+    let bytes = JsonCodec.encode(&envelope)?;
+    let decoded: Envelope<i32> = JsonCodec.decode(&bytes)?;
+    assert_eq!(decoded, envelope);
+
+    Ok(())
+}
@@ -0,0 +1,210 @@
+use async_pub_sub::{
+    Bus, ForwarderImpl, PublisherImpl, Result, Subscriber, SubscriberImpl, TopicBus, Topology,
+};
+
+/// Returns the subscriptions of the current topology involving the given nodes only, as the
+/// tests of this file share the process topology.
+fn topology_of(nodes: &[&str]) -> Topology {
+    Topology::current()
+        .subscriptions()
+        .iter()
+        .filter(|subscription| {
+            nodes.contains(&subscription.publisher()) && nodes.contains(&subscription.subscriber())
+        })
+        .copied()
+        .collect()
+}
+
+#[test]
+fn test_topology_records_subscriptions() -> Result<()> {
+    // -- Setup & Fixtures
+    // a service holds one publisher per subscriber, all with the service name
+    let mut timer_to_persistency = PublisherImpl::<u64>::new("query_timer", 1);
+    let mut timer_to_logger = PublisherImpl::<u64>::new("query_timer", 1);
+    let mut cache = PublisherImpl::<String>::new("query_cache", 1);
+
+    let mut persistency = SubscriberImpl::<u64>::new("query_persistency");
+    let mut logger = SubscriberImpl::<u64>::new("query_logger");
+    let mut cache_logger = SubscriberImpl::<String>::new("query_cache_logger");
+
+    // -- Exec
+    persistency.subscribe_to(&mut timer_to_persistency)?;
+    logger.subscribe_to(&mut timer_to_logger)?;
+    cache_logger.subscribe_to(&mut cache)?;
+
+    // -- Check
+    let topology = Topology::current();
+    assert_eq!(
+        topology.subscribers_of("query_timer"),
+        vec!["query_logger", "query_persistency"]
+    );
+    assert_eq!(
+        topology.publishers_of("query_cache_logger"),
+        vec!["query_cache"]
+    );
+    assert!(topology.subscriptions().iter().any(|subscription| {
+        subscription.publisher() == "query_cache"
+            && subscription.message_type() == std::any::type_name::<String>()
+    }));
+
+    Ok(())
+}
+
+#[test]
+fn test_topology_records_forwarders() -> Result<()> {
+    // -- Setup & Fixtures
+    let mut timer = PublisherImpl::<u64>::new("forward_timer", 1);
+    let mut forwarder = ForwarderImpl::<u64>::new("forward_relay");
+    let mut persistency = SubscriberImpl::<u64>::new("forward_persistency");
+
+    // -- Exec
+    forwarder.subscribe_to(&mut timer)?;
+    persistency.subscribe_to(&mut forwarder)?;
+
+    // -- Check
+    let topology = topology_of(&["forward_timer", "forward_relay", "forward_persistency"]);
+    assert_eq!(
+        topology.nodes(),
+        vec!["forward_persistency", "forward_relay", "forward_timer"]
+    );
+    assert_eq!(topology.subscriptions().len(), 2);
+
+    Ok(())
+}
+
+#[test]
+fn test_topology_forgets_dropped_subscribers() -> Result<()> {
+    // -- Setup & Fixtures
+    let mut timer = PublisherImpl::<u64>::new("drop_timer", 1);
+    let mut cache = SubscriberImpl::<u64>::new("drop_cache");
+    cache.subscribe_to(&mut timer)?;
+    assert_eq!(
+        topology_of(&["drop_timer", "drop_cache"])
+            .subscriptions()
+            .len(),
+        1
+    );
+
+    // -- Exec
+    drop(cache);
+
+    // -- Check
+    assert!(
+        topology_of(&["drop_timer", "drop_cache"])
+            .subscriptions()
+            .is_empty()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_topology_counts_same_named_subscribers() -> Result<()> {
+    // -- Setup & Fixtures
+    let mut first_timer = PublisherImpl::<u64>::new("shared_timer", 1);
+    let mut second_timer = PublisherImpl::<u64>::new("shared_timer", 1);
+    let mut first_cache = SubscriberImpl::<u64>::new("shared_cache");
+    let mut second_cache = SubscriberImpl::<u64>::new("shared_cache");
+    first_cache.subscribe_to(&mut first_timer)?;
+    second_cache.subscribe_to(&mut second_timer)?;
+
+    // -- Exec
+    drop(first_cache);
+
+    // -- Check
+    assert_eq!(
+        topology_of(&["shared_timer", "shared_cache"]).subscribers_of("shared_timer"),
+        vec!["shared_cache"]
+    );
+    drop(second_cache);
+    assert!(
+        topology_of(&["shared_timer", "shared_cache"])
+            .subscriptions()
+            .is_empty()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_topology_records_bus_wiring() -> Result<()> {
+    // -- Setup & Fixtures
+    let bus = Bus::default();
+    let topic_bus = TopicBus::default();
+    let mut cache = SubscriberImpl::<u64>::new("wiring_cache");
+    let mut logger = SubscriberImpl::<String>::new("wiring_logger");
+
+    // -- Exec
+    bus.subscribe::<u64>("wiring_timer", &mut cache)?;
+    topic_bus.subscribe::<String>("wiring/+/logs", &mut logger)?;
+
+    // -- Check
+    let topology = Topology::current();
+    assert_eq!(
+        topology.subscribers_of("wiring_timer"),
+        vec!["wiring_cache"]
+    );
+    assert_eq!(
+        topology.subscribers_of("wiring/+/logs"),
+        vec!["wiring_logger"]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_topology_export() -> Result<()> {
+    // -- Setup & Fixtures
+    let mut timer_to_cache = PublisherImpl::<u64>::new("export_timer", 1);
+    let mut timer_to_persistency = PublisherImpl::<u64>::new("export_timer", 1);
+    let mut other_timer_to_cache = PublisherImpl::<u64>::new("export_timer", 1);
+    let mut cache = SubscriberImpl::<u64>::new("export_cache");
+    let mut persistency = SubscriberImpl::<u64>::new("export_persistency");
+
+    // -- Exec
+    cache.subscribe_to(&mut timer_to_cache)?;
+    persistency.subscribe_to(&mut timer_to_persistency)?;
+    // subscribing twice to the same publisher name records a single edge
+    cache.subscribe_to(&mut other_timer_to_cache)?;
+
+    // -- Check
+    let topology = topology_of(&["export_timer", "export_cache", "export_persistency"]);
+    assert_eq!(
+        topology.to_dot(),
+        concat!(
+            "digraph topology {\n",
+            "    \"export_cache\";\n",
+            "    \"export_persistency\";\n",
+            "    \"export_timer\";\n",
+            "    \"export_timer\" -> \"export_cache\" [label=\"u64\"];\n",
+            "    \"export_timer\" -> \"export_persistency\" [label=\"u64\"];\n",
+            "}",
+        )
+    );
+    assert_eq!(
+        topology.to_json(),
+        concat!(
+            r#"{"nodes":["export_cache","export_persistency","export_timer"],"#,
+            r#""edges":[{"publisher":"export_timer","subscriber":"export_cache","message_type":"u64"},"#,
+            r#"{"publisher":"export_timer","subscriber":"export_persistency","message_type":"u64"}]}"#,
+        )
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_topology_export_escaping() {
+    Topology::record::<u8>("escape \"publisher\"", "escape\\subscriber");
+
+    let topology = topology_of(&["escape \"publisher\"", "escape\\subscriber"]);
+
+    assert!(
+        topology
+            .to_dot()
+            .contains(r#""escape \"publisher\"" -> "escape\\subscriber" [label="u8"];"#)
+    );
+    assert!(topology.to_json().contains(
+        r#"{"publisher":"escape \"publisher\"","subscriber":"escape\\subscriber","message_type":"u8"}"#
+    ));
+}
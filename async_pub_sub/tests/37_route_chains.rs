@@ -0,0 +1,89 @@
+use async_pub_sub::{
+    DebuggingForwarderLayer, ForwarderBuilder, ForwarderImpl, MapForwarder, PublisherImpl, Result,
+    SubscriberImpl,
+    macros::{DerivePublisher, route, routes},
+};
+
+#[derive(DerivePublisher)]
+struct Timer {
+    #[publisher(u64)]
+    ticks: PublisherImpl<u64>,
+    #[publisher(String)]
+    labels: PublisherImpl<String>,
+}
+
+#[tokio::test]
+async fn test_route_through_forwarders() -> Result<()> {
+    // -- Setup & Fixtures
+    let mut producer = PublisherImpl::new("producer", 10);
+    let mut debug_forwarder = ForwarderBuilder::new()
+        .layer(DebuggingForwarderLayer)
+        .forwarder(ForwarderImpl::new("debug_forwarder"));
+    let mut consumer = SubscriberImpl::new("consumer");
+
+    route!(producer -> debug_forwarder -> consumer: i32)?;
+
+    // -- Exec
+    producer.publish(42).await?;
+
+    // -- Check
+    assert_eq!(consumer.receive().await, 42);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_route_hops_are_typed_independently() -> Result<()> {
+    // -- Setup & Fixtures
+    let mut producer = PublisherImpl::new("producer", 10);
+    let mut stringifier = MapForwarder::new("stringifier", |value: i32| value.to_string());
+    let mut consumer = SubscriberImpl::new("consumer");
+
+    route!(producer -> stringifier -> consumer)?;
+
+    // -- Exec
+    producer.publish(42).await?;
+
+    // -- Check
+    assert_eq!(consumer.receive().await, "42");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_routes_fan_out_and_fan_in() -> Result<()> {
+    // -- Setup & Fixtures
+    let mut timer = Timer {
+        ticks: PublisherImpl::new("timer", 10),
+        labels: PublisherImpl::new("timer", 10),
+    };
+    let mut cache = SubscriberImpl::new("cache");
+    let mut logger = SubscriberImpl::new("logger");
+
+    let mut publisher_a = PublisherImpl::new("publisher_a", 10);
+    let mut publisher_b = PublisherImpl::new("publisher_b", 10);
+    let mut sink = SubscriberImpl::new("sink");
+
+    routes! {
+        timer -> [cache, logger],
+        [publisher_a, publisher_b] -> sink: i32,
+    }?;
+
+    // -- Exec
+    timer.ticks.publish(1).await?;
+    timer.labels.publish("tick".to_string()).await?;
+    publisher_a.publish(2).await?;
+    publisher_b.publish(3).await?;
+
+    // -- Check
+    let cache_message: u64 = cache.receive().await;
+    let logger_message: String = logger.receive().await;
+    assert_eq!(cache_message, 1);
+    assert_eq!(logger_message, "tick");
+
+    let mut received = vec![sink.receive().await, sink.receive().await];
+    received.sort();
+    assert_eq!(received, vec![2, 3]);
+
+    Ok(())
+}
@@ -0,0 +1,395 @@
+use std::{
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicUsize, Ordering},
+    },
+    time::{Duration, Instant},
+};
+
+use async_pub_sub::{
+    PublisherImpl, RestartStrategy, Result, Service, ShutdownSignal, Subscriber, SubscriberImpl,
+    Supervisor, Timer,
+    futures::{
+        FutureExt,
+        future::{BoxFuture, Either, select},
+        join,
+    },
+};
+
+/// Counts the calls to the lifecycle methods of a service.
+#[derive(Clone, Default)]
+struct Lifecycle {
+    starts: Arc<AtomicUsize>,
+    shutdowns: Arc<AtomicUsize>,
+}
+
+impl Lifecycle {
+    fn starts(&self) -> usize {
+        self.starts.load(Ordering::SeqCst)
+    }
+
+    fn shutdowns(&self) -> usize {
+        self.shutdowns.load(Ordering::SeqCst)
+    }
+}
+
+/// A service failing a given number of times, then stopping the whole supervisor.
+struct FlakyService {
+    lifecycle: Lifecycle,
+    failures: usize,
+    supervisor_shutdown: Option<ShutdownSignal>,
+}
+
+impl Service for FlakyService {
+    fn get_name(&self) -> &'static str {
+        "flaky_service"
+    }
+
+    fn start(&mut self) -> BoxFuture<'_, Result<()>> {
+        self.lifecycle.starts.fetch_add(1, Ordering::SeqCst);
+        async { Ok(()) }.boxed()
+    }
+
+    fn run(&mut self, shutdown: ShutdownSignal) -> BoxFuture<'_, Result<()>> {
+        async move {
+            if self.failures > 0 {
+                self.failures -= 1;
+                return Err("flaky failure".into());
+            }
+            if let Some(supervisor_shutdown) = &self.supervisor_shutdown {
+                supervisor_shutdown.cancel();
+            }
+            shutdown.cancelled().await;
+            Ok(())
+        }
+        .boxed()
+    }
+
+    fn shutdown(&mut self) -> BoxFuture<'_, Result<()>> {
+        self.lifecycle.shutdowns.fetch_add(1, Ordering::SeqCst);
+        async { Ok(()) }.boxed()
+    }
+}
+
+/// A service running until it is cancelled.
+struct StableService {
+    lifecycle: Lifecycle,
+}
+
+impl Service for StableService {
+    fn get_name(&self) -> &'static str {
+        "stable_service"
+    }
+
+    fn start(&mut self) -> BoxFuture<'_, Result<()>> {
+        self.lifecycle.starts.fetch_add(1, Ordering::SeqCst);
+        async { Ok(()) }.boxed()
+    }
+
+    fn run(&mut self, shutdown: ShutdownSignal) -> BoxFuture<'_, Result<()>> {
+        async move {
+            shutdown.cancelled().await;
+            Ok(())
+        }
+        .boxed()
+    }
+
+    fn shutdown(&mut self) -> BoxFuture<'_, Result<()>> {
+        self.lifecycle.shutdowns.fetch_add(1, Ordering::SeqCst);
+        async { Ok(()) }.boxed()
+    }
+}
+
+/// Builds a supervisor running a flaky service, failing the given number of times, and a
+/// stable service, each of them being restarted at most twice.
+fn supervise(strategy: RestartStrategy, failures: usize) -> (Supervisor, Lifecycle, Lifecycle) {
+    let flaky = Lifecycle::default();
+    let stable = Lifecycle::default();
+
+    let supervisor = Supervisor::new(strategy).with_max_restarts(2);
+    let flaky_service = FlakyService {
+        lifecycle: flaky.clone(),
+        failures,
+        supervisor_shutdown: Some(supervisor.shutdown_signal()),
+    };
+    let supervisor = supervisor
+        .with_service(flaky_service)
+        .with_service(StableService {
+            lifecycle: stable.clone(),
+        });
+
+    (supervisor, flaky, stable)
+}
+
+#[tokio::test]
+async fn test_one_for_one_restarts_the_failed_service() -> Result<()> {
+    // -- Setup & Fixtures
+    let (supervisor, flaky, stable) = supervise(RestartStrategy::OneForOne, 2);
+
+    // -- Exec
+    supervisor.run().await?;
+
+    // -- Check
+    assert_eq!(flaky.starts(), 3);
+    assert_eq!(stable.starts(), 1);
+    assert_eq!(flaky.shutdowns(), 1);
+    assert_eq!(stable.shutdowns(), 1);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_one_for_all_restarts_all_the_services() -> Result<()> {
+    // -- Setup & Fixtures
+    let (supervisor, flaky, stable) = supervise(RestartStrategy::OneForAll, 2);
+
+    // -- Exec
+    supervisor.run().await?;
+
+    // -- Check
+    assert_eq!(flaky.starts(), 3);
+    assert_eq!(stable.starts(), 3);
+    assert_eq!(stable.shutdowns(), 1);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_supervisor_gives_up_after_max_restarts() {
+    // -- Setup & Fixtures
+    let (supervisor, flaky, stable) = supervise(RestartStrategy::OneForOne, 5);
+
+    // -- Exec
+    let result = supervisor.run().await;
+
+    // -- Check
+    let err = result.expect_err("the flaky service should exceed the maximum restarts");
+    assert!(err.to_string().contains("flaky_service"));
+    assert_eq!(flaky.starts(), 3);
+    // the other services are stopped gracefully
+    assert_eq!(stable.shutdowns(), 1);
+}
+
+/// Timer following tokio's clock, so that the tests can pause it.
+#[derive(Clone)]
+struct TestTimer;
+
+impl Timer for TestTimer {
+    fn now(&self) -> Instant {
+        tokio::time::Instant::now().into_std()
+    }
+
+    fn sleep(&self, duration: Duration) -> BoxFuture<'static, ()> {
+        tokio::time::sleep(duration).boxed()
+    }
+}
+
+/// A service panicking a given number of times, then stopping the whole supervisor.
+struct PanickingService {
+    lifecycle: Lifecycle,
+    panics: usize,
+    supervisor_shutdown: ShutdownSignal,
+}
+
+impl Service for PanickingService {
+    fn get_name(&self) -> &'static str {
+        "panicking_service"
+    }
+
+    fn start(&mut self) -> BoxFuture<'_, Result<()>> {
+        self.lifecycle.starts.fetch_add(1, Ordering::SeqCst);
+        async { Ok(()) }.boxed()
+    }
+
+    fn run(&mut self, shutdown: ShutdownSignal) -> BoxFuture<'_, Result<()>> {
+        async move {
+            if self.panics > 0 {
+                self.panics -= 1;
+                panic!("panicking service");
+            }
+            self.supervisor_shutdown.cancel();
+            shutdown.cancelled().await;
+            Ok(())
+        }
+        .boxed()
+    }
+
+    fn shutdown(&mut self) -> BoxFuture<'_, Result<()>> {
+        self.lifecycle.shutdowns.fetch_add(1, Ordering::SeqCst);
+        async { Ok(()) }.boxed()
+    }
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_spawned_supervisor_restarts_panicking_services_with_backoff() -> Result<()> {
+    // -- Setup & Fixtures
+    let lifecycle = Lifecycle::default();
+    let supervisor = Supervisor::new(RestartStrategy::OneForOne)
+        .with_restart_backoff(Duration::from_millis(100), TestTimer);
+    let service = PanickingService {
+        lifecycle: lifecycle.clone(),
+        panics: 2,
+        supervisor_shutdown: supervisor.shutdown_signal(),
+    };
+    let supervisor = supervisor.with_service(service);
+    let start = tokio::time::Instant::now();
+
+    // -- Exec
+    let result =
+        tokio::spawn(async move { supervisor.run().await.map_err(|err| err.to_string()) }).await?;
+
+    // -- Check
+    result?;
+    assert_eq!(lifecycle.starts(), 3);
+    assert_eq!(lifecycle.shutdowns(), 1);
+    // the backoff doubles after each restart
+    assert!(start.elapsed() >= Duration::from_millis(300));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_supervisor_gives_up_on_a_service_panicking_too_many_times() {
+    // -- Setup & Fixtures
+    let lifecycle = Lifecycle::default();
+    let supervisor = Supervisor::new(RestartStrategy::OneForAll).with_max_restarts(1);
+    let service = PanickingService {
+        lifecycle: lifecycle.clone(),
+        panics: 5,
+        supervisor_shutdown: supervisor.shutdown_signal(),
+    };
+    let supervisor = supervisor.with_service(service);
+
+    // -- Exec
+    let result = supervisor.run().await;
+
+    // -- Check
+    let err = result.expect_err("the panicking service should exceed the maximum restarts");
+    assert!(err.to_string().contains("panicked: panicking service"));
+    assert_eq!(lifecycle.starts(), 2);
+    assert_eq!(lifecycle.shutdowns(), 1);
+}
+
+/// A service storing the received messages, draining its subscriber on shutdown.
+struct ConsumerService {
+    subscriber: SubscriberImpl<i32>,
+    received: Arc<Mutex<Vec<i32>>>,
+}
+
+impl Service for ConsumerService {
+    fn get_name(&self) -> &'static str {
+        "consumer_service"
+    }
+
+    fn run(&mut self, shutdown: ShutdownSignal) -> BoxFuture<'_, Result<()>> {
+        async move {
+            let mut cancelled = shutdown.cancelled();
+            loop {
+                let message = match select(self.subscriber.receive().boxed(), &mut cancelled).await
+                {
+                    Either::Left((message, _)) => message,
+                    Either::Right(_) => return Ok(()),
+                };
+                self.received.lock().unwrap().push(message);
+            }
+        }
+        .boxed()
+    }
+
+    fn shutdown(&mut self) -> BoxFuture<'_, Result<()>> {
+        while let Some(message) = self.subscriber.try_receive() {
+            self.received.lock().unwrap().push(message);
+        }
+        async { Ok(()) }.boxed()
+    }
+}
+
+#[tokio::test]
+async fn test_shutdown_drains_in_flight_messages() -> Result<()> {
+    // -- Setup & Fixtures
+    let mut producer = PublisherImpl::new("producer", 10);
+    let mut subscriber = SubscriberImpl::new("consumer_service");
+    subscriber.subscribe_to(&mut producer)?;
+    let received = Arc::new(Mutex::new(Vec::new()));
+
+    let supervisor = Supervisor::new(RestartStrategy::OneForOne).with_service(ConsumerService {
+        subscriber,
+        received: received.clone(),
+    });
+
+    for message in 1..=3 {
+        producer.publish(message).await?;
+    }
+
+    // -- Exec
+    supervisor.shutdown_signal().cancel();
+    supervisor.run().await?;
+
+    // -- Check
+    assert_eq!(*received.lock().unwrap(), vec![1, 2, 3]);
+
+    Ok(())
+}
+
+mod interface {
+    use async_pub_sub::macros::rpc_interface;
+
+    #[rpc_interface]
+    pub trait RpcInterface {
+        async fn add_one(&self, value: i32) -> i32;
+    }
+}
+
+use interface::{RpcInterface, RpcInterfaceClient, RpcInterfaceMessage, RpcInterfaceServer};
+
+#[derive(async_pub_sub::macros::DeriveSubscriber)]
+struct RpcServer {
+    #[subscriber(RpcInterfaceMessage)]
+    subscriber: SubscriberImpl<RpcInterfaceMessage>,
+}
+
+impl RpcInterface for RpcServer {
+    async fn add_one(&self, value: i32) -> i32 {
+        value + 1
+    }
+}
+
+impl Service for RpcServer {
+    fn get_name(&self) -> &'static str {
+        "rpc_server"
+    }
+
+    fn run(&mut self, shutdown: ShutdownSignal) -> BoxFuture<'_, Result<()>> {
+        async move {
+            self.run_until(shutdown).await;
+            Ok(())
+        }
+        .boxed()
+    }
+}
+
+#[tokio::test]
+async fn test_rpc_server_answers_queued_requests_on_shutdown() -> Result<()> {
+    // -- Setup & Fixtures
+    let mut server = RpcServer {
+        subscriber: SubscriberImpl::new("rpc_server"),
+    };
+    let mut client = RpcInterfaceClient::new(PublisherImpl::new("rpc_client", 1));
+    server.subscribe_to(&mut client)?;
+
+    let supervisor = Supervisor::new(RestartStrategy::OneForOne).with_service(server);
+    let shutdown = supervisor.shutdown_signal();
+
+    // -- Exec
+    // the request is queued before the shutdown, and answered while the server drains
+    let (response, result) = join!(client.add_one(41), async {
+        shutdown.cancel();
+        supervisor.run().await
+    });
+
+    // -- Check
+    result?;
+    assert_eq!(response, 42);
+
+    Ok(())
+}
@@ -0,0 +1,214 @@
+use async_pub_sub::{
+    DebuggingPublisherLayer, DebuggingSubscriberLayer, ForwarderImpl, LoggingSubscriberLayer,
+    MapForwarder, PublishError, Publisher, PublisherBuilder, PublisherImpl, Result, Subscriber,
+    SubscriberBuilder, SubscriberImpl, TopicBus, futures::FutureExt,
+};
+
+#[tokio::test]
+async fn test_close_drains_queued_messages_in_order() -> Result<()> {
+    // -- Setup & Fixtures
+    let mut publisher = PublisherImpl::new("publisher", 10);
+    let mut subscriber = SubscriberImpl::new("subscriber");
+    subscriber.subscribe_to(&mut publisher)?;
+
+    for message in 1..=3 {
+        publisher.publish(message).await?;
+    }
+
+    // -- Exec
+    publisher.close();
+    let rejected = publisher.publish(4).await;
+
+    // -- Check
+    assert!(publisher.is_closed());
+    let err = rejected.expect_err("a closed publisher should reject messages");
+    let err = err
+        .downcast::<PublishError<i32>>()
+        .expect("the error should carry the rejected message");
+    assert_eq!(err.into_message(), 4);
+
+    assert_eq!(subscriber.next_message().await, Some(1));
+    assert_eq!(subscriber.next_message().await, Some(2));
+    assert_eq!(subscriber.next_message().await, Some(3));
+    assert_eq!(subscriber.next_message().await, None);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_closed_waits_for_the_queue_to_be_drained() -> Result<()> {
+    // -- Setup & Fixtures
+    let mut publisher = PublisherImpl::new("publisher", 10);
+    let mut subscriber = SubscriberImpl::new("subscriber");
+    subscriber.subscribe_to(&mut publisher)?;
+
+    publisher.publish(1).await?;
+    publisher.publish(2).await?;
+
+    // -- Exec & Check
+    publisher.close();
+    assert!(publisher.closed().now_or_never().is_none());
+
+    assert_eq!(subscriber.next_message().await, Some(1));
+    assert!(publisher.closed().now_or_never().is_none());
+
+    assert_eq!(subscriber.next_message().await, Some(2));
+    assert_eq!(subscriber.next_message().await, None);
+    assert!(publisher.closed().now_or_never().is_some());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_closed_completes_when_the_subscriber_is_dropped() -> Result<()> {
+    // -- Setup & Fixtures
+    let mut publisher = PublisherImpl::new("publisher", 10);
+    let mut subscriber = SubscriberImpl::new("subscriber");
+    subscriber.subscribe_to(&mut publisher)?;
+    publisher.publish(1).await?;
+
+    // -- Exec
+    drop(subscriber);
+
+    // -- Check
+    assert!(publisher.is_closed());
+    assert!(publisher.closed().now_or_never().is_some());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_close_through_layers_and_forwarders() -> Result<()> {
+    // -- Setup & Fixtures
+    let mut publisher = PublisherBuilder::new()
+        .layer(DebuggingPublisherLayer)
+        .publisher(PublisherImpl::new("publisher", 10));
+    let mut forwarder = ForwarderImpl::new("forwarder");
+    let mut subscriber = SubscriberImpl::new("subscriber");
+
+    forwarder.subscribe_to(&mut publisher)?;
+    subscriber.subscribe_to(&mut forwarder)?;
+
+    publisher.publish(1).await?;
+    publisher.publish(2).await?;
+
+    // -- Exec
+    Publisher::close(&publisher);
+
+    // -- Check
+    assert!(Publisher::is_closed(&publisher));
+    assert_eq!(subscriber.next_message().await, Some(1));
+    assert_eq!(subscriber.next_message().await, Some(2));
+    assert_eq!(subscriber.next_message().await, None);
+    Publisher::closed(&publisher).await;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_closed_completes_without_subscriber() {
+    // -- Setup & Fixtures
+    let publisher = PublisherImpl::<i32>::new("publisher", 10);
+    assert!(publisher.closed().now_or_never().is_none());
+
+    // -- Exec
+    publisher.close();
+
+    // -- Check
+    assert!(publisher.closed().now_or_never().is_some());
+}
+
+#[tokio::test]
+async fn test_next_message_through_layers() -> Result<()> {
+    // -- Setup & Fixtures
+    let mut publisher = PublisherImpl::new("publisher", 10);
+    let mut subscriber = SubscriberBuilder::new()
+        .layer(LoggingSubscriberLayer)
+        .layer(DebuggingSubscriberLayer)
+        .subscriber(SubscriberImpl::new("subscriber"));
+    subscriber.subscribe_to(&mut publisher)?;
+
+    publisher.publish(1).await?;
+
+    // -- Exec
+    publisher.close();
+
+    // -- Check
+    assert_eq!(Subscriber::next_message(&mut subscriber).await, Some(1));
+    assert_eq!(Subscriber::next_message(&mut subscriber).await, None);
+    assert_eq!(Subscriber::next_message(&mut subscriber).await, None);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_close_forwarder() -> Result<()> {
+    // -- Setup & Fixtures
+    let mut publisher = PublisherImpl::new("publisher", 10);
+    let mut forwarder = MapForwarder::new("forwarder", |message: i32| message * 2);
+    let mut subscriber = SubscriberImpl::new("subscriber");
+
+    forwarder.subscribe_to(&mut publisher)?;
+    subscriber.subscribe_to(&mut forwarder)?;
+
+    publisher.publish(1).await?;
+    assert_eq!(subscriber.next_message().await, Some(2));
+
+    // -- Exec
+    Publisher::close(&forwarder);
+
+    // -- Check
+    assert!(Publisher::is_closed(&forwarder));
+    assert_eq!(subscriber.next_message().await, None);
+    Publisher::closed(&forwarder).await;
+    // the forwarder no longer consumes the messages of its publisher
+    assert!(publisher.is_closed());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_closed_completes_for_an_unbound_forwarder() {
+    // -- Setup & Fixtures
+    let forwarder = ForwarderImpl::<i32>::new("forwarder");
+    assert!(Publisher::closed(&forwarder).now_or_never().is_none());
+
+    // -- Exec
+    Publisher::close(&forwarder);
+
+    // -- Check
+    assert!(Publisher::closed(&forwarder).now_or_never().is_some());
+}
+
+#[tokio::test]
+async fn test_close_topic_publisher() -> Result<()> {
+    // -- Setup & Fixtures
+    let bus = TopicBus::new(10);
+    let mut subscriber = bus.subscriber::<i32>("subscriber", "sensors/#")?;
+    let publisher = bus.publisher::<i32>("sensors/temp")?;
+    let clone = publisher.clone();
+    let other = bus.publisher::<i32>("sensors/temp")?;
+
+    publisher.publish(1).await?;
+
+    // -- Exec
+    publisher.close();
+    let rejected = clone.publish(2).await;
+    other.publish(3).await?;
+
+    // -- Check
+    assert!(clone.is_closed());
+    assert!(!other.is_closed());
+    let err = rejected.expect_err("a closed publisher should reject messages");
+    let err = err
+        .downcast::<PublishError<i32>>()
+        .expect("the error should carry the rejected message");
+    assert_eq!(err.into_message(), 2);
+    publisher.closed().await;
+
+    // the other publishers of the topic are not affected
+    assert_eq!(subscriber.next_message().await, Some(1));
+    assert_eq!(subscriber.next_message().await, Some(3));
+
+    Ok(())
+}
@@ -0,0 +1,229 @@
+use async_pub_sub::{
+    Bus, BusPublisher, PublishError, Publisher, PublisherImpl, Result, SubscriberImpl, Topology,
+    futures::FutureExt,
+};
+
+#[derive(Clone, Debug, PartialEq)]
+struct CacheTimerNotification(u64);
+
+/// A producer built from the bus only.
+struct TimerService {
+    notifications: BusPublisher<CacheTimerNotification>,
+    ticks: BusPublisher<u64>,
+}
+
+impl TimerService {
+    fn new(bus: &Bus) -> Self {
+        Self {
+            notifications: bus.publisher("bus_timer"),
+            ticks: bus.publisher("bus_timer"),
+        }
+    }
+}
+
+/// A consumer built from the bus only.
+struct CacheService {
+    notifications: SubscriberImpl<CacheTimerNotification>,
+}
+
+impl CacheService {
+    fn new(bus: &Bus, name: &'static str) -> Result<Self> {
+        let mut notifications = SubscriberImpl::new(name);
+        bus.subscribe::<CacheTimerNotification>("bus_timer", &mut notifications)?;
+        Ok(Self { notifications })
+    }
+}
+
+#[tokio::test]
+async fn test_bus_wires_services_by_name_and_type() -> Result<()> {
+    // -- Setup & Fixtures
+    let bus = Bus::new(10);
+    // the consumers can be created before the producer
+    let mut cache = CacheService::new(&bus, "bus_cache")?;
+    let mut persistency = CacheService::new(&bus, "bus_persistency")?;
+    let timer = TimerService::new(&bus);
+
+    // -- Exec
+    timer
+        .notifications
+        .publish(CacheTimerNotification(1))
+        .await?;
+
+    // -- Check
+    assert_eq!(
+        cache.notifications.receive().await,
+        CacheTimerNotification(1)
+    );
+    assert_eq!(
+        persistency.notifications.receive().await,
+        CacheTimerNotification(1)
+    );
+    let subscribers = Topology::current().subscribers_of("bus_timer");
+    assert!(subscribers.contains(&"bus_cache"));
+    assert!(subscribers.contains(&"bus_persistency"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_bus_publishers_are_distinguished_by_type() -> Result<()> {
+    // -- Setup & Fixtures
+    let bus = Bus::default();
+    let timer = TimerService::new(&bus);
+    let mut cache = CacheService::new(&bus, "typed_cache")?;
+    let mut ticks = SubscriberImpl::new("typed_ticks");
+    bus.subscribe::<u64>("bus_timer", &mut ticks)?;
+
+    // -- Exec
+    timer.ticks.publish(42).await?;
+    timer
+        .notifications
+        .publish(CacheTimerNotification(7))
+        .await?;
+
+    // -- Check
+    assert!(bus.contains::<u64>("bus_timer"));
+    assert!(!bus.contains::<u32>("bus_timer"));
+    assert_eq!(ticks.receive().await, 42);
+    assert_eq!(
+        cache.notifications.receive().await,
+        CacheTimerNotification(7)
+    );
+    assert!(ticks.try_receive().is_none());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_bus_publish_without_subscriber_fails() {
+    // -- Setup & Fixtures
+    let bus = Bus::default();
+    let publisher = bus.publisher::<u64>("lonely_timer");
+
+    // -- Exec
+    let result = publisher.publish(1).await;
+
+    // -- Check
+    let err = result.expect_err("no subscriber should receive the message");
+    let err = err
+        .downcast::<PublishError<u64>>()
+        .expect("the error should carry the message");
+    assert_eq!(err.into_message(), 1);
+}
+
+#[tokio::test]
+async fn test_bus_close_drains_all_subscribers() -> Result<()> {
+    // -- Setup & Fixtures
+    let bus = Bus::default();
+    let publisher = bus.publisher::<u64>("closing_timer");
+    let mut first = SubscriberImpl::new("closing_first");
+    let mut second = SubscriberImpl::new("closing_second");
+    bus.subscribe::<u64>("closing_timer", &mut first)?;
+    bus.subscribe::<u64>("closing_timer", &mut second)?;
+
+    publisher.publish(1).await?;
+    publisher.publish(2).await?;
+
+    // -- Exec
+    // closing any handle closes the publisher of the bus
+    bus.publisher::<u64>("closing_timer").close();
+
+    // -- Check
+    assert!(publisher.is_closed());
+    assert!(publisher.publish(3).await.is_err());
+
+    assert_eq!(first.next_message().await, Some(1));
+    assert_eq!(first.next_message().await, Some(2));
+    assert_eq!(first.next_message().await, None);
+    assert!(publisher.closed().now_or_never().is_none());
+
+    assert_eq!(second.next_message().await, Some(1));
+    assert_eq!(second.next_message().await, Some(2));
+    assert_eq!(second.next_message().await, None);
+    assert!(publisher.closed().now_or_never().is_some());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_bus_reopens_closed_publishers() -> Result<()> {
+    // -- Setup & Fixtures
+    let bus = Bus::default();
+    let closed = bus.publisher::<u64>("reopened_timer");
+    let mut first = SubscriberImpl::new("reopened_first");
+    bus.subscribe::<u64>("reopened_timer", &mut first)?;
+    closed.close();
+
+    // -- Exec
+    let reopened = bus.publisher::<u64>("reopened_timer");
+    let mut second = SubscriberImpl::new("reopened_second");
+    bus.subscribe::<u64>("reopened_timer", &mut second)?;
+    reopened.publish(1).await?;
+
+    // -- Check
+    assert!(closed.is_closed());
+    assert!(!reopened.is_closed());
+    assert_eq!(first.next_message().await, None);
+    assert_eq!(second.next_message().await, Some(1));
+
+    Ok(())
+}
+
+/// A producer owning its publishers, bound to its consumers by name.
+struct SensorService {
+    measures: PublisherImpl<u64>,
+}
+
+impl SensorService {
+    fn new(bus: &Bus) -> Result<Self> {
+        let mut measures = PublisherImpl::new("bus_sensor", 10);
+        bus.bind::<u64>(&mut measures, "bus_monitor")?;
+        Ok(Self { measures })
+    }
+}
+
+#[tokio::test]
+async fn test_bus_binds_publishers_to_named_subscribers() -> Result<()> {
+    // -- Setup & Fixtures
+    let bus = Bus::new(10);
+    // the producer can be created before the consumer registers its subscriber
+    let sensor = SensorService::new(&bus)?;
+    let mut monitor = bus.subscriber::<u64>("bus_monitor")?;
+    let mut other_sensor = PublisherImpl::new("bus_other_sensor", 10);
+    bus.bind::<u64>(&mut other_sensor, "bus_monitor")?;
+
+    // -- Exec
+    sensor.measures.publish(1).await?;
+    other_sensor.publish(2).await?;
+
+    // -- Check
+    let mut received = vec![monitor.receive().await, monitor.receive().await];
+    received.sort();
+    assert_eq!(received, vec![1, 2]);
+
+    let publishers = Topology::current().publishers_of("bus_monitor");
+    assert!(publishers.contains(&"bus_sensor"));
+    assert!(publishers.contains(&"bus_other_sensor"));
+
+    drop(monitor);
+    assert!(Topology::current().publishers_of("bus_monitor").is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_bus_subscriber_names_are_unique() -> Result<()> {
+    // -- Setup & Fixtures
+    let bus = Bus::default();
+    let subscriber = bus.subscriber::<u64>("unique_monitor")?;
+
+    // -- Exec & Check
+    assert!(bus.subscriber::<u64>("unique_monitor").is_err());
+    // the message type is part of the registration
+    bus.subscriber::<u32>("unique_monitor")?;
+
+    drop(subscriber);
+    bus.subscriber::<u64>("unique_monitor")?;
+
+    Ok(())
+}
@@ -0,0 +1,178 @@
+use async_pub_sub::{
+    DedupSubscriberLayer, Publisher, Result, Subscriber, SubscriberBuilder, SubscriberImpl,
+    TopicBus, TopicMessage,
+};
+
+#[derive(Clone, Debug, PartialEq)]
+struct Reading {
+    id: u32,
+    value: f32,
+}
+
+#[test]
+fn test_topic_matching() {
+    let cases = [
+        ("sensors/room1/temp", "sensors/room1/temp", true),
+        ("sensors/room1/temp", "sensors/room2/temp", false),
+        ("sensors/+/temp", "sensors/room1/temp", true),
+        ("sensors/+/temp", "sensors/room1/humidity", false),
+        ("sensors/+/temp", "sensors/room1/floor2/temp", false),
+        ("sensors/+", "sensors", false),
+        ("sensors/#", "sensors", true),
+        ("sensors/#", "sensors/room1/temp", true),
+        ("sensors/#", "actuators/room1", false),
+        ("#", "sensors/room1/temp", true),
+        ("+/+", "sensors/room1", true),
+        ("+/+", "sensors", false),
+        ("sensors/+/#", "sensors/room1", true),
+        ("#", "$SYS/uptime", false),
+        ("+/uptime", "$SYS/uptime", false),
+        ("$SYS/#", "$SYS/uptime", true),
+    ];
+
+    for (filter, topic, expected) in cases {
+        assert_eq!(
+            TopicBus::matches(filter, topic),
+            expected,
+            "filter `{filter}` on topic `{topic}`"
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_topic_bus_wildcard_subscriptions() -> Result<()> {
+    // -- Setup & Fixtures
+    let bus = TopicBus::default();
+    let mut temperatures = bus.subscriber::<f32>("temperatures", "sensors/+/temp")?;
+    let mut all_sensors = bus.subscriber::<f32>("all_sensors", "sensors/#")?;
+
+    let room1_temp = bus.publisher::<f32>("sensors/room1/temp")?;
+    let room2_humidity = bus.publisher::<f32>("sensors/room2/humidity")?;
+    let actuator = bus.publisher::<f32>("actuators/room1/valve")?;
+
+    // -- Exec
+    room1_temp.publish(21.5).await?;
+    room2_humidity.publish(40.0).await?;
+    actuator.publish(0.5).await?;
+
+    // -- Check
+    assert_eq!(temperatures.receive().await, 21.5);
+    assert!(temperatures.try_receive().is_none());
+
+    assert_eq!(all_sensors.receive().await, 21.5);
+    assert_eq!(all_sensors.receive().await, 40.0);
+    assert!(all_sensors.try_receive().is_none());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_topic_bus_subscriptions_are_typed() -> Result<()> {
+    // -- Setup & Fixtures
+    let bus = TopicBus::default();
+    let mut values = bus.subscriber::<f32>("values", "sensors/#")?;
+    let mut labels = bus.subscriber::<String>("labels", "sensors/#")?;
+
+    // -- Exec
+    bus.publisher::<f32>("sensors/room1/temp")?
+        .publish(21.5)
+        .await?;
+    bus.publisher::<String>("sensors/room1/temp")?
+        .publish("warm".to_string())
+        .await?;
+
+    // -- Check
+    assert_eq!(values.receive().await, 21.5);
+    assert!(values.try_receive().is_none());
+    assert_eq!(labels.receive().await, "warm");
+    assert!(labels.try_receive().is_none());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_topic_bus_merges_filters_with_topics() -> Result<()> {
+    // -- Setup & Fixtures
+    let bus = TopicBus::default();
+    let mut subscriber = SubscriberImpl::<TopicMessage<f32>>::new("dashboard");
+    bus.subscribe_with_topic::<f32>("sensors/+/temp", &mut subscriber)?;
+    bus.subscribe_with_topic::<f32>("sensors/+/humidity", &mut subscriber)?;
+
+    // -- Exec
+    bus.publisher::<f32>("sensors/room1/temp")?
+        .publish(21.5)
+        .await?;
+    bus.publisher::<f32>("sensors/room2/humidity")?
+        .publish(40.0)
+        .await?;
+
+    // -- Check
+    let mut received = [subscriber.receive().await, subscriber.receive().await];
+    received.sort_by_key(|message| message.topic());
+    assert_eq!(received[0].topic(), "sensors/room1/temp");
+    assert_eq!(*received[0].message(), 21.5);
+    assert_eq!(received[1].topic(), "sensors/room2/humidity");
+    assert_eq!(received[1].clone().into_inner(), 40.0);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_topic_bus_with_subscriber_layers() -> Result<()> {
+    // -- Setup & Fixtures
+    let bus = TopicBus::default();
+    // the overlapping filters deliver each reading twice, the dedup layer drops the repeats
+    let mut subscriber = SubscriberBuilder::new()
+        .layer(DedupSubscriberLayer::new(10, |reading: &Reading| {
+            reading.id
+        }))
+        .subscriber(SubscriberImpl::new("deduplicated"));
+    bus.subscribe::<Reading>("sensors/#", &mut subscriber)?;
+    bus.subscribe::<Reading>("sensors/+/temp", &mut subscriber)?;
+
+    let mut exact = SubscriberImpl::new("exact");
+    let mut room1_temp = bus.publisher::<Reading>("sensors/room1/temp")?;
+    // binding a subscriber to a publisher subscribes it to the exact topic
+    exact.subscribe_to(&mut room1_temp)?;
+
+    // -- Exec
+    room1_temp.publish(Reading { id: 1, value: 21.5 }).await?;
+    room1_temp.publish(Reading { id: 2, value: 22.0 }).await?;
+
+    // -- Check
+    let mut ids = vec![subscriber.receive().await.id, subscriber.receive().await.id];
+    ids.sort();
+    assert_eq!(ids, vec![1, 2]);
+    assert!(subscriber.try_receive().is_none());
+    assert_eq!(subscriber.suppressed_count(), 2);
+
+    assert_eq!(exact.receive().await, Reading { id: 1, value: 21.5 });
+    assert_eq!(exact.receive().await, Reading { id: 2, value: 22.0 });
+
+    Ok(())
+}
+
+#[test]
+fn test_topic_bus_rejects_invalid_topics_and_filters() {
+    let bus = TopicBus::default();
+    let mut subscriber = SubscriberImpl::<u8>::new("invalid");
+
+    assert!(bus.publisher::<u8>("").is_err());
+    assert!(bus.publisher::<u8>("sensors/+/temp").is_err());
+    assert!(bus.publisher::<u8>("sensors/#").is_err());
+
+    assert!(bus.subscribe::<u8>("", &mut subscriber).is_err());
+    assert!(
+        bus.subscribe::<u8>("sensors/room+/temp", &mut subscriber)
+            .is_err()
+    );
+    assert!(
+        bus.subscribe::<u8>("sensors/#/temp", &mut subscriber)
+            .is_err()
+    );
+    assert!(
+        bus.subscribe::<u8>("sensors/room#", &mut subscriber)
+            .is_err()
+    );
+    assert!(bus.subscribe::<u8>("sensors/+/#", &mut subscriber).is_ok());
+}
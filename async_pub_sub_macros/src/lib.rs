@@ -5,7 +5,9 @@ mod helpers;
 mod publisher;
 mod route;
 mod rpc;
+mod split;
 mod subscriber;
+mod topology;
 
 use proc_macro::TokenStream;
 use syn::parse_macro_input;
@@ -93,10 +95,93 @@ pub fn derive_publisher(input: TokenStream) -> TokenStream {
     publisher::derive_publisher_impl(input)
 }
 
+/// Generates a splitter publishing each variant of an enum on its own publisher.
+///
+/// Deriving `SplitPublisher` on an `Event` enum generates an `EventSplitter` subscribing to
+/// publishers of `Event` messages. It has one public `RouterOutput` field per variant, named
+/// after the variant in snake case and publishing the payload of the variant, or `()` for
+/// unit variants. Variants must have a single unnamed field or no field.
+///
+/// # Examples
+/// ```rust
+/// use async_pub_sub::{Publisher, PublisherImpl, Subscriber, SubscriberImpl};
+/// use async_pub_sub_macros::{SplitPublisher, routes};
+///
+/// #[derive(SplitPublisher)]
+/// enum Event {
+///     Measure(f64),
+///     Error(String),
+///     Heartbeat,
+/// }
+///
+/// # #[tokio::main]
+/// # async fn main() -> async_pub_sub::Result<()> {
+/// let mut publisher = PublisherImpl::new("events", 1);
+/// let mut splitter = EventSplitter::new("splitter", 1);
+/// let mut measures = SubscriberImpl::new("measures");
+///
+/// routes! {
+///     publisher -> splitter,
+///     splitter.measure -> measures,
+/// }?;
+/// tokio::spawn(async move { splitter.run().await });
+///
+/// publisher.publish(Event::Measure(1.0)).await?;
+/// assert_eq!(measures.receive().await, 1.0);
+/// # Ok(())
+/// # }
+/// ```
+#[proc_macro_derive(SplitPublisher)]
+pub fn derive_split_publisher(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as syn::DeriveInput);
+    split::derive_split_publisher_impl(input)
+}
+
+/// Generates a merger wrapping payloads back into the variants of an enum.
+///
+/// This is the reverse of [`SplitPublisher`]: deriving `MergeSubscriber` on an `Event` enum
+/// generates an `EventMerger` publishing `Event` messages. It has one public `SubscriberImpl`
+/// field per variant, named after the variant in snake case and receiving the payload of the
+/// variant, or `()` for unit variants.
+///
+/// # Examples
+/// ```rust
+/// use async_pub_sub::{Publisher, PublisherImpl, Subscriber, SubscriberImpl};
+/// use async_pub_sub_macros::{MergeSubscriber, routes};
+///
+/// #[derive(Debug, PartialEq, MergeSubscriber)]
+/// enum Event {
+///     Measure(f64),
+///     Heartbeat,
+/// }
+///
+/// # #[tokio::main]
+/// # async fn main() -> async_pub_sub::Result<()> {
+/// let mut measures = PublisherImpl::new("measures", 1);
+/// let mut merger = EventMerger::new("merger");
+/// let mut subscriber = SubscriberImpl::new("events");
+///
+/// routes! {
+///     measures -> merger.measure,
+///     merger -> subscriber,
+/// }?;
+///
+/// measures.publish(1.0).await?;
+/// assert_eq!(subscriber.receive().await, Event::Measure(1.0));
+/// # Ok(())
+/// # }
+/// ```
+#[proc_macro_derive(MergeSubscriber)]
+pub fn derive_merge_subscriber(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as syn::DeriveInput);
+    split::derive_merge_subscriber_impl(input)
+}
+
 /// Generates the necessary code for defining RPC interfaces.
 ///
 /// This macro generates:
 /// - Message enums for RPC communication (with optional derive attributes)
+/// - Method enums identifying which RPC a message represents
 /// - Client traits
 /// - Server traits
 ///
@@ -122,6 +207,44 @@ pub fn derive_publisher(input: TokenStream) -> TokenStream {
 /// message enum. Common derive attributes.
 /// As an example, you might want to add the `Debug` derive attribute to the
 /// generated message enum to enable debugging the communication between you client and you server.
+///
+/// The generated message enum also provides a `method_name()` accessor, a `METHODS`
+/// constant listing every RPC of the interface and a `Display` implementation rendering
+/// the call as `method(args)`, which makes it usable with the logging middlewares.
+///
+/// ```rust
+/// use async_pub_sub_macros::rpc_interface;
+///
+/// #[rpc_interface]
+/// trait Calculator {
+///     async fn add(&self, left: i32, right: i32) -> i32;
+/// }
+///
+/// let (request, _response) = async_pub_sub::Request::new((1, 2));
+/// let message = CalculatorMessage::Add(request);
+///
+/// assert_eq!(message.method(), CalculatorMethod::Add);
+/// assert_eq!(message.method_name(), "add");
+/// assert_eq!(CalculatorMessage::METHODS, &["add"]);
+/// assert_eq!(message.to_string(), "add(1, 2)");
+/// ```
+///
+/// The server trait provides a `run()` method handling the requests forever, and a
+/// `run_until(shutdown)` method stopping once the given `async_pub_sub::ShutdownSignal` is
+/// cancelled, after handling the requests already queued. The latter is meant to be called
+/// from `async_pub_sub::Service::run`, so that servers can be managed by a `Supervisor`.
+///
+/// With the `serde` feature of `async_pub_sub` enabled, the `serde` option additionally
+/// generates serializable `XxxCall` and `XxxReply` enums carrying correlation ids, and
+/// implements `async_pub_sub::RemoteRpcMessage` for the message enum so that the interface
+/// can be served across process boundaries with `RemoteRpcClient` and `RemoteRpcServer`.
+///
+/// ```rust,ignore
+/// #[rpc_interface(Debug, serde)]
+/// trait PersistencyInterface {
+///     async fn store_data(&mut self, data: Vec<u8>);
+/// }
+/// ```
 #[proc_macro_attribute]
 pub fn rpc_interface(attr: TokenStream, item: TokenStream) -> TokenStream {
     let input = parse_macro_input!(item as syn::Item);
@@ -130,6 +253,11 @@ pub fn rpc_interface(attr: TokenStream, item: TokenStream) -> TokenStream {
 
 /// Creates a connection between a single publisher and subscriber.
 ///
+/// A route can also go through forwarders, `publisher -> forwarder -> subscriber`, and fan
+/// out or in with bracketed lists, `publisher -> [subscriber_a, subscriber_b]` or
+/// `[publisher_a, publisher_b] -> subscriber`. Each hop is type checked on its own, the
+/// optional message type applying to all of them.
+///
 /// # Examples
 /// ```rust
 /// use async_pub_sub_macros::route;
@@ -140,6 +268,13 @@ pub fn rpc_interface(attr: TokenStream, item: TokenStream) -> TokenStream {
 /// let mut subscriber = SubscriberImpl::new("subscriber");
 ///
 /// route!(publisher -> subscriber: i32).unwrap();
+///
+/// // Route through a forwarder
+/// let mut publisher = PublisherImpl::new("publisher", 1);
+/// let mut forwarder = async_pub_sub::ForwarderImpl::new("forwarder");
+/// let mut subscriber = SubscriberImpl::new("subscriber");
+///
+/// route!(publisher -> forwarder -> subscriber: i32).unwrap();
 /// ```
 #[proc_macro]
 pub fn route(input: TokenStream) -> TokenStream {
@@ -149,6 +284,8 @@ pub fn route(input: TokenStream) -> TokenStream {
 
 /// Creates connections between multiple publishers and subscribers.
 ///
+/// Each route accepts the chain and fan-out/fan-in syntax of [`route!`].
+///
 /// # Examples
 /// ```rust
 /// use async_pub_sub_macros::routes;
@@ -159,10 +296,14 @@ pub fn route(input: TokenStream) -> TokenStream {
 /// let mut publisher_b = PublisherImpl::new("publisher_b", 1);
 /// let mut subscriber_a = SubscriberImpl::new("subscriber_a");
 /// let mut subscriber_b = SubscriberImpl::new("subscriber_b");
+/// let mut publisher_c = PublisherImpl::<i32>::new("publisher_c", 1);
+/// let mut publisher_d = PublisherImpl::<i32>::new("publisher_d", 1);
+/// let mut subscriber_c = SubscriberImpl::new("subscriber_c");
 ///
 /// routes! {
 ///     publisher_a -> subscriber_a,
 ///     publisher_b -> subscriber_b: String,
+///     [publisher_c, publisher_d] -> subscriber_c,
 /// }.unwrap();
 /// ```
 #[proc_macro]
@@ -170,3 +311,78 @@ pub fn routes(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as route::RoutesInput);
     route::generate_routes(input)
 }
+
+/// Declares a whole publish-subscribe graph, checked at compile time.
+///
+/// The macro generates a struct owning all the declared services, a `new()` constructor
+/// creating the services and connecting them with the listed routes, and a `run()` method
+/// running all the services concurrently. Every service must therefore provide a
+/// `run(self)` async method. The fields holding the services, like the constructor and the
+/// `run()` method, have the visibility of the struct.
+///
+/// The graph is validated when the macro expands:
+/// - routes must connect declared services,
+/// - a publisher of a given message type can only be bound to one subscriber, and the routes
+///   leaving a service bound more than once must name their message type. The types are
+///   compared as written when the macro expands, so the same type should be written the same
+///   way in each route. A type written differently (e.g. `Message` and `crate::Message`) is
+///   still rejected by the compiler, with a conflicting implementation error naming the
+///   publisher,
+/// - the services marked with `#[subscriber]` must be connected to at least one publisher,
+/// - the routes whose publisher and subscriber do not share the message type fail to compile,
+///   the error pointing at the route.
+///
+/// The routes accept the chain and fan-out/fan-in syntax of [`route!`], with services as
+/// endpoints.
+///
+/// # Examples
+/// ```rust
+/// use async_pub_sub::{Publisher, PublisherImpl, Subscriber, SubscriberImpl};
+/// use async_pub_sub_macros::{DerivePublisher, DeriveSubscriber, topology};
+///
+/// #[derive(DerivePublisher)]
+/// struct Timer {
+///     #[publisher(u64)]
+///     publisher: PublisherImpl<u64>,
+/// }
+///
+/// impl Timer {
+///     async fn run(self) {
+///         self.publisher.publish(42).await.unwrap();
+///     }
+/// }
+///
+/// #[derive(DeriveSubscriber)]
+/// struct Cache {
+///     #[subscriber(u64)]
+///     subscriber: SubscriberImpl<u64>,
+/// }
+///
+/// impl Cache {
+///     async fn run(mut self) {
+///         assert_eq!(self.subscriber.receive().await, 42);
+///     }
+/// }
+///
+/// topology! {
+///     /// The services of the application.
+///     struct App {
+///         timer: Timer = Timer { publisher: PublisherImpl::new("timer", 1) },
+///         #[subscriber]
+///         cache: Cache = Cache { subscriber: SubscriberImpl::new("cache") },
+///     }
+///
+///     timer -> cache: u64,
+/// }
+///
+/// # #[tokio::main]
+/// # async fn main() -> async_pub_sub::Result<()> {
+/// App::new()?.run().await;
+/// # Ok(())
+/// # }
+/// ```
+#[proc_macro]
+pub fn topology(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as topology::TopologyInput);
+    topology::generate_topology(input)
+}
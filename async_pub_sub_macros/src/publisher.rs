@@ -145,6 +145,18 @@ impl PublisherField {
                     async_pub_sub::Publisher::publish(&self.#field_name, message)
                 }
 
+                fn close(&self) {
+                    async_pub_sub::Publisher::close(&self.#field_name)
+                }
+
+                fn is_closed(&self) -> bool {
+                    async_pub_sub::Publisher::is_closed(&self.#field_name)
+                }
+
+                fn closed(&self) -> async_pub_sub::futures::future::BoxFuture<'_, ()> {
+                    async_pub_sub::Publisher::closed(&self.#field_name)
+                }
+
                 fn get_message_stream(
                     &mut self,
                     subscriber_name: &'static str,
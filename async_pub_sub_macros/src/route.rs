@@ -1,21 +1,35 @@
 use proc_macro::TokenStream;
-use proc_macro2::TokenTree;
-use quote::quote;
+use proc_macro2::{Spacing, TokenTree};
+use quote::{ToTokens, quote, quote_spanned};
 use syn::parse::{Parse, ParseStream};
-use syn::{Token, Type};
+use syn::punctuated::Punctuated;
+use syn::{Token, Type, bracketed};
 
+/// A route going through one or more hops: `a -> b`, `a -> forwarder -> b`, `a -> [b, c]`
+/// or `[a, b] -> c`.
 pub struct RouteInput {
-    subscriber: syn::Expr,
-    publisher: proc_macro2::TokenStream,
+    /// The successive stages of the route, each of them holding one or more endpoints
+    stages: Vec<Vec<proc_macro2::TokenStream>>,
     message_type: Option<Type>,
 }
 
+/// A connection between a publisher and a subscriber of a route.
+pub(crate) struct Hop<'a> {
+    pub(crate) publisher: &'a proc_macro2::TokenStream,
+    pub(crate) subscriber: &'a proc_macro2::TokenStream,
+    pub(crate) message_type: Option<&'a Type>,
+}
+
 impl Parse for RouteInput {
     fn parse(input: ParseStream) -> syn::Result<Self> {
-        let publisher = parse_publisher_token_stream(input)?;
-        input.parse::<Token![->]>()?;
-
-        let subscriber = input.parse()?;
+        let mut stages = vec![parse_stage(input)?];
+        if !input.peek(Token![->]) {
+            return Err(input.error("Expected '->'"));
+        }
+        while input.peek(Token![->]) {
+            input.parse::<Token![->]>()?;
+            stages.push(parse_stage(input)?);
+        }
 
         let message_type = if input.peek(Token![:]) {
             input.parse::<Token![:]>()?;
@@ -25,64 +39,140 @@ impl Parse for RouteInput {
         };
 
         Ok(RouteInput {
-            subscriber,
-            publisher,
+            stages,
             message_type,
         })
     }
 }
 
-fn parse_publisher_token_stream(input: ParseStream) -> syn::Result<proc_macro2::TokenStream> {
-    let publisher_start = input.cursor();
+impl RouteInput {
+    /// Returns the connections of the route, each endpoint of a stage being connected to
+    /// each endpoint of the next one.
+    pub(crate) fn hops(&self) -> impl Iterator<Item = Hop<'_>> {
+        self.stages.windows(2).flat_map(move |stages| {
+            stages[0].iter().flat_map(move |publisher| {
+                stages[1].iter().map(move |subscriber| Hop {
+                    publisher,
+                    subscriber,
+                    message_type: self.message_type.as_ref(),
+                })
+            })
+        })
+    }
+}
+
+/// Parses a stage of a route, either a single endpoint or a bracketed list of endpoints.
+fn parse_stage(input: ParseStream) -> syn::Result<Vec<proc_macro2::TokenStream>> {
+    if !input.peek(syn::token::Bracket) {
+        return Ok(vec![parse_endpoint(input)?]);
+    }
+
+    let content;
+    let brackets = bracketed!(content in input);
+    let endpoints: Vec<_> =
+        Punctuated::<_, Token![,]>::parse_terminated_with(&content, parse_endpoint)?
+            .into_iter()
+            .collect();
+    if endpoints.is_empty() {
+        return Err(syn::Error::new(
+            brackets.span.join(),
+            "Expected at least one publisher or subscriber",
+        ));
+    }
+
+    Ok(endpoints)
+}
 
-    input.step(|cursor| {
+/// Parses the tokens of an endpoint, up to the next '->', ',' or ':'.
+///
+/// Expressions cannot be parsed directly as syn would read the '-' of '->' as a subtraction.
+fn parse_endpoint(input: ParseStream) -> syn::Result<proc_macro2::TokenStream> {
+    let endpoint = input.step(|cursor| {
+        let mut endpoint = proc_macro2::TokenStream::new();
         let mut rest = *cursor;
+        let mut after_joint_colon = false;
         while let Some((tt, next)) = rest.token_tree() {
             if let TokenTree::Punct(punct) = &tt {
-                if punct.as_char() == '-' {
-                    if let Some((TokenTree::Punct(punct), _)) = next.token_tree() {
-                        if punct.as_char() == '>' {
-                            return Ok(((), rest));
-                        }
-                    }
+                let is_arrow = punct.as_char() == '-'
+                    && next
+                        .punct()
+                        .is_some_and(|(punct, _)| punct.as_char() == '>');
+                let is_lone_colon = punct.as_char() == ':'
+                    && punct.spacing() == Spacing::Alone
+                    && !after_joint_colon;
+                if is_arrow || is_lone_colon || punct.as_char() == ',' {
+                    break;
                 }
+                after_joint_colon = punct.as_char() == ':' && punct.spacing() == Spacing::Joint;
+            } else {
+                after_joint_colon = false;
             }
+            endpoint.extend(std::iter::once(tt));
             rest = next;
         }
-        Err(cursor.error("Expected '->'"))
+        Ok((endpoint, rest))
     })?;
 
-    let publisher_end = input.cursor();
-
-    let mut cursor = publisher_start;
-    let mut publisher = proc_macro2::TokenStream::new();
-    while cursor < publisher_end {
-        let (token, next) = cursor.token_tree().unwrap();
-        publisher.extend(std::iter::once(token));
-        cursor = next;
+    if endpoint.is_empty() {
+        return Err(input.error("Expected a publisher or subscriber"));
     }
 
-    Ok(publisher)
+    Ok(endpoint)
 }
 
-pub(crate) fn generate_route(input: RouteInput) -> TokenStream {
-    let subscriber = input.subscriber;
-    let publisher = input.publisher;
+/// Returns the span of the first token of an endpoint, the span of the whole endpoint not
+/// being available on stable.
+fn endpoint_span(endpoint: &proc_macro2::TokenStream) -> proc_macro2::Span {
+    endpoint
+        .clone()
+        .into_iter()
+        .next()
+        .map_or_else(proc_macro2::Span::call_site, |token| token.span())
+}
 
-    let output = if let Some(message_type) = input.message_type {
-        quote! {
-            async_pub_sub::SubscriberWrapper::<#message_type>::subscribe_to(
-                &mut #subscriber,
-                async_pub_sub::PublisherWrapper::<_>::get_publisher_mut(&mut #publisher),
-            )
-        }
-    } else {
+/// Generates the subscription of a hop, spanned on its endpoints so that type mismatches
+/// point at the faulty hop.
+fn generate_hop(hop: Hop) -> proc_macro2::TokenStream {
+    let Hop {
+        publisher,
+        subscriber,
+        message_type,
+    } = hop;
+
+    let message_type = message_type
+        .map(ToTokens::to_token_stream)
+        .unwrap_or_else(|| quote! { _ });
+    let publisher = quote_spanned! {endpoint_span(publisher)=>
+        async_pub_sub::PublisherWrapper::<_>::get_publisher_mut(&mut #publisher)
+    };
+
+    quote_spanned! {endpoint_span(subscriber)=>
+        async_pub_sub::SubscriberWrapper::<#message_type>::subscribe_to(
+            &mut #subscriber,
+            #publisher,
+        )
+    }
+}
+
+/// Chains the subscriptions of several hops, stopping at the first failure.
+fn generate_hops<'a>(hops: impl Iterator<Item = Hop<'a>>) -> proc_macro2::TokenStream {
+    let hops = hops.map(|hop| {
+        let hop = generate_hop(hop);
         quote! {
-            async_pub_sub::SubscriberWrapper::<_>::subscribe_to(
-                &mut #subscriber,
-                async_pub_sub::PublisherWrapper::<_>::get_publisher_mut(&mut #publisher),
-            )
+            .and_then(|_| {#hop})
         }
+    });
+
+    quote! {
+        Ok(())#(#hops)*
+    }
+}
+
+pub(crate) fn generate_route(input: RouteInput) -> TokenStream {
+    let mut hops = input.hops();
+    let output = match (hops.next(), hops.next()) {
+        (Some(hop), None) => generate_hop(hop),
+        _ => generate_hops(input.hops()),
     };
 
     output.into()
@@ -104,15 +194,5 @@ impl Parse for RoutesInput {
 }
 
 pub(crate) fn generate_routes(input: RoutesInput) -> TokenStream {
-    let routes = input.routes.into_iter().map(|route| {
-        let route: proc_macro2::TokenStream = generate_route(route).into();
-        quote! {
-            .and_then(|_| {#route})
-        }
-    });
-
-    quote! {
-        Ok(())#(#routes)*
-    }
-    .into()
+    generate_hops(input.routes.iter().flat_map(RouteInput::hops)).into()
 }
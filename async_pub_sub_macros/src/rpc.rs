@@ -21,7 +21,7 @@ impl Parse for AttributeArgs {
 }
 
 pub(crate) fn generate_rpc_interface(attr: TokenStream, input: Item) -> TokenStream {
-   let attrs = parse_macro_input!(attr as AttributeArgs);
+    let attrs = parse_macro_input!(attr as AttributeArgs);
 
     let input_trait = match input.clone() {
         // Clone item for potential error reporting span
@@ -36,13 +36,29 @@ pub(crate) fn generate_rpc_interface(attr: TokenStream, input: Item) -> TokenStr
         }
     };
 
+    // The `serde` option is not a derive, it requests the serializable call and reply enums
+    let (serde_options, derives): (Vec<_>, Vec<_>) =
+        attrs.derives.iter().partition(|ident| *ident == "serde");
+
+    if let Some(serde_option) = serde_options.first()
+        && !cfg!(feature = "serde")
+    {
+        return syn::Error::new_spanned(
+            serde_option,
+            "The serde option of the rpc_interface macro requires the serde feature of async_pub_sub",
+        )
+        .to_compile_error()
+        .into();
+    }
+
     // Extract derives from parsed attributes and convert to iterator
-    let derives = attrs.derives.iter();
+    let derives = derives.into_iter();
 
     let trait_name = input_trait.ident.clone();
     let message_enum_name = format_ident!("{}Message", trait_name);
     let client_name = format_ident!("{}Client", trait_name);
     let server_trait_name = format_ident!("{}Server", trait_name);
+    let method_enum_name = format_ident!("{}Method", trait_name);
 
     let methods: Vec<_> = input_trait
         .items
@@ -65,6 +81,13 @@ pub(crate) fn generate_rpc_interface(attr: TokenStream, input: Item) -> TokenStr
     let server_impl = generate_server_impl(&message_enum_name, &trait_name, &methods);
     let server_trait_impl =
         generate_server_trait_impl(&server_trait_name, &message_enum_name, &trait_name);
+    let introspection_impl =
+        generate_introspection_impl(&message_enum_name, &method_enum_name, &methods);
+    let remote_impl = if serde_options.is_empty() {
+        quote! {}
+    } else {
+        generate_remote_impl(&trait_name, &message_enum_name, &methods)
+    };
 
     let expanded = quote! {
         #[allow(async_fn_in_trait)]
@@ -75,6 +98,10 @@ pub(crate) fn generate_rpc_interface(attr: TokenStream, input: Item) -> TokenStr
             #(#enum_variants)*
         }
 
+        #introspection_impl
+
+        #remote_impl
+
         #[derive(async_pub_sub::macros::DerivePublisher)]
         pub struct #client_name
         {
@@ -104,6 +131,26 @@ pub(crate) fn generate_rpc_interface(attr: TokenStream, input: Item) -> TokenStr
                 }
             }
 
+            async fn run_until(&mut self, shutdown: async_pub_sub::ShutdownSignal) {
+                let mut cancelled = shutdown.cancelled();
+                loop {
+                    let request = {
+                        let receive = self.receive();
+                        async_pub_sub::futures::pin_mut!(receive);
+                        match async_pub_sub::futures::future::select(receive, &mut cancelled).await {
+                            async_pub_sub::futures::future::Either::Left((request, _)) => request,
+                            async_pub_sub::futures::future::Either::Right(_) => break,
+                        }
+                    };
+                    self.handle_request(request).await;
+                }
+
+                // the requests already queued are still handled, so that their callers get a response
+                while let Some(request) = self.try_receive() {
+                    self.handle_request(request).await;
+                }
+            }
+
             async fn handle_request(&mut self, request: #message_enum_name) {
                 match request {
                     #(#server_impl)*
@@ -124,35 +171,35 @@ fn validate_method_signatures(methods: &[&syn::TraitItemFn]) -> syn::Result<()>
 
         // Check inputs for references
         for arg in &method.sig.inputs {
-            if let syn::FnArg::Typed(pat_type) = arg {
-                if let syn::Type::Reference(ty) = &*pat_type.ty {
-                    let arg_name = &pat_type.pat;
-                    return Err(syn::Error::new_spanned(
-                        &*pat_type.ty,
-                        format!(
-                            "References in RPC method arguments are not supported yet. Method '{}' uses a reference in its argument '{}' ({}). Please use owned types.",
-                            method_name,
-                            quote! {#arg_name}, // Attempt to get arg name, might need refinement
-                            quote! {#ty}
-                        ),
-                    ));
-                }
-            }
-        }
-
-        // Check output for references
-        if let syn::ReturnType::Type(_, ty) = &method.sig.output {
-            if let syn::Type::Reference(ref_ty) = &**ty {
+            if let syn::FnArg::Typed(pat_type) = arg
+                && let syn::Type::Reference(ty) = &*pat_type.ty
+            {
+                let arg_name = &pat_type.pat;
                 return Err(syn::Error::new_spanned(
-                    &**ty,
+                    &*pat_type.ty,
                     format!(
-                        "References in RPC method return types are not supported yet. Method '{}' returns a reference ({}). Please use owned types.",
+                        "References in RPC method arguments are not supported yet. Method '{}' uses a reference in its argument '{}' ({}). Please use owned types.",
                         method_name,
-                        quote! {#ref_ty}
+                        quote! {#arg_name}, // Attempt to get arg name, might need refinement
+                        quote! {#ty}
                     ),
                 ));
             }
         }
+
+        // Check output for references
+        if let syn::ReturnType::Type(_, ty) = &method.sig.output
+            && let syn::Type::Reference(ref_ty) = &**ty
+        {
+            return Err(syn::Error::new_spanned(
+                &**ty,
+                format!(
+                    "References in RPC method return types are not supported yet. Method '{}' returns a reference ({}). Please use owned types.",
+                    method_name,
+                    quote! {#ref_ty}
+                ),
+            ));
+        }
     }
     Ok(())
 }
@@ -163,38 +210,252 @@ fn generate_enum_variants<'a>(
     methods.iter().map(|method| {
         let name = &method.sig.ident;
         let variant_name = format_ident!("{}", name.to_string().to_upper_camel_case());
+        let input_types = request_content_type(method);
+        let output_type = response_type(method);
+
+        quote! {
+            #variant_name(async_pub_sub::Request<#input_types, #output_type>),
+        }
+    })
+}
+
+fn generate_introspection_impl(
+    message_enum_name: &syn::Ident,
+    method_enum_name: &syn::Ident,
+    methods: &[&syn::TraitItemFn],
+) -> proc_macro2::TokenStream {
+    let method_names: Vec<_> = methods
+        .iter()
+        .map(|method| method.sig.ident.to_string())
+        .collect();
+    let variant_names: Vec<_> = method_names
+        .iter()
+        .map(|name| format_ident!("{}", name.to_upper_camel_case()))
+        .collect();
 
-        let input_types: Vec<_> = method
+    let display_arms = methods.iter().zip(&variant_names).map(|(method, variant_name)| {
+        let name = method.sig.ident.to_string();
+        let arg_count = method
             .sig
             .inputs
             .iter()
-            .filter_map(|input| match input {
-                syn::FnArg::Typed(pat_type) => Some(&pat_type.ty),
-                syn::FnArg::Receiver(_) => None, // ignore self
-            })
-            .collect();
+            .filter(|input| matches!(input, syn::FnArg::Typed(_)))
+            .count();
+
+        match arg_count {
+            0 => quote! {
+                #message_enum_name::#variant_name(_) => write!(f, "{}()", #name),
+            },
+            1 => quote! {
+                #message_enum_name::#variant_name(ref req) => write!(f, "{}({:?})", #name, req.content),
+            },
+            _ => {
+                let args: Vec<_> = (0..arg_count).map(|i| format_ident!("arg{}", i)).collect();
+                let format_string = format!("{{}}({})", vec!["{:?}"; arg_count].join(", "));
+                quote! {
+                    #message_enum_name::#variant_name(ref req) => {
+                        let (#(#args),*) = &req.content;
+                        write!(f, #format_string, #name, #(#args),*)
+                    }
+                }
+            }
+        }
+    });
 
-        let input_types = if input_types.is_empty() {
-            quote! { () }
-        } else if input_types.len() == 1 {
-            let ty = input_types
-                .first()
-                .expect("input_types should not be empty");
+    quote! {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum #method_enum_name {
+            #(#variant_names,)*
+        }
 
-            quote! { #ty }
-        } else {
-            quote! { (#(#input_types),*) }
-        };
+        impl #method_enum_name {
+            pub const ALL: &'static [#method_enum_name] = &[#(#method_enum_name::#variant_names),*];
 
-        let output_type = match &method.sig.output {
-            syn::ReturnType::Type(_, ty) => quote! { #ty },
-            syn::ReturnType::Default => quote! { () },
-        };
+            pub fn name(&self) -> &'static str {
+                match *self {
+                    #(#method_enum_name::#variant_names => #method_names,)*
+                }
+            }
+        }
+
+        impl std::fmt::Display for #method_enum_name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str(self.name())
+            }
+        }
+
+        impl #message_enum_name {
+            pub const METHODS: &'static [&'static str] = &[#(#method_names),*];
+
+            pub fn method(&self) -> #method_enum_name {
+                match *self {
+                    #(#message_enum_name::#variant_names(_) => #method_enum_name::#variant_names,)*
+                }
+            }
 
+            pub fn method_name(&self) -> &'static str {
+                self.method().name()
+            }
+        }
+
+        impl std::fmt::Display for #message_enum_name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                match *self {
+                    #(#display_arms)*
+                }
+            }
+        }
+    }
+}
+
+fn generate_remote_impl(
+    trait_name: &syn::Ident,
+    message_enum_name: &syn::Ident,
+    methods: &[&syn::TraitItemFn],
+) -> proc_macro2::TokenStream {
+    let call_enum_name = format_ident!("{}Call", trait_name);
+    let reply_enum_name = format_ident!("{}Reply", trait_name);
+
+    let variant_names: Vec<_> = methods
+        .iter()
+        .map(|method| format_ident!("{}", method.sig.ident.to_string().to_upper_camel_case()))
+        .collect();
+
+    let call_variants = methods
+        .iter()
+        .zip(&variant_names)
+        .map(|(method, variant_name)| {
+            let input_types = request_content_type(method);
+            quote! {
+                #variant_name { metadata: async_pub_sub::Metadata, args: #input_types },
+            }
+        });
+
+    let reply_variants = methods
+        .iter()
+        .zip(&variant_names)
+        .map(|(method, variant_name)| {
+            let output_type = response_type(method);
+            quote! {
+                #variant_name { correlation_id: u64, response: #output_type },
+            }
+        });
+
+    let into_call_arms = methods.iter().zip(&variant_names).map(|(method, variant_name)| {
+        let unexpected_reply_message = format!("unexpected reply to {} call", method.sig.ident);
         quote! {
-            #variant_name(async_pub_sub::Request<#input_types, #output_type>),
+            #message_enum_name::#variant_name(request) => {
+                let metadata = request.metadata().clone();
+                let async_pub_sub::Request { content, response_sender, .. } = request;
+                let call = #call_enum_name::#variant_name { metadata, args: content };
+                let reply_sender: async_pub_sub::ReplySender<#reply_enum_name> =
+                    Box::new(move |reply| {
+                        #[allow(unreachable_patterns)]
+                        match reply {
+                            #reply_enum_name::#variant_name { response, .. } => response_sender
+                                .send(response)
+                                .map_err(|_| "the caller is no longer waiting for the response".into()),
+                            _ => Err(#unexpected_reply_message.into()),
+                        }
+                    });
+                (call, reply_sender)
+            }
         }
-    })
+    });
+
+    let from_call_arms = variant_names.iter().map(|variant_name| {
+        quote! {
+            #call_enum_name::#variant_name { metadata, args } => {
+                let correlation_id = metadata.id();
+                let (request, response) = async_pub_sub::Request::with_metadata(args, metadata);
+                let reply = async move {
+                    response
+                        .await
+                        .ok()
+                        .map(|response| #reply_enum_name::#variant_name { correlation_id, response })
+                };
+                (#message_enum_name::#variant_name(request), reply.boxed())
+            }
+        }
+    });
+
+    quote! {
+        #[derive(Debug, async_pub_sub::serde::Serialize, async_pub_sub::serde::Deserialize)]
+        #[serde(crate = "async_pub_sub::serde")]
+        pub enum #call_enum_name {
+            #(#call_variants)*
+        }
+
+        #[derive(Debug, async_pub_sub::serde::Serialize, async_pub_sub::serde::Deserialize)]
+        #[serde(crate = "async_pub_sub::serde")]
+        pub enum #reply_enum_name {
+            #(#reply_variants)*
+        }
+
+        impl async_pub_sub::RemoteRpcMessage for #message_enum_name {
+            type Call = #call_enum_name;
+            type Reply = #reply_enum_name;
+
+            fn into_call(self) -> (Self::Call, async_pub_sub::ReplySender<Self::Reply>) {
+                match self {
+                    #(#into_call_arms)*
+                }
+            }
+
+            fn from_call(
+                call: Self::Call,
+            ) -> (Self, async_pub_sub::futures::future::BoxFuture<'static, Option<Self::Reply>>) {
+                use async_pub_sub::futures::FutureExt;
+
+                match call {
+                    #(#from_call_arms)*
+                }
+            }
+
+            fn call_correlation_id(call: &Self::Call) -> u64 {
+                match call {
+                    #(#call_enum_name::#variant_names { metadata, .. } => metadata.id(),)*
+                }
+            }
+
+            fn reply_correlation_id(reply: &Self::Reply) -> u64 {
+                match *reply {
+                    #(#reply_enum_name::#variant_names { correlation_id, .. } => correlation_id,)*
+                }
+            }
+        }
+    }
+}
+
+fn request_content_type(method: &syn::TraitItemFn) -> proc_macro2::TokenStream {
+    let input_types: Vec<_> = method
+        .sig
+        .inputs
+        .iter()
+        .filter_map(|input| match input {
+            syn::FnArg::Typed(pat_type) => Some(&pat_type.ty),
+            syn::FnArg::Receiver(_) => None, // ignore self
+        })
+        .collect();
+
+    if input_types.is_empty() {
+        quote! { () }
+    } else if input_types.len() == 1 {
+        let ty = input_types
+            .first()
+            .expect("input_types should not be empty");
+
+        quote! { #ty }
+    } else {
+        quote! { (#(#input_types),*) }
+    }
+}
+
+fn response_type(method: &syn::TraitItemFn) -> proc_macro2::TokenStream {
+    match &method.sig.output {
+        syn::ReturnType::Type(_, ty) => quote! { #ty },
+        syn::ReturnType::Default => quote! { () },
+    }
 }
 
 fn generate_client_methods<'a>(
@@ -296,6 +557,7 @@ fn generate_server_impl<'a>(
                 let async_pub_sub::Request {
                     #content,
                     response_sender,
+                    ..
                 } = req;
                 #function_call
                 response_sender.send(response).expect("failed to send response");
@@ -0,0 +1,322 @@
+use heck::ToSnakeCase;
+use proc_macro::TokenStream;
+use quote::{format_ident, quote};
+use syn::DeriveInput;
+
+pub(crate) fn derive_split_publisher_impl(input: DeriveInput) -> TokenStream {
+    InputEnum::try_from_derive_input(input, "SplitPublisher")
+        .map(|input| input.generate_splitter())
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
+
+pub(crate) fn derive_merge_subscriber_impl(input: DeriveInput) -> TokenStream {
+    InputEnum::try_from_derive_input(input, "MergeSubscriber")
+        .map(|input| input.generate_merger())
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
+
+struct InputEnum {
+    visibility: syn::Visibility,
+    enum_name: syn::Ident,
+    variants: Vec<EnumVariant>,
+}
+
+struct EnumVariant {
+    name: syn::Ident,
+    field_name: syn::Ident,
+    /// The type of the single field of the variant, `None` for unit variants
+    payload_type: Option<syn::Type>,
+}
+
+impl InputEnum {
+    fn try_from_derive_input(input: DeriveInput, macro_name: &str) -> syn::Result<Self> {
+        let syn::Data::Enum(data) = &input.data else {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                format!("{macro_name} macro can only be derived for enums"),
+            ));
+        };
+
+        if !input.generics.params.is_empty() {
+            return Err(syn::Error::new_spanned(
+                &input.generics,
+                format!("{macro_name} macro does not support generic enums"),
+            ));
+        }
+
+        let variants = data
+            .variants
+            .iter()
+            .map(|variant| EnumVariant::try_from_variant(variant, macro_name))
+            .collect::<syn::Result<Vec<_>>>()?;
+
+        Ok(Self {
+            visibility: input.vis.clone(),
+            enum_name: input.ident.clone(),
+            variants,
+        })
+    }
+
+    fn generate_splitter(&self) -> proc_macro2::TokenStream {
+        let visibility = &self.visibility;
+        let enum_name = &self.enum_name;
+        let splitter_name = format_ident!("{}Splitter", enum_name);
+        let splitter_doc = format!(
+            "Splits a stream of [`{enum_name}`] messages into one publisher per variant, \
+             carrying the payload of the variant."
+        );
+
+        let output_fields = self.variants.iter().map(|variant| {
+            let field_name = &variant.field_name;
+            let payload_type = variant.payload_type_tokens();
+            let field_doc = format!("Publishes the payloads of the `{}` variant", variant.name);
+            quote! {
+                #[doc = #field_doc]
+                #visibility #field_name: async_pub_sub::RouterOutput<#payload_type>
+            }
+        });
+
+        let output_initializations = self.variants.iter().map(|variant| {
+            let field_name = &variant.field_name;
+            let variant_name = &variant.name;
+            quote! {
+                #field_name: async_pub_sub::RouterOutput::new(
+                    Box::leak(
+                        format!(concat!("{}::", stringify!(#variant_name)), name).into_boxed_str(),
+                    ),
+                    buffer_size,
+                )
+            }
+        });
+
+        let output_names = self.variants.iter().map(|variant| &variant.field_name);
+
+        let match_arms = self.variants.iter().map(|variant| {
+            let field_name = &variant.field_name;
+            let variant_name = &variant.name;
+            if variant.payload_type.is_some() {
+                quote! {
+                    #enum_name::#variant_name(payload) => {
+                        self.#field_name.forward(self.__name, payload).await
+                    }
+                }
+            } else {
+                quote! {
+                    #enum_name::#variant_name => self.#field_name.forward(self.__name, ()).await
+                }
+            }
+        });
+
+        quote! {
+            #[doc = #splitter_doc]
+            #visibility struct #splitter_name {
+                __name: &'static str,
+                __subscriber: async_pub_sub::SubscriberImpl<#enum_name>,
+                #(#output_fields,)*
+            }
+
+            impl #splitter_name {
+                /// Creates a new splitter, each variant publisher having its own buffer and
+                /// being named after the splitter and the variant, like `name::Variant`.
+                #visibility fn new(name: &'static str, buffer_size: usize) -> Self {
+                    Self {
+                        __name: name,
+                        __subscriber: async_pub_sub::SubscriberImpl::new(name),
+                        #(#output_initializations,)*
+                    }
+                }
+
+                /// Publishes the payloads of the received messages on the publisher of their
+                /// variant, until all the publishers the splitter subscribed to are closed or
+                /// dropped. The variant publishers are then closed.
+                #visibility async fn run(&mut self) {
+                    while let Some(message) = self.__subscriber.next_message().await {
+                        match message {
+                            #(#match_arms)*
+                        }
+                    }
+
+                    #(self.#output_names.close();)*
+                }
+            }
+
+            impl async_pub_sub::Subscriber for #splitter_name {
+                type Message = #enum_name;
+
+                fn get_name(&self) -> &'static str {
+                    self.__name
+                }
+
+                fn subscribe_to(
+                    &mut self,
+                    publisher: &mut dyn async_pub_sub::Publisher<Message = Self::Message>,
+                ) -> async_pub_sub::Result<()> {
+                    self.__subscriber.subscribe_to(publisher)
+                }
+
+                fn receive(&mut self) -> async_pub_sub::futures::future::BoxFuture<'_, Self::Message> {
+                    panic!(concat!(
+                        stringify!(#splitter_name),
+                        " does not implement receive method, use run instead"
+                    ))
+                }
+            }
+        }
+    }
+
+    fn generate_merger(&self) -> proc_macro2::TokenStream {
+        let visibility = &self.visibility;
+        let enum_name = &self.enum_name;
+        let merger_name = format_ident!("{}Merger", enum_name);
+        let merger_doc = format!(
+            "Merges the payloads received for each variant back into a stream of \
+             [`{enum_name}`] messages."
+        );
+
+        let input_fields = self.variants.iter().map(|variant| {
+            let field_name = &variant.field_name;
+            let payload_type = variant.payload_type_tokens();
+            let field_doc = format!("Receives the payloads of the `{}` variant", variant.name);
+            quote! {
+                #[doc = #field_doc]
+                #visibility #field_name: async_pub_sub::SubscriberImpl<#payload_type>
+            }
+        });
+
+        let input_initializations = self.variants.iter().map(|variant| {
+            let field_name = &variant.field_name;
+            let variant_name = &variant.name;
+            quote! {
+                #field_name: async_pub_sub::SubscriberImpl::new(Box::leak(
+                    format!(concat!("{}::", stringify!(#variant_name)), name).into_boxed_str(),
+                ))
+            }
+        });
+
+        let input_streams = self.variants.iter().map(|variant| {
+            let field_name = &variant.field_name;
+            let variant_name = &variant.name;
+            let wrap_payload = if variant.payload_type.is_some() {
+                quote! { #enum_name::#variant_name }
+            } else {
+                quote! { |_| #enum_name::#variant_name }
+            };
+            quote! {
+                Box::pin(async_pub_sub::futures::StreamExt::map(
+                    std::mem::replace(
+                        &mut self.#field_name,
+                        async_pub_sub::SubscriberImpl::new(self.__name),
+                    )
+                    .into_stream(),
+                    #wrap_payload,
+                ))
+            }
+        });
+
+        quote! {
+            #[doc = #merger_doc]
+            #visibility struct #merger_name {
+                __name: &'static str,
+                __subscriber_name: Option<&'static str>,
+                #(#input_fields,)*
+            }
+
+            impl #merger_name {
+                /// Creates a new merger, each variant subscriber being named after the merger
+                /// and the variant, like `name::Variant`.
+                #visibility fn new(name: &'static str) -> Self {
+                    Self {
+                        __name: name,
+                        __subscriber_name: None,
+                        #(#input_initializations,)*
+                    }
+                }
+            }
+
+            impl async_pub_sub::Publisher for #merger_name {
+                type Message = #enum_name;
+
+                fn get_name(&self) -> &'static str {
+                    self.__name
+                }
+
+                fn publish(
+                    &self,
+                    _message: Self::Message,
+                ) -> async_pub_sub::futures::future::BoxFuture<'_, async_pub_sub::Result<()>> {
+                    panic!(concat!(stringify!(#merger_name), " does not implement publish method"))
+                }
+
+                fn get_message_stream(
+                    &mut self,
+                    subscriber_name: &'static str,
+                ) -> async_pub_sub::Result<
+                    std::pin::Pin<
+                        Box<
+                            dyn async_pub_sub::futures::Stream<Item = Self::Message>
+                                + Send
+                                + Sync
+                                + 'static,
+                        >,
+                    >,
+                > {
+                    if let Some(bound_subscriber_name) = self.__subscriber_name {
+                        return Err(format!(
+                            "{} merger can only be bound to one subscriber (already bound to {})",
+                            self.__name, bound_subscriber_name
+                        )
+                        .into());
+                    }
+                    self.__subscriber_name = Some(subscriber_name);
+
+                    let streams: Vec<
+                        std::pin::Pin<
+                            Box<
+                                dyn async_pub_sub::futures::Stream<Item = Self::Message>
+                                    + Send
+                                    + Sync
+                                    + 'static,
+                            >,
+                        >,
+                    > = vec![#(#input_streams),*];
+
+                    Ok(Box::pin(async_pub_sub::futures::stream::select_all(streams)))
+                }
+            }
+        }
+    }
+}
+
+impl EnumVariant {
+    fn try_from_variant(variant: &syn::Variant, macro_name: &str) -> syn::Result<Self> {
+        let payload_type = match &variant.fields {
+            syn::Fields::Unit => None,
+            syn::Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+                Some(fields.unnamed[0].ty.clone())
+            }
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    variant,
+                    format!(
+                        "{macro_name} macro requires variants with a single unnamed field or no field"
+                    ),
+                ));
+            }
+        };
+
+        Ok(Self {
+            name: variant.ident.clone(),
+            field_name: format_ident!("{}", variant.ident.to_string().to_snake_case()),
+            payload_type,
+        })
+    }
+
+    fn payload_type_tokens(&self) -> proc_macro2::TokenStream {
+        match &self.payload_type {
+            Some(payload_type) => quote! { #payload_type },
+            None => quote! { () },
+        }
+    }
+}
@@ -151,6 +151,14 @@ impl SubscriberField {
                 fn receive(&mut self) -> async_pub_sub::futures::future::BoxFuture<Self::Message> {
                     async_pub_sub::Subscriber::receive(&mut self.#field_name)
                 }
+
+                fn try_receive(&mut self) -> Option<Self::Message> {
+                    async_pub_sub::Subscriber::try_receive(&mut self.#field_name)
+                }
+
+                fn next_message(&mut self) -> async_pub_sub::futures::future::BoxFuture<Option<Self::Message>> {
+                    async_pub_sub::Subscriber::next_message(&mut self.#field_name)
+                }
             }
         }
     }
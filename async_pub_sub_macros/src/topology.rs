@@ -0,0 +1,346 @@
+use std::collections::HashMap;
+
+use proc_macro::TokenStream;
+use quote::{ToTokens, format_ident, quote, quote_spanned};
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{Token, Type, braced, spanned::Spanned};
+
+use crate::route::{Hop, RouteInput};
+
+pub struct TopologyInput {
+    attributes: Vec<syn::Attribute>,
+    visibility: syn::Visibility,
+    struct_name: syn::Ident,
+    services: Vec<Service>,
+    routes: Vec<TopologyRoute>,
+}
+
+struct Service {
+    attributes: Vec<syn::Attribute>,
+    name: syn::Ident,
+    service_type: Type,
+    init: syn::Expr,
+    /// Whether the service must be connected to at least one publisher
+    required_subscriber: bool,
+}
+
+struct TopologyRoute {
+    publisher: syn::Ident,
+    subscriber: syn::Ident,
+    message_type: Option<Type>,
+}
+
+impl Parse for TopologyInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let attributes = input.call(syn::Attribute::parse_outer)?;
+        let visibility = input.parse()?;
+        input.parse::<Token![struct]>()?;
+        let struct_name = input.parse()?;
+
+        let content;
+        braced!(content in input);
+        let services = Punctuated::<Service, Token![,]>::parse_terminated(&content)?
+            .into_iter()
+            .collect();
+
+        let routes = Punctuated::<RouteInput, Token![,]>::parse_terminated(input)?
+            .iter()
+            .flat_map(RouteInput::hops)
+            .map(TopologyRoute::try_from_hop)
+            .collect::<syn::Result<_>>()?;
+
+        Ok(Self {
+            attributes,
+            visibility,
+            struct_name,
+            services,
+            routes,
+        })
+    }
+}
+
+impl Parse for Service {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut attributes = input.call(syn::Attribute::parse_outer)?;
+        let attribute_count = attributes.len();
+        attributes.retain(|attribute| !attribute.path().is_ident("subscriber"));
+        let required_subscriber = attributes.len() != attribute_count;
+
+        let name = input.parse()?;
+        input.parse::<Token![:]>()?;
+        let service_type = input.parse()?;
+        input.parse::<Token![=]>()?;
+        let init = input.parse()?;
+
+        Ok(Self {
+            attributes,
+            name,
+            service_type,
+            init,
+            required_subscriber,
+        })
+    }
+}
+
+impl TopologyRoute {
+    /// Converts a hop of a route, whose endpoints must be service names.
+    fn try_from_hop(hop: Hop) -> syn::Result<Self> {
+        let service_name = |endpoint: &proc_macro2::TokenStream| {
+            syn::parse2::<syn::Ident>(endpoint.clone()).map_err(|_| {
+                syn::Error::new_spanned(endpoint, "Expected the name of a declared service")
+            })
+        };
+
+        Ok(Self {
+            publisher: service_name(hop.publisher)?,
+            subscriber: service_name(hop.subscriber)?,
+            message_type: hop.message_type.cloned(),
+        })
+    }
+}
+
+pub(crate) fn generate_topology(input: TopologyInput) -> TokenStream {
+    input
+        .validate()
+        .map(|_| input.generate())
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
+
+impl TopologyInput {
+    /// Checks the whole graph, reporting all the errors at once.
+    fn validate(&self) -> syn::Result<()> {
+        let mut errors = Vec::new();
+
+        let mut services = HashMap::new();
+        for service in &self.services {
+            if let Some(previous) = services.insert(service.name.to_string(), &service.name) {
+                errors.push(syn::Error::new_spanned(
+                    &service.name,
+                    format!("service `{}` is declared more than once", service.name),
+                ));
+                errors.push(syn::Error::new_spanned(previous, "first declared here"));
+            }
+        }
+
+        for route in &self.routes {
+            for endpoint in [&route.publisher, &route.subscriber] {
+                if !services.contains_key(&endpoint.to_string()) {
+                    errors.push(syn::Error::new_spanned(
+                        endpoint,
+                        format!("unknown service `{endpoint}`"),
+                    ));
+                }
+            }
+        }
+
+        let mut bindings: HashMap<(String, String), &TopologyRoute> = HashMap::new();
+        let mut untyped_publishers = Vec::new();
+        for route in &self.routes {
+            let message_type = route
+                .message_type
+                .as_ref()
+                .map(|message_type| message_type.to_token_stream().to_string());
+            let routes_from_publisher = self
+                .routes
+                .iter()
+                .filter(|other| other.publisher == route.publisher)
+                .count();
+
+            let Some(message_type) = message_type else {
+                // reported once per publisher, on its first untyped route
+                if routes_from_publisher > 1 && !untyped_publishers.contains(&&route.publisher) {
+                    untyped_publishers.push(&route.publisher);
+                    errors.push(syn::Error::new_spanned(
+                        &route.publisher,
+                        format!(
+                            "`{}` has several routes, each of them must name its message type \
+                             (e.g. `{} -> {}: Message`)",
+                            route.publisher, route.publisher, route.subscriber
+                        ),
+                    ));
+                }
+                continue;
+            };
+
+            let key = (route.publisher.to_string(), message_type);
+            if let Some(previous) = bindings.insert(key, route) {
+                errors.push(syn::Error::new_spanned(
+                    &route.subscriber,
+                    format!(
+                        "publisher `{}` of `{}` messages is already bound to `{}`, it can only be \
+                         bound to one subscriber",
+                        route.publisher,
+                        route.message_type.to_token_stream(),
+                        previous.subscriber
+                    ),
+                ));
+            }
+        }
+
+        for service in &self.services {
+            if service.required_subscriber
+                && !self
+                    .routes
+                    .iter()
+                    .any(|route| route.subscriber == service.name)
+            {
+                errors.push(syn::Error::new_spanned(
+                    &service.name,
+                    format!(
+                        "subscriber `{}` is not connected to any publisher",
+                        service.name
+                    ),
+                ));
+            }
+        }
+
+        errors
+            .into_iter()
+            .reduce(|mut error, other| {
+                error.combine(other);
+                error
+            })
+            .map_or(Ok(()), Err)
+    }
+
+    fn generate(&self) -> proc_macro2::TokenStream {
+        let Self {
+            attributes,
+            visibility,
+            struct_name,
+            ..
+        } = self;
+
+        let fields = self.services.iter().map(|service| {
+            let Service {
+                attributes,
+                name,
+                service_type,
+                ..
+            } = service;
+            quote! {
+                #(#attributes)*
+                #visibility #name: #service_type
+            }
+        });
+
+        let service_names: Vec<_> = self.services.iter().map(|service| &service.name).collect();
+        let service_inits = self.services.iter().map(|service| {
+            let Service { name, init, .. } = service;
+            let routed = self
+                .routes
+                .iter()
+                .any(|route| route.publisher == *name || route.subscriber == *name);
+            if routed {
+                quote! { let mut #name = #init; }
+            } else {
+                quote! { let #name = #init; }
+            }
+        });
+        let routes = self.routes.iter().map(TopologyRoute::generate);
+        let binding_checks = self.generate_binding_checks();
+
+        quote! {
+            #(#attributes)*
+            #visibility struct #struct_name {
+                #(#fields,)*
+            }
+
+            impl #struct_name {
+                /// Creates all the services and connects them according to the topology.
+                #visibility fn new() -> async_pub_sub::Result<Self> {
+                    #(#service_inits)*
+                    #(#routes)*
+                    Ok(Self { #(#service_names),* })
+                }
+
+                /// Runs all the services concurrently, until all of them return.
+                #visibility async fn run(self) {
+                    async_pub_sub::futures::join!(#(self.#service_names.run()),*);
+                }
+            }
+
+            #(#binding_checks)*
+        }
+    }
+
+    /// Generates, for each publisher with several typed routes, a trait implemented for each of
+    /// their message types, so that the compiler rejects two routes of the same type written
+    /// differently (e.g. `Message` and `crate::Message`).
+    ///
+    /// The routes whose types are written the same way are already rejected by
+    /// [`TopologyInput::validate`], their type is implemented only once.
+    fn generate_binding_checks(&self) -> Vec<proc_macro2::TokenStream> {
+        let mut publishers: Vec<(&syn::Ident, Vec<&Type>)> = Vec::new();
+        for route in &self.routes {
+            let Some(message_type) = &route.message_type else {
+                continue;
+            };
+            let types = match publishers
+                .iter_mut()
+                .find(|(publisher, _)| **publisher == route.publisher)
+            {
+                Some((_, types)) => types,
+                None => {
+                    publishers.push((&route.publisher, Vec::new()));
+                    &mut publishers.last_mut().expect("just pushed").1
+                }
+            };
+            let spelling = message_type.to_token_stream().to_string();
+            if !types
+                .iter()
+                .any(|other| other.to_token_stream().to_string() == spelling)
+            {
+                types.push(message_type);
+            }
+        }
+
+        publishers
+            .into_iter()
+            .filter(|(_, types)| types.len() > 1)
+            .map(|(publisher, types)| {
+                let check = format_ident!("{}_is_bound_once_per_message_type", publisher);
+                let impls = types.iter().map(|message_type| {
+                    quote_spanned! {message_type.span()=>
+                        impl #check for #message_type {}
+                    }
+                });
+                quote! {
+                    const _: () = {
+                        #[allow(non_camel_case_types)]
+                        trait #check {}
+                        #(#impls)*
+                    };
+                }
+            })
+            .collect()
+    }
+}
+
+impl TopologyRoute {
+    /// Generates the subscription, spanned on the route so that type mismatches point at it.
+    fn generate(&self) -> proc_macro2::TokenStream {
+        let Self {
+            publisher,
+            subscriber,
+            message_type,
+        } = self;
+
+        let message_type = message_type
+            .as_ref()
+            .map(ToTokens::to_token_stream)
+            .unwrap_or_else(|| quote! { _ });
+        let publisher = quote_spanned! {publisher.span()=>
+            async_pub_sub::PublisherWrapper::<#message_type>::get_publisher_mut(&mut #publisher)
+        };
+
+        quote_spanned! {subscriber.span()=>
+            async_pub_sub::SubscriberWrapper::<#message_type>::subscribe_to(
+                &mut #subscriber,
+                #publisher,
+            )?;
+        }
+    }
+}
@@ -15,6 +15,15 @@ impl<P: Publisher> async_pub_sub::Publisher for TestPublisherA<P> {
     ) -> async_pub_sub::futures::future::BoxFuture<async_pub_sub::Result<()>> {
         async_pub_sub::Publisher::publish(&self.publisher_a, message)
     }
+    fn close(&self) {
+        async_pub_sub::Publisher::close(&self.publisher_a)
+    }
+    fn is_closed(&self) -> bool {
+        async_pub_sub::Publisher::is_closed(&self.publisher_a)
+    }
+    fn closed(&self) -> async_pub_sub::futures::future::BoxFuture<'_, ()> {
+        async_pub_sub::Publisher::closed(&self.publisher_a)
+    }
     fn get_message_stream(
         &mut self,
         subscriber_name: &'static str,
@@ -53,6 +62,15 @@ where
     ) -> async_pub_sub::futures::future::BoxFuture<async_pub_sub::Result<()>> {
         async_pub_sub::Publisher::publish(&self.publisher_b, message)
     }
+    fn close(&self) {
+        async_pub_sub::Publisher::close(&self.publisher_b)
+    }
+    fn is_closed(&self) -> bool {
+        async_pub_sub::Publisher::is_closed(&self.publisher_b)
+    }
+    fn closed(&self) -> async_pub_sub::futures::future::BoxFuture<'_, ()> {
+        async_pub_sub::Publisher::closed(&self.publisher_b)
+    }
     fn get_message_stream(
         &mut self,
         subscriber_name: &'static str,
@@ -0,0 +1,93 @@
+#![allow(unused_imports, dead_code)]
+use async_pub_sub::{ForwarderImpl, PublisherImpl, SubscriberImpl};
+use async_pub_sub_macros::{DerivePublisher, route, routes};
+struct Timer {
+    #[publisher(u64)]
+    ticks: PublisherImpl<u64>,
+    #[publisher(String)]
+    labels: PublisherImpl<String>,
+}
+impl async_pub_sub::PublisherWrapper<u64> for Timer {
+    fn get_publisher(&self) -> &dyn async_pub_sub::Publisher<Message = u64> {
+        &self.ticks
+    }
+    fn get_publisher_mut(&mut self) -> &mut dyn async_pub_sub::Publisher<Message = u64> {
+        &mut self.ticks
+    }
+}
+impl async_pub_sub::PublisherWrapper<String> for Timer {
+    fn get_publisher(&self) -> &dyn async_pub_sub::Publisher<Message = String> {
+        &self.labels
+    }
+    fn get_publisher_mut(
+        &mut self,
+    ) -> &mut dyn async_pub_sub::Publisher<Message = String> {
+        &mut self.labels
+    }
+}
+fn main() {
+    let mut producer = PublisherImpl::<i32>::new("producer", 1);
+    let mut forwarder = ForwarderImpl::<i32>::new("forwarder");
+    let mut consumer = SubscriberImpl::<i32>::new("consumer");
+    Ok(())
+        .and_then(|_| {
+            async_pub_sub::SubscriberWrapper::<
+                i32,
+            >::subscribe_to(
+                &mut forwarder,
+                async_pub_sub::PublisherWrapper::<_>::get_publisher_mut(&mut producer),
+            )
+        })
+        .and_then(|_| {
+            async_pub_sub::SubscriberWrapper::<
+                i32,
+            >::subscribe_to(
+                &mut consumer,
+                async_pub_sub::PublisherWrapper::<_>::get_publisher_mut(&mut forwarder),
+            )
+        })
+        .unwrap();
+    let mut timer = Timer {
+        ticks: PublisherImpl::new("timer", 1),
+        labels: PublisherImpl::new("timer", 1),
+    };
+    let mut cache = SubscriberImpl::<u64>::new("cache");
+    let mut logger = SubscriberImpl::<String>::new("logger");
+    let mut publisher_a = PublisherImpl::<i32>::new("publisher_a", 1);
+    let mut publisher_b = PublisherImpl::<i32>::new("publisher_b", 1);
+    let mut sink = SubscriberImpl::<i32>::new("sink");
+    Ok(())
+        .and_then(|_| {
+            async_pub_sub::SubscriberWrapper::<
+                _,
+            >::subscribe_to(
+                &mut cache,
+                async_pub_sub::PublisherWrapper::<_>::get_publisher_mut(&mut timer),
+            )
+        })
+        .and_then(|_| {
+            async_pub_sub::SubscriberWrapper::<
+                _,
+            >::subscribe_to(
+                &mut logger,
+                async_pub_sub::PublisherWrapper::<_>::get_publisher_mut(&mut timer),
+            )
+        })
+        .and_then(|_| {
+            async_pub_sub::SubscriberWrapper::<
+                _,
+            >::subscribe_to(
+                &mut sink,
+                async_pub_sub::PublisherWrapper::<_>::get_publisher_mut(&mut publisher_a),
+            )
+        })
+        .and_then(|_| {
+            async_pub_sub::SubscriberWrapper::<
+                _,
+            >::subscribe_to(
+                &mut sink,
+                async_pub_sub::PublisherWrapper::<_>::get_publisher_mut(&mut publisher_b),
+            )
+        })
+        .unwrap();
+}
@@ -0,0 +1,33 @@
+#![allow(unused_imports, dead_code)]
+use async_pub_sub::{ForwarderImpl, PublisherImpl, SubscriberImpl};
+use async_pub_sub_macros::{DerivePublisher, route, routes};
+
+#[derive(DerivePublisher)]
+struct Timer {
+    #[publisher(u64)]
+    ticks: PublisherImpl<u64>,
+    #[publisher(String)]
+    labels: PublisherImpl<String>,
+}
+
+fn main() {
+    let mut producer = PublisherImpl::<i32>::new("producer", 1);
+    let mut forwarder = ForwarderImpl::<i32>::new("forwarder");
+    let mut consumer = SubscriberImpl::<i32>::new("consumer");
+    route!(producer -> forwarder -> consumer: i32).unwrap();
+
+    let mut timer = Timer {
+        ticks: PublisherImpl::new("timer", 1),
+        labels: PublisherImpl::new("timer", 1),
+    };
+    let mut cache = SubscriberImpl::<u64>::new("cache");
+    let mut logger = SubscriberImpl::<String>::new("logger");
+    let mut publisher_a = PublisherImpl::<i32>::new("publisher_a", 1);
+    let mut publisher_b = PublisherImpl::<i32>::new("publisher_b", 1);
+    let mut sink = SubscriberImpl::<i32>::new("sink");
+    routes!(
+        timer -> [cache, logger],
+        [publisher_a, publisher_b] -> sink,
+    )
+    .unwrap();
+}
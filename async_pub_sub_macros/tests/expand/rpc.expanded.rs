@@ -15,6 +15,132 @@ pub enum RpcInterfaceMessage {
     GetToto(async_pub_sub::Request<(), String>),
     SetTata(async_pub_sub::Request<String, ()>),
 }
+pub enum RpcInterfaceMethod {
+    AddOne,
+    Add,
+    PrefixWithBar,
+    GetToto,
+    SetTata,
+}
+#[automatically_derived]
+impl ::core::fmt::Debug for RpcInterfaceMethod {
+    #[inline]
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        ::core::fmt::Formatter::write_str(
+            f,
+            match self {
+                RpcInterfaceMethod::AddOne => "AddOne",
+                RpcInterfaceMethod::Add => "Add",
+                RpcInterfaceMethod::PrefixWithBar => "PrefixWithBar",
+                RpcInterfaceMethod::GetToto => "GetToto",
+                RpcInterfaceMethod::SetTata => "SetTata",
+            },
+        )
+    }
+}
+#[automatically_derived]
+#[doc(hidden)]
+unsafe impl ::core::clone::TrivialClone for RpcInterfaceMethod {}
+#[automatically_derived]
+impl ::core::clone::Clone for RpcInterfaceMethod {
+    #[inline]
+    fn clone(&self) -> RpcInterfaceMethod {
+        *self
+    }
+}
+#[automatically_derived]
+impl ::core::marker::Copy for RpcInterfaceMethod {}
+#[automatically_derived]
+impl ::core::marker::StructuralPartialEq for RpcInterfaceMethod {}
+#[automatically_derived]
+impl ::core::cmp::PartialEq for RpcInterfaceMethod {
+    #[inline]
+    fn eq(&self, other: &RpcInterfaceMethod) -> bool {
+        let __self_discr = ::core::intrinsics::discriminant_value(self);
+        let __arg1_discr = ::core::intrinsics::discriminant_value(other);
+        __self_discr == __arg1_discr
+    }
+}
+#[automatically_derived]
+impl ::core::cmp::Eq for RpcInterfaceMethod {
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn assert_fields_are_eq(&self) {}
+}
+#[automatically_derived]
+impl ::core::hash::Hash for RpcInterfaceMethod {
+    #[inline]
+    fn hash<__H: ::core::hash::Hasher>(&self, state: &mut __H) {
+        let __self_discr = ::core::intrinsics::discriminant_value(self);
+        ::core::hash::Hash::hash(&__self_discr, state)
+    }
+}
+impl RpcInterfaceMethod {
+    pub const ALL: &'static [RpcInterfaceMethod] = &[
+        RpcInterfaceMethod::AddOne,
+        RpcInterfaceMethod::Add,
+        RpcInterfaceMethod::PrefixWithBar,
+        RpcInterfaceMethod::GetToto,
+        RpcInterfaceMethod::SetTata,
+    ];
+    pub fn name(&self) -> &'static str {
+        match *self {
+            RpcInterfaceMethod::AddOne => "add_one",
+            RpcInterfaceMethod::Add => "add",
+            RpcInterfaceMethod::PrefixWithBar => "prefix_with_bar",
+            RpcInterfaceMethod::GetToto => "get_toto",
+            RpcInterfaceMethod::SetTata => "set_tata",
+        }
+    }
+}
+impl std::fmt::Display for RpcInterfaceMethod {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+impl RpcInterfaceMessage {
+    pub const METHODS: &'static [&'static str] = &[
+        "add_one",
+        "add",
+        "prefix_with_bar",
+        "get_toto",
+        "set_tata",
+    ];
+    pub fn method(&self) -> RpcInterfaceMethod {
+        match *self {
+            RpcInterfaceMessage::AddOne(_) => RpcInterfaceMethod::AddOne,
+            RpcInterfaceMessage::Add(_) => RpcInterfaceMethod::Add,
+            RpcInterfaceMessage::PrefixWithBar(_) => RpcInterfaceMethod::PrefixWithBar,
+            RpcInterfaceMessage::GetToto(_) => RpcInterfaceMethod::GetToto,
+            RpcInterfaceMessage::SetTata(_) => RpcInterfaceMethod::SetTata,
+        }
+    }
+    pub fn method_name(&self) -> &'static str {
+        self.method().name()
+    }
+}
+impl std::fmt::Display for RpcInterfaceMessage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            RpcInterfaceMessage::AddOne(ref req) => {
+                f.write_fmt(format_args!("{0}({1:?})", "add_one", req.content))
+            }
+            RpcInterfaceMessage::Add(ref req) => {
+                let (arg0, arg1) = &req.content;
+                f.write_fmt(format_args!("{0}({1:?}, {2:?})", "add", arg0, arg1))
+            }
+            RpcInterfaceMessage::PrefixWithBar(ref req) => {
+                f.write_fmt(format_args!("{0}({1:?})", "prefix_with_bar", req.content))
+            }
+            RpcInterfaceMessage::GetToto(_) => {
+                f.write_fmt(format_args!("{0}()", "get_toto"))
+            }
+            RpcInterfaceMessage::SetTata(ref req) => {
+                f.write_fmt(format_args!("{0}({1:?})", "set_tata", req.content))
+            }
+        }
+    }
+}
 pub struct RpcInterfaceClient {
     #[publisher(RpcInterfaceMessage)]
     pub publisher: Box<
@@ -32,6 +158,15 @@ impl async_pub_sub::Publisher for RpcInterfaceClient {
     ) -> async_pub_sub::futures::future::BoxFuture<async_pub_sub::Result<()>> {
         async_pub_sub::Publisher::publish(&self.publisher, message)
     }
+    fn close(&self) {
+        async_pub_sub::Publisher::close(&self.publisher)
+    }
+    fn is_closed(&self) -> bool {
+        async_pub_sub::Publisher::is_closed(&self.publisher)
+    }
+    fn closed(&self) -> async_pub_sub::futures::future::BoxFuture<'_, ()> {
+        async_pub_sub::Publisher::closed(&self.publisher)
+    }
     fn get_message_stream(
         &mut self,
         subscriber_name: &'static str,
@@ -149,32 +284,55 @@ pub trait RpcInterfaceServer: async_pub_sub::SubscriberWrapper<
             self.handle_request(request).await;
         }
     }
+    async fn run_until(&mut self, shutdown: async_pub_sub::ShutdownSignal) {
+        let mut cancelled = shutdown.cancelled();
+        loop {
+            let request = {
+                let receive = self.receive();
+                let mut receive = receive;
+                #[allow(unused_mut)]
+                let mut receive = unsafe {
+                    ::pin_utils::core_reexport::pin::Pin::new_unchecked(&mut receive)
+                };
+                match async_pub_sub::futures::future::select(receive, &mut cancelled)
+                    .await
+                {
+                    async_pub_sub::futures::future::Either::Left((request, _)) => request,
+                    async_pub_sub::futures::future::Either::Right(_) => break,
+                }
+            };
+            self.handle_request(request).await;
+        }
+        while let Some(request) = self.try_receive() {
+            self.handle_request(request).await;
+        }
+    }
     async fn handle_request(&mut self, request: RpcInterfaceMessage) {
         match request {
             RpcInterfaceMessage::AddOne(req) => {
-                let async_pub_sub::Request { content, response_sender } = req;
+                let async_pub_sub::Request { content, response_sender, .. } = req;
                 let response = <Self as RpcInterface>::add_one(self, content).await;
                 response_sender.send(response).expect("failed to send response");
             }
             RpcInterfaceMessage::Add(req) => {
-                let async_pub_sub::Request { content, response_sender } = req;
+                let async_pub_sub::Request { content, response_sender, .. } = req;
                 let (left, right) = content;
                 let response = <Self as RpcInterface>::add(self, left, right).await;
                 response_sender.send(response).expect("failed to send response");
             }
             RpcInterfaceMessage::PrefixWithBar(req) => {
-                let async_pub_sub::Request { content, response_sender } = req;
+                let async_pub_sub::Request { content, response_sender, .. } = req;
                 let response = <Self as RpcInterface>::prefix_with_bar(self, content)
                     .await;
                 response_sender.send(response).expect("failed to send response");
             }
             RpcInterfaceMessage::GetToto(req) => {
-                let async_pub_sub::Request { content: _, response_sender } = req;
+                let async_pub_sub::Request { content: _, response_sender, .. } = req;
                 let response = <Self as RpcInterface>::get_toto(self).await;
                 response_sender.send(response).expect("failed to send response");
             }
             RpcInterfaceMessage::SetTata(req) => {
-                let async_pub_sub::Request { content, response_sender } = req;
+                let async_pub_sub::Request { content, response_sender, .. } = req;
                 let response = <Self as RpcInterface>::set_tata(self, content).await;
                 response_sender.send(response).expect("failed to send response");
             }
@@ -0,0 +1,234 @@
+use async_pub_sub_macros::{MergeSubscriber, SplitPublisher};
+pub enum Event {
+    Measure(f64),
+    SensorError(String),
+    Heartbeat,
+}
+///Splits a stream of [`Event`] messages into one publisher per variant, carrying the payload of the variant.
+pub struct EventSplitter {
+    __name: &'static str,
+    __subscriber: async_pub_sub::SubscriberImpl<Event>,
+    ///Publishes the payloads of the `Measure` variant
+    pub measure: async_pub_sub::RouterOutput<f64>,
+    ///Publishes the payloads of the `SensorError` variant
+    pub sensor_error: async_pub_sub::RouterOutput<String>,
+    ///Publishes the payloads of the `Heartbeat` variant
+    pub heartbeat: async_pub_sub::RouterOutput<()>,
+}
+impl EventSplitter {
+    /// Creates a new splitter, each variant publisher having its own buffer and
+    /// being named after the splitter and the variant, like `name::Variant`.
+    pub fn new(name: &'static str, buffer_size: usize) -> Self {
+        Self {
+            __name: name,
+            __subscriber: async_pub_sub::SubscriberImpl::new(name),
+            measure: async_pub_sub::RouterOutput::new(
+                Box::leak(
+                    ::alloc::__export::must_use({
+                            ::alloc::fmt::format(format_args!("{0}::Measure", name))
+                        })
+                        .into_boxed_str(),
+                ),
+                buffer_size,
+            ),
+            sensor_error: async_pub_sub::RouterOutput::new(
+                Box::leak(
+                    ::alloc::__export::must_use({
+                            ::alloc::fmt::format(format_args!("{0}::SensorError", name))
+                        })
+                        .into_boxed_str(),
+                ),
+                buffer_size,
+            ),
+            heartbeat: async_pub_sub::RouterOutput::new(
+                Box::leak(
+                    ::alloc::__export::must_use({
+                            ::alloc::fmt::format(format_args!("{0}::Heartbeat", name))
+                        })
+                        .into_boxed_str(),
+                ),
+                buffer_size,
+            ),
+        }
+    }
+    /// Publishes the payloads of the received messages on the publisher of their
+    /// variant, until all the publishers the splitter subscribed to are closed or
+    /// dropped. The variant publishers are then closed.
+    pub async fn run(&mut self) {
+        while let Some(message) = self.__subscriber.next_message().await {
+            match message {
+                Event::Measure(payload) => {
+                    self.measure.forward(self.__name, payload).await
+                }
+                Event::SensorError(payload) => {
+                    self.sensor_error.forward(self.__name, payload).await
+                }
+                Event::Heartbeat => self.heartbeat.forward(self.__name, ()).await,
+            }
+        }
+        self.measure.close();
+        self.sensor_error.close();
+        self.heartbeat.close();
+    }
+}
+impl async_pub_sub::Subscriber for EventSplitter {
+    type Message = Event;
+    fn get_name(&self) -> &'static str {
+        self.__name
+    }
+    fn subscribe_to(
+        &mut self,
+        publisher: &mut dyn async_pub_sub::Publisher<Message = Self::Message>,
+    ) -> async_pub_sub::Result<()> {
+        self.__subscriber.subscribe_to(publisher)
+    }
+    fn receive(
+        &mut self,
+    ) -> async_pub_sub::futures::future::BoxFuture<'_, Self::Message> {
+        {
+            ::core::panicking::panic_fmt(
+                format_args!(
+                    "EventSplitter does not implement receive method, use run instead",
+                ),
+            );
+        }
+    }
+}
+///Merges the payloads received for each variant back into a stream of [`Event`] messages.
+pub struct EventMerger {
+    __name: &'static str,
+    __subscriber_name: Option<&'static str>,
+    ///Receives the payloads of the `Measure` variant
+    pub measure: async_pub_sub::SubscriberImpl<f64>,
+    ///Receives the payloads of the `SensorError` variant
+    pub sensor_error: async_pub_sub::SubscriberImpl<String>,
+    ///Receives the payloads of the `Heartbeat` variant
+    pub heartbeat: async_pub_sub::SubscriberImpl<()>,
+}
+impl EventMerger {
+    /// Creates a new merger, each variant subscriber being named after the merger
+    /// and the variant, like `name::Variant`.
+    pub fn new(name: &'static str) -> Self {
+        Self {
+            __name: name,
+            __subscriber_name: None,
+            measure: async_pub_sub::SubscriberImpl::new(
+                Box::leak(
+                    ::alloc::__export::must_use({
+                            ::alloc::fmt::format(format_args!("{0}::Measure", name))
+                        })
+                        .into_boxed_str(),
+                ),
+            ),
+            sensor_error: async_pub_sub::SubscriberImpl::new(
+                Box::leak(
+                    ::alloc::__export::must_use({
+                            ::alloc::fmt::format(format_args!("{0}::SensorError", name))
+                        })
+                        .into_boxed_str(),
+                ),
+            ),
+            heartbeat: async_pub_sub::SubscriberImpl::new(
+                Box::leak(
+                    ::alloc::__export::must_use({
+                            ::alloc::fmt::format(format_args!("{0}::Heartbeat", name))
+                        })
+                        .into_boxed_str(),
+                ),
+            ),
+        }
+    }
+}
+impl async_pub_sub::Publisher for EventMerger {
+    type Message = Event;
+    fn get_name(&self) -> &'static str {
+        self.__name
+    }
+    fn publish(
+        &self,
+        _message: Self::Message,
+    ) -> async_pub_sub::futures::future::BoxFuture<'_, async_pub_sub::Result<()>> {
+        {
+            ::core::panicking::panic_fmt(
+                format_args!("EventMerger does not implement publish method"),
+            );
+        }
+    }
+    fn get_message_stream(
+        &mut self,
+        subscriber_name: &'static str,
+    ) -> async_pub_sub::Result<
+        std::pin::Pin<
+            Box<
+                dyn async_pub_sub::futures::Stream<
+                    Item = Self::Message,
+                > + Send + Sync + 'static,
+            >,
+        >,
+    > {
+        if let Some(bound_subscriber_name) = self.__subscriber_name {
+            return Err(
+                ::alloc::__export::must_use({
+                        ::alloc::fmt::format(
+                            format_args!(
+                                "{0} merger can only be bound to one subscriber (already bound to {1})",
+                                self.__name, bound_subscriber_name,
+                            ),
+                        )
+                    })
+                    .into(),
+            );
+        }
+        self.__subscriber_name = Some(subscriber_name);
+        let streams: Vec<
+            std::pin::Pin<
+                Box<
+                    dyn async_pub_sub::futures::Stream<
+                        Item = Self::Message,
+                    > + Send + Sync + 'static,
+                >,
+            >,
+        > = ::alloc::boxed::box_assume_init_into_vec_unsafe(
+            ::alloc::intrinsics::write_box_via_move(
+                ::alloc::boxed::Box::new_uninit(),
+                [
+                    Box::pin(
+                        async_pub_sub::futures::StreamExt::map(
+                            std::mem::replace(
+                                    &mut self.measure,
+                                    async_pub_sub::SubscriberImpl::new(self.__name),
+                                )
+                                .into_stream(),
+                            Event::Measure,
+                        ),
+                    ),
+                    Box::pin(
+                        async_pub_sub::futures::StreamExt::map(
+                            std::mem::replace(
+                                    &mut self.sensor_error,
+                                    async_pub_sub::SubscriberImpl::new(self.__name),
+                                )
+                                .into_stream(),
+                            Event::SensorError,
+                        ),
+                    ),
+                    Box::pin(
+                        async_pub_sub::futures::StreamExt::map(
+                            std::mem::replace(
+                                    &mut self.heartbeat,
+                                    async_pub_sub::SubscriberImpl::new(self.__name),
+                                )
+                                .into_stream(),
+                            |_| Event::Heartbeat,
+                        ),
+                    ),
+                ],
+            ),
+        );
+        Ok(Box::pin(async_pub_sub::futures::stream::select_all(streams)))
+    }
+}
+fn main() {
+    let _splitter = EventSplitter::new("splitter", 1);
+    let _merger = EventMerger::new("merger");
+}
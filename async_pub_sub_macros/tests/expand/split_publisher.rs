@@ -0,0 +1,13 @@
+use async_pub_sub_macros::{MergeSubscriber, SplitPublisher};
+
+#[derive(SplitPublisher, MergeSubscriber)]
+pub enum Event {
+    Measure(f64),
+    SensorError(String),
+    Heartbeat,
+}
+
+fn main() {
+    let _splitter = EventSplitter::new("splitter", 1);
+    let _merger = EventMerger::new("merger");
+}
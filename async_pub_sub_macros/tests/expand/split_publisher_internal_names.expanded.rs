@@ -0,0 +1,242 @@
+use async_pub_sub_macros::{MergeSubscriber, SplitPublisher};
+pub enum Registration {
+    Name(String),
+    Subscriber(u32),
+    SubscriberName,
+}
+///Splits a stream of [`Registration`] messages into one publisher per variant, carrying the payload of the variant.
+pub struct RegistrationSplitter {
+    __name: &'static str,
+    __subscriber: async_pub_sub::SubscriberImpl<Registration>,
+    ///Publishes the payloads of the `Name` variant
+    pub name: async_pub_sub::RouterOutput<String>,
+    ///Publishes the payloads of the `Subscriber` variant
+    pub subscriber: async_pub_sub::RouterOutput<u32>,
+    ///Publishes the payloads of the `SubscriberName` variant
+    pub subscriber_name: async_pub_sub::RouterOutput<()>,
+}
+impl RegistrationSplitter {
+    /// Creates a new splitter, each variant publisher having its own buffer and
+    /// being named after the splitter and the variant, like `name::Variant`.
+    pub fn new(name: &'static str, buffer_size: usize) -> Self {
+        Self {
+            __name: name,
+            __subscriber: async_pub_sub::SubscriberImpl::new(name),
+            name: async_pub_sub::RouterOutput::new(
+                Box::leak(
+                    ::alloc::__export::must_use({
+                            ::alloc::fmt::format(format_args!("{0}::Name", name))
+                        })
+                        .into_boxed_str(),
+                ),
+                buffer_size,
+            ),
+            subscriber: async_pub_sub::RouterOutput::new(
+                Box::leak(
+                    ::alloc::__export::must_use({
+                            ::alloc::fmt::format(format_args!("{0}::Subscriber", name))
+                        })
+                        .into_boxed_str(),
+                ),
+                buffer_size,
+            ),
+            subscriber_name: async_pub_sub::RouterOutput::new(
+                Box::leak(
+                    ::alloc::__export::must_use({
+                            ::alloc::fmt::format(
+                                format_args!("{0}::SubscriberName", name),
+                            )
+                        })
+                        .into_boxed_str(),
+                ),
+                buffer_size,
+            ),
+        }
+    }
+    /// Publishes the payloads of the received messages on the publisher of their
+    /// variant, until all the publishers the splitter subscribed to are closed or
+    /// dropped. The variant publishers are then closed.
+    pub async fn run(&mut self) {
+        while let Some(message) = self.__subscriber.next_message().await {
+            match message {
+                Registration::Name(payload) => {
+                    self.name.forward(self.__name, payload).await
+                }
+                Registration::Subscriber(payload) => {
+                    self.subscriber.forward(self.__name, payload).await
+                }
+                Registration::SubscriberName => {
+                    self.subscriber_name.forward(self.__name, ()).await
+                }
+            }
+        }
+        self.name.close();
+        self.subscriber.close();
+        self.subscriber_name.close();
+    }
+}
+impl async_pub_sub::Subscriber for RegistrationSplitter {
+    type Message = Registration;
+    fn get_name(&self) -> &'static str {
+        self.__name
+    }
+    fn subscribe_to(
+        &mut self,
+        publisher: &mut dyn async_pub_sub::Publisher<Message = Self::Message>,
+    ) -> async_pub_sub::Result<()> {
+        self.__subscriber.subscribe_to(publisher)
+    }
+    fn receive(
+        &mut self,
+    ) -> async_pub_sub::futures::future::BoxFuture<'_, Self::Message> {
+        {
+            ::core::panicking::panic_fmt(
+                format_args!(
+                    "RegistrationSplitter does not implement receive method, use run instead",
+                ),
+            );
+        }
+    }
+}
+///Merges the payloads received for each variant back into a stream of [`Registration`] messages.
+pub struct RegistrationMerger {
+    __name: &'static str,
+    __subscriber_name: Option<&'static str>,
+    ///Receives the payloads of the `Name` variant
+    pub name: async_pub_sub::SubscriberImpl<String>,
+    ///Receives the payloads of the `Subscriber` variant
+    pub subscriber: async_pub_sub::SubscriberImpl<u32>,
+    ///Receives the payloads of the `SubscriberName` variant
+    pub subscriber_name: async_pub_sub::SubscriberImpl<()>,
+}
+impl RegistrationMerger {
+    /// Creates a new merger, each variant subscriber being named after the merger
+    /// and the variant, like `name::Variant`.
+    pub fn new(name: &'static str) -> Self {
+        Self {
+            __name: name,
+            __subscriber_name: None,
+            name: async_pub_sub::SubscriberImpl::new(
+                Box::leak(
+                    ::alloc::__export::must_use({
+                            ::alloc::fmt::format(format_args!("{0}::Name", name))
+                        })
+                        .into_boxed_str(),
+                ),
+            ),
+            subscriber: async_pub_sub::SubscriberImpl::new(
+                Box::leak(
+                    ::alloc::__export::must_use({
+                            ::alloc::fmt::format(format_args!("{0}::Subscriber", name))
+                        })
+                        .into_boxed_str(),
+                ),
+            ),
+            subscriber_name: async_pub_sub::SubscriberImpl::new(
+                Box::leak(
+                    ::alloc::__export::must_use({
+                            ::alloc::fmt::format(
+                                format_args!("{0}::SubscriberName", name),
+                            )
+                        })
+                        .into_boxed_str(),
+                ),
+            ),
+        }
+    }
+}
+impl async_pub_sub::Publisher for RegistrationMerger {
+    type Message = Registration;
+    fn get_name(&self) -> &'static str {
+        self.__name
+    }
+    fn publish(
+        &self,
+        _message: Self::Message,
+    ) -> async_pub_sub::futures::future::BoxFuture<'_, async_pub_sub::Result<()>> {
+        {
+            ::core::panicking::panic_fmt(
+                format_args!("RegistrationMerger does not implement publish method"),
+            );
+        }
+    }
+    fn get_message_stream(
+        &mut self,
+        subscriber_name: &'static str,
+    ) -> async_pub_sub::Result<
+        std::pin::Pin<
+            Box<
+                dyn async_pub_sub::futures::Stream<
+                    Item = Self::Message,
+                > + Send + Sync + 'static,
+            >,
+        >,
+    > {
+        if let Some(bound_subscriber_name) = self.__subscriber_name {
+            return Err(
+                ::alloc::__export::must_use({
+                        ::alloc::fmt::format(
+                            format_args!(
+                                "{0} merger can only be bound to one subscriber (already bound to {1})",
+                                self.__name, bound_subscriber_name,
+                            ),
+                        )
+                    })
+                    .into(),
+            );
+        }
+        self.__subscriber_name = Some(subscriber_name);
+        let streams: Vec<
+            std::pin::Pin<
+                Box<
+                    dyn async_pub_sub::futures::Stream<
+                        Item = Self::Message,
+                    > + Send + Sync + 'static,
+                >,
+            >,
+        > = ::alloc::boxed::box_assume_init_into_vec_unsafe(
+            ::alloc::intrinsics::write_box_via_move(
+                ::alloc::boxed::Box::new_uninit(),
+                [
+                    Box::pin(
+                        async_pub_sub::futures::StreamExt::map(
+                            std::mem::replace(
+                                    &mut self.name,
+                                    async_pub_sub::SubscriberImpl::new(self.__name),
+                                )
+                                .into_stream(),
+                            Registration::Name,
+                        ),
+                    ),
+                    Box::pin(
+                        async_pub_sub::futures::StreamExt::map(
+                            std::mem::replace(
+                                    &mut self.subscriber,
+                                    async_pub_sub::SubscriberImpl::new(self.__name),
+                                )
+                                .into_stream(),
+                            Registration::Subscriber,
+                        ),
+                    ),
+                    Box::pin(
+                        async_pub_sub::futures::StreamExt::map(
+                            std::mem::replace(
+                                    &mut self.subscriber_name,
+                                    async_pub_sub::SubscriberImpl::new(self.__name),
+                                )
+                                .into_stream(),
+                            |_| Registration::SubscriberName,
+                        ),
+                    ),
+                ],
+            ),
+        );
+        Ok(Box::pin(async_pub_sub::futures::stream::select_all(streams)))
+    }
+}
+fn main() {
+    let splitter = RegistrationSplitter::new("splitter", 1);
+    let _outputs = (splitter.name, splitter.subscriber, splitter.subscriber_name);
+    let merger = RegistrationMerger::new("merger");
+    let _inputs = (merger.name, merger.subscriber, merger.subscriber_name);
+}
@@ -0,0 +1,16 @@
+use async_pub_sub_macros::{MergeSubscriber, SplitPublisher};
+
+// the variants are named like the internal fields of the splitter and of the merger
+#[derive(SplitPublisher, MergeSubscriber)]
+pub enum Registration {
+    Name(String),
+    Subscriber(u32),
+    SubscriberName,
+}
+
+fn main() {
+    let splitter = RegistrationSplitter::new("splitter", 1);
+    let _outputs = (splitter.name, splitter.subscriber, splitter.subscriber_name);
+    let merger = RegistrationMerger::new("merger");
+    let _inputs = (merger.name, merger.subscriber, merger.subscriber_name);
+}
@@ -18,6 +18,14 @@ impl<A: Subscriber> async_pub_sub::Subscriber for TestSubscriberA<A> {
     fn receive(&mut self) -> async_pub_sub::futures::future::BoxFuture<Self::Message> {
         async_pub_sub::Subscriber::receive(&mut self.subscriber_a)
     }
+    fn try_receive(&mut self) -> Option<Self::Message> {
+        async_pub_sub::Subscriber::try_receive(&mut self.subscriber_a)
+    }
+    fn next_message(
+        &mut self,
+    ) -> async_pub_sub::futures::future::BoxFuture<Option<Self::Message>> {
+        async_pub_sub::Subscriber::next_message(&mut self.subscriber_a)
+    }
 }
 struct TestSubscriberB<B>
 where
@@ -42,5 +50,13 @@ where
     fn receive(&mut self) -> async_pub_sub::futures::future::BoxFuture<Self::Message> {
         async_pub_sub::Subscriber::receive(&mut self.subscriber_b)
     }
+    fn try_receive(&mut self) -> Option<Self::Message> {
+        async_pub_sub::Subscriber::try_receive(&mut self.subscriber_b)
+    }
+    fn next_message(
+        &mut self,
+    ) -> async_pub_sub::futures::future::BoxFuture<Option<Self::Message>> {
+        async_pub_sub::Subscriber::next_message(&mut self.subscriber_b)
+    }
 }
 fn main() {}
@@ -0,0 +1,184 @@
+#![allow(unused_imports, dead_code)]
+use async_pub_sub::{PublisherImpl, SubscriberImpl};
+use async_pub_sub_macros::{DerivePublisher, DeriveSubscriber, topology};
+struct Timer {
+    #[publisher(u64)]
+    cache_publisher: PublisherImpl<u64>,
+    #[publisher(String)]
+    logger_publisher: PublisherImpl<String>,
+}
+impl async_pub_sub::PublisherWrapper<u64> for Timer {
+    fn get_publisher(&self) -> &dyn async_pub_sub::Publisher<Message = u64> {
+        &self.cache_publisher
+    }
+    fn get_publisher_mut(&mut self) -> &mut dyn async_pub_sub::Publisher<Message = u64> {
+        &mut self.cache_publisher
+    }
+}
+impl async_pub_sub::PublisherWrapper<String> for Timer {
+    fn get_publisher(&self) -> &dyn async_pub_sub::Publisher<Message = String> {
+        &self.logger_publisher
+    }
+    fn get_publisher_mut(
+        &mut self,
+    ) -> &mut dyn async_pub_sub::Publisher<Message = String> {
+        &mut self.logger_publisher
+    }
+}
+impl Timer {
+    async fn run(self) {}
+}
+struct Cache {
+    #[subscriber(u64)]
+    subscriber: SubscriberImpl<u64>,
+}
+impl async_pub_sub::Subscriber for Cache {
+    type Message = u64;
+    fn get_name(&self) -> &'static str {
+        async_pub_sub::Subscriber::get_name(&self.subscriber)
+    }
+    fn subscribe_to(
+        &mut self,
+        publisher: &mut dyn async_pub_sub::Publisher<Message = Self::Message>,
+    ) -> async_pub_sub::Result<()> {
+        async_pub_sub::Subscriber::subscribe_to(&mut self.subscriber, publisher)
+    }
+    fn receive(&mut self) -> async_pub_sub::futures::future::BoxFuture<Self::Message> {
+        async_pub_sub::Subscriber::receive(&mut self.subscriber)
+    }
+    fn try_receive(&mut self) -> Option<Self::Message> {
+        async_pub_sub::Subscriber::try_receive(&mut self.subscriber)
+    }
+    fn next_message(
+        &mut self,
+    ) -> async_pub_sub::futures::future::BoxFuture<Option<Self::Message>> {
+        async_pub_sub::Subscriber::next_message(&mut self.subscriber)
+    }
+}
+impl Cache {
+    async fn run(self) {}
+}
+struct Logger {
+    #[subscriber(String)]
+    subscriber: SubscriberImpl<String>,
+}
+impl async_pub_sub::Subscriber for Logger {
+    type Message = String;
+    fn get_name(&self) -> &'static str {
+        async_pub_sub::Subscriber::get_name(&self.subscriber)
+    }
+    fn subscribe_to(
+        &mut self,
+        publisher: &mut dyn async_pub_sub::Publisher<Message = Self::Message>,
+    ) -> async_pub_sub::Result<()> {
+        async_pub_sub::Subscriber::subscribe_to(&mut self.subscriber, publisher)
+    }
+    fn receive(&mut self) -> async_pub_sub::futures::future::BoxFuture<Self::Message> {
+        async_pub_sub::Subscriber::receive(&mut self.subscriber)
+    }
+    fn try_receive(&mut self) -> Option<Self::Message> {
+        async_pub_sub::Subscriber::try_receive(&mut self.subscriber)
+    }
+    fn next_message(
+        &mut self,
+    ) -> async_pub_sub::futures::future::BoxFuture<Option<Self::Message>> {
+        async_pub_sub::Subscriber::next_message(&mut self.subscriber)
+    }
+}
+impl Logger {
+    async fn run(self) {}
+}
+/// The services of the application.
+pub(crate) struct App {
+    pub(crate) timer: Timer,
+    pub(crate) cache: Cache,
+    pub(crate) logger: Logger,
+}
+impl App {
+    /// Creates all the services and connects them according to the topology.
+    pub(crate) fn new() -> async_pub_sub::Result<Self> {
+        let mut timer = Timer {
+            cache_publisher: PublisherImpl::new("timer", 1),
+            logger_publisher: PublisherImpl::new("timer", 1),
+        };
+        let mut cache = Cache {
+            subscriber: SubscriberImpl::new("cache"),
+        };
+        let mut logger = Logger {
+            subscriber: SubscriberImpl::new("logger"),
+        };
+        async_pub_sub::SubscriberWrapper::<
+            u64,
+        >::subscribe_to(
+            &mut cache,
+            async_pub_sub::PublisherWrapper::<u64>::get_publisher_mut(&mut timer),
+        )?;
+        async_pub_sub::SubscriberWrapper::<
+            String,
+        >::subscribe_to(
+            &mut logger,
+            async_pub_sub::PublisherWrapper::<String>::get_publisher_mut(&mut timer),
+        )?;
+        Ok(Self { timer, cache, logger })
+    }
+    /// Runs all the services concurrently, until all of them return.
+    pub(crate) async fn run(self) {
+        {
+            use ::futures_util::__private as __futures_crate;
+            {
+                let mut _fut0 = __futures_crate::future::maybe_done(self.timer.run());
+                let mut _fut0 = unsafe {
+                    __futures_crate::Pin::new_unchecked(&mut _fut0)
+                };
+                let mut _fut1 = __futures_crate::future::maybe_done(self.cache.run());
+                let mut _fut1 = unsafe {
+                    __futures_crate::Pin::new_unchecked(&mut _fut1)
+                };
+                let mut _fut2 = __futures_crate::future::maybe_done(self.logger.run());
+                let mut _fut2 = unsafe {
+                    __futures_crate::Pin::new_unchecked(&mut _fut2)
+                };
+                __futures_crate::future::poll_fn(move |
+                        __cx: &mut __futures_crate::task::Context<'_>|
+                    {
+                        let mut __all_done = true;
+                        __all_done
+                            &= __futures_crate::future::Future::poll(
+                                    _fut0.as_mut(),
+                                    __cx,
+                                )
+                                .is_ready();
+                        __all_done
+                            &= __futures_crate::future::Future::poll(
+                                    _fut1.as_mut(),
+                                    __cx,
+                                )
+                                .is_ready();
+                        __all_done
+                            &= __futures_crate::future::Future::poll(
+                                    _fut2.as_mut(),
+                                    __cx,
+                                )
+                                .is_ready();
+                        if __all_done {
+                            __futures_crate::task::Poll::Ready((
+                                _fut0.as_mut().take_output().unwrap(),
+                                _fut1.as_mut().take_output().unwrap(),
+                                _fut2.as_mut().take_output().unwrap(),
+                            ))
+                        } else {
+                            __futures_crate::task::Poll::Pending
+                        }
+                    })
+                    .await
+            }
+        };
+    }
+}
+const _: () = {
+    #[allow(non_camel_case_types)]
+    trait timer_is_bound_once_per_message_type {}
+    impl timer_is_bound_once_per_message_type for u64 {}
+    impl timer_is_bound_once_per_message_type for String {}
+};
+fn main() {}
@@ -0,0 +1,53 @@
+#![allow(unused_imports, dead_code)]
+use async_pub_sub::{PublisherImpl, SubscriberImpl};
+use async_pub_sub_macros::{DerivePublisher, DeriveSubscriber, topology};
+
+#[derive(DerivePublisher)]
+struct Timer {
+    #[publisher(u64)]
+    cache_publisher: PublisherImpl<u64>,
+    #[publisher(String)]
+    logger_publisher: PublisherImpl<String>,
+}
+
+impl Timer {
+    async fn run(self) {}
+}
+
+#[derive(DeriveSubscriber)]
+struct Cache {
+    #[subscriber(u64)]
+    subscriber: SubscriberImpl<u64>,
+}
+
+impl Cache {
+    async fn run(self) {}
+}
+
+#[derive(DeriveSubscriber)]
+struct Logger {
+    #[subscriber(String)]
+    subscriber: SubscriberImpl<String>,
+}
+
+impl Logger {
+    async fn run(self) {}
+}
+
+topology! {
+    /// The services of the application.
+    pub(crate) struct App {
+        timer: Timer = Timer {
+            cache_publisher: PublisherImpl::new("timer", 1),
+            logger_publisher: PublisherImpl::new("timer", 1),
+        },
+        #[subscriber]
+        cache: Cache = Cache { subscriber: SubscriberImpl::new("cache") },
+        logger: Logger = Logger { subscriber: SubscriberImpl::new("logger") },
+    }
+
+    timer -> cache: u64,
+    timer -> logger: String,
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use async_pub_sub_macros::MergeSubscriber;
+
+#[derive(MergeSubscriber)]
+struct Event {
+    value: f64,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use async_pub_sub::{ForwarderImpl, PublisherImpl, SubscriberImpl};
+use async_pub_sub_macros::routes;
+
+fn main() {
+    let mut producer = PublisherImpl::<i32>::new("producer", 1);
+    let mut forwarder = ForwarderImpl::<i32>::new("forwarder");
+    let mut consumer = SubscriberImpl::<String>::new("consumer");
+    let mut logger = SubscriberImpl::<i32>::new("logger");
+
+    routes!(producer -> forwarder -> [logger, consumer]).unwrap();
+}
@@ -0,0 +1,10 @@
+use async_pub_sub::{PublisherImpl, SubscriberImpl};
+use async_pub_sub_macros::routes;
+
+fn main() {
+    let mut producer = PublisherImpl::<i32>::new("producer", 1);
+    let mut consumer = SubscriberImpl::<i32>::new("consumer");
+
+    routes!(producer -> -> consumer).unwrap();
+    routes!(producer -> []).unwrap();
+}
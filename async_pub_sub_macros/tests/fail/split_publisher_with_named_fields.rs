@@ -0,0 +1,8 @@
+use async_pub_sub_macros::SplitPublisher;
+
+#[derive(SplitPublisher)]
+enum Event {
+    Measure { sensor: u32, value: f64 },
+}
+
+fn main() {}
@@ -0,0 +1,17 @@
+use async_pub_sub_macros::topology;
+
+topology! {
+    struct App {
+        timer: async_pub_sub::PublisherImpl<u64> = async_pub_sub::PublisherImpl::new("timer", 1),
+        clock: async_pub_sub::PublisherImpl<u64> = async_pub_sub::PublisherImpl::new("clock", 1),
+        cache: async_pub_sub::SubscriberImpl<u64> = async_pub_sub::SubscriberImpl::new("cache"),
+        persistency: async_pub_sub::SubscriberImpl<u64> = async_pub_sub::SubscriberImpl::new("persistency"),
+    }
+
+    timer -> cache: u64,
+    timer -> persistency: u64,
+    clock -> cache,
+    clock -> persistency,
+}
+
+fn main() {}
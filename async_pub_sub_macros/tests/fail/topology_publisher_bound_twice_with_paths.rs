@@ -0,0 +1,40 @@
+use async_pub_sub::{PublisherImpl, SubscriberImpl};
+use async_pub_sub_macros::{DerivePublisher, DeriveSubscriber, topology};
+
+mod messages {
+    pub type Tick = u64;
+}
+
+#[derive(DerivePublisher)]
+struct Timer {
+    #[publisher(u64)]
+    publisher: PublisherImpl<u64>,
+}
+
+impl Timer {
+    async fn run(self) {}
+}
+
+#[derive(DeriveSubscriber)]
+struct Cache {
+    #[subscriber(u64)]
+    subscriber: SubscriberImpl<u64>,
+}
+
+impl Cache {
+    async fn run(self) {}
+}
+
+// the same type written differently is not caught when the macro expands, but by the compiler
+topology! {
+    struct App {
+        timer: Timer = Timer { publisher: PublisherImpl::new("timer", 1) },
+        cache: Cache = Cache { subscriber: SubscriberImpl::new("cache") },
+        persistency: Cache = Cache { subscriber: SubscriberImpl::new("persistency") },
+    }
+
+    timer -> cache: u64,
+    timer -> persistency: messages::Tick,
+}
+
+fn main() {}
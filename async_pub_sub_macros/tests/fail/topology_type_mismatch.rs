@@ -0,0 +1,33 @@
+use async_pub_sub::{PublisherImpl, SubscriberImpl};
+use async_pub_sub_macros::{DerivePublisher, DeriveSubscriber, topology};
+
+#[derive(DerivePublisher)]
+struct Timer {
+    #[publisher(u64)]
+    publisher: PublisherImpl<u64>,
+}
+
+impl Timer {
+    async fn run(self) {}
+}
+
+#[derive(DeriveSubscriber)]
+struct Cache {
+    #[subscriber(String)]
+    subscriber: SubscriberImpl<String>,
+}
+
+impl Cache {
+    async fn run(self) {}
+}
+
+topology! {
+    struct App {
+        timer: Timer = Timer { publisher: PublisherImpl::new("timer", 1) },
+        cache: Cache = Cache { subscriber: SubscriberImpl::new("cache") },
+    }
+
+    timer -> cache: u64,
+}
+
+fn main() {}
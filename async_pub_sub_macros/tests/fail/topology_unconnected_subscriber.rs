@@ -0,0 +1,15 @@
+use async_pub_sub_macros::topology;
+
+topology! {
+    struct App {
+        timer: async_pub_sub::PublisherImpl<u64> = async_pub_sub::PublisherImpl::new("timer", 1),
+        #[subscriber]
+        cache: async_pub_sub::SubscriberImpl<u64> = async_pub_sub::SubscriberImpl::new("cache"),
+        #[subscriber]
+        persistency: async_pub_sub::SubscriberImpl<u64> = async_pub_sub::SubscriberImpl::new("persistency"),
+    }
+
+    timer -> cache,
+}
+
+fn main() {}
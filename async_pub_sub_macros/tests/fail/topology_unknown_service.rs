@@ -0,0 +1,12 @@
+use async_pub_sub_macros::topology;
+
+topology! {
+    struct App {
+        timer: async_pub_sub::PublisherImpl<u64> = async_pub_sub::PublisherImpl::new("timer", 1),
+        cache: async_pub_sub::SubscriberImpl<u64> = async_pub_sub::SubscriberImpl::new("cache"),
+    }
+
+    timer -> cahce,
+}
+
+fn main() {}
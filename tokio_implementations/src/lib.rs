@@ -1 +1,3 @@
 pub mod publisher;
+pub mod timer;
+pub mod transport;
@@ -1,12 +1,19 @@
-use std::pin::Pin;
+use std::{
+    pin::Pin,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex,
+    },
+};
 
 use futures::{
-    future::BoxFuture,
+    channel::oneshot,
+    future::{BoxFuture, Shared},
     stream::{self},
     FutureExt, Stream,
 };
 
-use async_pub_sub::{Publisher, Result};
+use async_pub_sub::{PublishError, Publisher, Result};
 
 pub struct MpscPublisher<Message>
 where
@@ -14,8 +21,14 @@ where
 {
     name: &'static str,
     subscriber_name: Option<&'static str>,
-    sender: tokio::sync::mpsc::Sender<Message>,
+    /// Dropped when the publisher is closed, so that the subscriber sees the end of the stream
+    sender: Mutex<Option<tokio::sync::mpsc::Sender<Message>>>,
     receiver: Option<tokio::sync::mpsc::Receiver<Message>>,
+    closed: AtomicBool,
+    /// Completes once the subscriber stream has ended or has been dropped
+    drained: Shared<oneshot::Receiver<()>>,
+    /// Moved into the subscriber stream, or dropped on close if no subscriber was bound
+    drained_sender: Mutex<Option<oneshot::Sender<()>>>,
 }
 
 impl<Message> MpscPublisher<Message>
@@ -24,17 +37,59 @@ where
 {
     pub fn new(name: &'static str, buffer_size: usize) -> Self {
         let (sender, receiver) = tokio::sync::mpsc::channel(buffer_size);
+        let (drained_sender, drained) = oneshot::channel();
         Self {
             name,
             subscriber_name: None,
-            sender,
+            sender: Mutex::new(Some(sender)),
             receiver: Some(receiver),
+            closed: AtomicBool::new(false),
+            drained: drained.shared(),
+            drained_sender: Mutex::new(Some(drained_sender)),
         }
     }
 
     pub async fn publish(&self, message: Message) -> Result<()> {
-        self.sender.send(message).await?;
-        Ok(())
+        let sender = self.sender.lock().expect("publisher lock poisoned").clone();
+        let Some(sender) = sender else {
+            let reason = "Failed to send message (err: publisher closed)".to_string();
+            return Err(PublishError::new(self.name, reason, message).into());
+        };
+
+        sender.send(message).await.map_err(|err| {
+            let reason = format!("Failed to send message (err: {err})");
+            PublishError::new(self.name, reason, err.0).into()
+        })
+    }
+
+    /// Closes the publisher: the messages published afterwards are rejected with a
+    /// [`PublishError`], and the subscriber sees the end of the stream once it has received
+    /// the messages already queued.
+    pub fn close(&self) {
+        self.closed.store(true, Ordering::Release);
+        self.sender.lock().expect("publisher lock poisoned").take();
+        // without a subscriber, there is no queue to drain
+        self.drained_sender
+            .lock()
+            .expect("publisher lock poisoned")
+            .take();
+    }
+
+    /// Returns whether the publisher has been closed, or its subscriber dropped.
+    pub fn is_closed(&self) -> bool {
+        self.closed.load(Ordering::Acquire)
+            || self
+                .sender
+                .lock()
+                .expect("publisher lock poisoned")
+                .as_ref()
+                .is_none_or(|sender| sender.is_closed())
+    }
+
+    /// Waits until the subscriber has received all the messages published before the
+    /// publisher was closed, or has been dropped.
+    pub async fn closed(&self) {
+        let _ = self.drained.clone().await;
     }
 
     pub fn get_message_stream(
@@ -53,9 +108,19 @@ where
 
         self.subscriber_name = Some(subscriber_name);
 
+        // the sender is dropped once the stream ends or is dropped, completing `closed`
+        let drained_sender = self
+            .drained_sender
+            .get_mut()
+            .expect("publisher lock poisoned")
+            .take();
+
         Ok(Box::pin(stream::unfold(
-            receiver,
-            |mut receiver| async move { receiver.recv().await.map(|message| (message, receiver)) },
+            (receiver, drained_sender),
+            |(mut receiver, drained_sender)| async move {
+                let message = receiver.recv().await?;
+                Some((message, (receiver, drained_sender)))
+            },
         )))
     }
 
@@ -74,10 +139,22 @@ where
         self.name
     }
 
-    fn publish(&self, message: Message) -> BoxFuture<Result<()>> {
+    fn publish(&self, message: Message) -> BoxFuture<'_, Result<()>> {
         MpscPublisher::publish(self, message).boxed()
     }
 
+    fn close(&self) {
+        MpscPublisher::close(self)
+    }
+
+    fn is_closed(&self) -> bool {
+        MpscPublisher::is_closed(self)
+    }
+
+    fn closed(&self) -> BoxFuture<'_, ()> {
+        MpscPublisher::closed(self).boxed()
+    }
+
     fn get_message_stream(
         &mut self,
         subscriber_name: &'static str,
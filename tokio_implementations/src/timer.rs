@@ -0,0 +1,22 @@
+use std::time::{Duration, Instant};
+
+use futures::{future::BoxFuture, FutureExt};
+
+use async_pub_sub::Timer;
+
+/// A [`Timer`] based on `tokio::time`.
+///
+/// As it relies on tokio's clock, it follows the clock when it is paused in tests, so that
+/// time-based middlewares can be tested deterministically.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct TokioTimer;
+
+impl Timer for TokioTimer {
+    fn now(&self) -> Instant {
+        tokio::time::Instant::now().into_std()
+    }
+
+    fn sleep(&self, duration: Duration) -> BoxFuture<'static, ()> {
+        tokio::time::sleep(duration).boxed()
+    }
+}
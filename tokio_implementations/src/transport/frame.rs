@@ -0,0 +1,52 @@
+use std::pin::Pin;
+
+use bytes::Bytes;
+use futures::{SinkExt, Stream, StreamExt};
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio_util::codec::{FramedRead, FramedWrite, LengthDelimitedCodec};
+
+use async_pub_sub::{Codec, Result};
+
+/// Writes a payload as a single length-delimited frame.
+#[cfg_attr(not(feature = "uds"), allow(dead_code))]
+pub(crate) async fn write_frame<W>(
+    writer: &mut FramedWrite<W, LengthDelimitedCodec>,
+    frame: Bytes,
+) -> Result<()>
+where
+    W: AsyncWrite + Unpin,
+{
+    writer.send(frame).await?;
+    Ok(())
+}
+
+/// Turns a reader into a stream of messages decoded with the given codec.
+///
+/// Frames that cannot be read or decoded are logged and skipped, the stream ends
+/// when the peer closes the connection.
+#[cfg_attr(not(feature = "uds"), allow(dead_code))]
+pub(crate) fn message_stream<R, M, C>(
+    name: &'static str,
+    reader: R,
+    codec: C,
+) -> Pin<Box<dyn Stream<Item = M> + Send + Sync + 'static>>
+where
+    R: AsyncRead + Send + Sync + 'static,
+    M: Send + Sync + 'static,
+    C: Codec<M> + 'static,
+{
+    let frames = FramedRead::new(reader, LengthDelimitedCodec::new());
+
+    Box::pin(frames.filter_map(move |frame| {
+        let message = match frame {
+            Ok(frame) => codec.decode(&frame).map_err(|err| err.to_string()),
+            Err(err) => Err(err.to_string()),
+        };
+
+        let message = message
+            .inspect_err(|err| log::warn!("[{}] dropping invalid frame (err: {})", name, err))
+            .ok();
+
+        futures::future::ready(message)
+    }))
+}
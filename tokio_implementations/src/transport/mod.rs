@@ -0,0 +1,6 @@
+#[cfg(any(feature = "uds", feature = "tcp"))]
+mod frame;
+#[cfg(feature = "tcp")]
+pub mod tcp;
+#[cfg(feature = "uds")]
+pub mod uds;
@@ -0,0 +1,713 @@
+//! TCP transport, bridging publishers and subscribers across hosts.
+//!
+//! Messages are encoded with a [`Codec`], JSON by default, and sent as length-delimited
+//! frames. When connecting, the
+//! publisher performs a handshake announcing the schema id and version of its messages, the
+//! subscriber rejects the connection if either does not match its own.
+//!
+//! The [`TcpPublisher`] reconnects with an exponential backoff when the connection is lost,
+//! and buffers the published messages while disconnected, up to a configurable limit.
+//! While connected, publishing waits for the connection to keep up.
+
+use std::{
+    marker::PhantomData,
+    net::SocketAddr,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    time::Duration,
+};
+
+use bytes::Bytes;
+use futures::{
+    channel::oneshot,
+    future::{BoxFuture, Shared},
+    stream::{self, SelectAll},
+    FutureExt, SinkExt, Stream, StreamExt,
+};
+use serde::{Deserialize, Serialize};
+use tokio::{
+    net::{TcpListener, TcpStream, ToSocketAddrs},
+    sync::mpsc,
+};
+use tokio_util::codec::{Framed, LengthDelimitedCodec};
+
+use async_pub_sub::{Codec, JsonCodec, PublishError, Publisher, Result, Subscriber, Topology};
+
+/// Configuration shared by the TCP publisher and subscriber.
+#[derive(Clone, Debug)]
+pub struct TcpConfig {
+    /// The identifier of the message schema, both ends must use the same id
+    pub schema_id: &'static str,
+    /// The version of the message schema, both ends must use the same version
+    pub schema_version: u32,
+    /// The maximum number of messages buffered by the publisher while disconnected, or by
+    /// the subscriber before they are received
+    pub buffer_size: usize,
+    /// The delay before the first reconnection attempt
+    pub initial_backoff: Duration,
+    /// The maximum delay between two reconnection attempts
+    pub max_backoff: Duration,
+    /// The maximum duration of the handshake, a peer not completing it in time is
+    /// disconnected
+    pub handshake_timeout: Duration,
+}
+
+impl TcpConfig {
+    /// Creates a default configuration for the messages of the given schema.
+    ///
+    /// The schema id is mandatory, so that two ends exchanging different messages cannot
+    /// connect by accident.
+    ///
+    /// # Arguments
+    ///
+    /// * `schema_id` - The identifier of the message schema, like `"sensors.Measure"`
+    pub fn new(schema_id: &'static str) -> Self {
+        Self {
+            schema_id,
+            schema_version: 0,
+            buffer_size: 1024,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(10),
+            handshake_timeout: Duration::from_secs(5),
+        }
+    }
+}
+
+/// The first frame sent by a publisher on a new connection.
+#[derive(Debug, Serialize, Deserialize)]
+struct Handshake {
+    schema_id: String,
+    schema_version: u32,
+}
+
+/// The answer of the subscriber to a handshake.
+#[derive(Debug, Serialize, Deserialize)]
+enum HandshakeReply {
+    Accepted,
+    Rejected { reason: String },
+}
+
+type Connection = Framed<TcpStream, LengthDelimitedCodec>;
+
+/// A publisher sending its messages to a subscriber on another host over TCP.
+///
+/// Messages are handed to a background task owning the connection. While connected,
+/// publishing waits for room in the buffer of [`TcpConfig::buffer_size`] messages, so that a
+/// slow connection slows the publisher down. While disconnected, the messages are buffered
+/// and publishing fails once the buffer is full. A connection rejected by the subscriber
+/// during the handshake is not retried.
+///
+/// Closing the publisher rejects the messages published afterwards, the background task
+/// sending the buffered messages before closing the connection. While disconnected, it stops
+/// reconnecting once the publisher is closed or dropped, the buffered messages being lost.
+///
+/// # Type Parameters
+///
+/// * `Message` - The type of message that can be published, encoded before being sent
+/// * `C` - The codec encoding the messages
+pub struct TcpPublisher<Message, C = JsonCodec> {
+    name: &'static str,
+    /// The sender of the frames to the background task, dropped once closed
+    frames: Mutex<Option<mpsc::Sender<Bytes>>>,
+    /// Dropped once closed, stopping the reconnection attempts of the background task
+    closing: Mutex<Option<oneshot::Sender<()>>>,
+    is_connected: Arc<AtomicBool>,
+    /// Completes once the background task has returned
+    finished: Shared<oneshot::Receiver<()>>,
+    codec: C,
+    _message: PhantomData<fn(Message)>,
+}
+
+impl<Message> TcpPublisher<Message> {
+    /// Creates a new publisher and starts connecting to the given address in the background.
+    ///
+    /// Must be called from within a tokio runtime.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - A static string identifier for the publisher
+    /// * `address` - The address the subscriber listens on
+    /// * `config` - The transport configuration
+    pub fn connect(name: &'static str, address: SocketAddr, config: TcpConfig) -> Self {
+        let (frames, receiver) = mpsc::channel(config.buffer_size.max(1));
+        let (closing, closing_receiver) = oneshot::channel();
+        let is_connected = Arc::new(AtomicBool::new(false));
+        let (finished_sender, finished) = oneshot::channel();
+
+        tokio::spawn(run_publisher_connection(
+            name,
+            address,
+            config,
+            receiver,
+            closing_receiver,
+            is_connected.clone(),
+            finished_sender,
+        ));
+
+        Self {
+            name,
+            frames: Mutex::new(Some(frames)),
+            closing: Mutex::new(Some(closing)),
+            is_connected,
+            finished: finished.shared(),
+            codec: JsonCodec,
+            _message: PhantomData,
+        }
+    }
+}
+
+impl<Message, C> TcpPublisher<Message, C>
+where
+    Message: Send + 'static,
+    C: Codec<Message>,
+{
+    /// Replaces the codec encoding the messages, the subscriber must decode them with the
+    /// same codec.
+    ///
+    /// # Arguments
+    ///
+    /// * `codec` - The codec encoding the messages
+    pub fn with_codec<C2>(self, codec: C2) -> TcpPublisher<Message, C2> {
+        TcpPublisher {
+            name: self.name,
+            frames: self.frames,
+            closing: self.closing,
+            is_connected: self.is_connected,
+            finished: self.finished,
+            codec,
+            _message: PhantomData,
+        }
+    }
+
+    /// Returns whether the publisher is currently connected to the subscriber.
+    pub fn is_connected(&self) -> bool {
+        self.is_connected.load(Ordering::SeqCst)
+    }
+
+    /// Encodes a message and queues it for sending.
+    ///
+    /// # Arguments
+    ///
+    /// * `message` - The message to publish
+    ///
+    /// # Returns
+    ///
+    /// A [`PublishError`] carrying the message if the buffer is full while disconnected, if
+    /// the connection was rejected by the subscriber or if the publisher is closed
+    pub async fn publish(&self, message: Message) -> Result<()> {
+        let frame = self.codec.encode(&message)?;
+        let Some(frames) = self.frames.lock().expect("publisher lock poisoned").clone() else {
+            return Err(PublishError::new(self.name, "the publisher is closed", message).into());
+        };
+
+        if self.is_connected() {
+            return match frames.send(frame).await {
+                Ok(()) => Ok(()),
+                Err(_) => {
+                    Err(PublishError::new(self.name, "the connection is closed", message).into())
+                }
+            };
+        }
+
+        frames.try_send(frame).map_err(|err| {
+            let reason = match err {
+                mpsc::error::TrySendError::Full(_) => "the buffer is full",
+                mpsc::error::TrySendError::Closed(_) => "the connection is closed",
+            };
+            PublishError::new(self.name, reason, message).into()
+        })
+    }
+
+    /// Closes the publisher, the messages already published being sent before the connection
+    /// is closed.
+    pub fn close(&self) {
+        self.frames.lock().expect("publisher lock poisoned").take();
+        self.closing.lock().expect("publisher lock poisoned").take();
+    }
+
+    /// Returns whether the publisher has been closed, or its connection rejected by the
+    /// subscriber.
+    pub fn is_closed(&self) -> bool {
+        self.frames
+            .lock()
+            .expect("publisher lock poisoned")
+            .as_ref()
+            .is_none_or(mpsc::Sender::is_closed)
+    }
+
+    /// Waits until the messages published before the publisher was closed have been sent and
+    /// the connection closed, or the connection rejected by the subscriber.
+    pub async fn closed(&self) {
+        let _ = self.finished.clone().await;
+    }
+}
+
+impl<Message, C> Publisher for TcpPublisher<Message, C>
+where
+    Message: Send + 'static,
+    C: Codec<Message>,
+{
+    type Message = Message;
+
+    fn get_name(&self) -> &'static str {
+        self.name
+    }
+
+    fn publish(&self, message: Message) -> BoxFuture<'_, Result<()>> {
+        TcpPublisher::publish(self, message).boxed()
+    }
+
+    fn close(&self) {
+        TcpPublisher::close(self)
+    }
+
+    fn is_closed(&self) -> bool {
+        TcpPublisher::is_closed(self)
+    }
+
+    fn closed(&self) -> BoxFuture<'_, ()> {
+        TcpPublisher::closed(self).boxed()
+    }
+
+    /// Always fails, the subscriber of this publisher is on the other end of the connection.
+    fn get_message_stream(
+        &mut self,
+        subscriber_name: &'static str,
+    ) -> Result<Pin<Box<dyn Stream<Item = Message> + Send + Sync + 'static>>> {
+        Err(format!(
+            "{} publisher sends its messages over tcp and cannot be bound to {}",
+            self.name, subscriber_name
+        )
+        .into())
+    }
+}
+
+/// Connects to the subscriber, reconnecting with backoff, and sends the buffered frames.
+async fn run_publisher_connection(
+    name: &'static str,
+    address: SocketAddr,
+    config: TcpConfig,
+    mut frames: mpsc::Receiver<Bytes>,
+    // completes once the publisher is closed or dropped
+    mut closing: oneshot::Receiver<()>,
+    is_connected: Arc<AtomicBool>,
+    // dropped on return, completing `TcpPublisher::closed`
+    _finished: oneshot::Sender<()>,
+) {
+    let handshake = Handshake {
+        schema_id: config.schema_id.to_string(),
+        schema_version: config.schema_version,
+    };
+    let mut backoff = config.initial_backoff;
+    let mut pending_frame = None;
+
+    loop {
+        let connection = match tokio::time::timeout(
+            config.handshake_timeout,
+            open_publisher_connection(address, &handshake),
+        )
+        .await
+        .unwrap_or_else(|_| Err("the handshake timed out".into()))
+        {
+            Ok(Ok(connection)) => Some(connection),
+            Ok(Err(reason)) => {
+                log::error!("[{}] connection rejected by {}: {}", name, address, reason);
+                return;
+            }
+            Err(err) => {
+                log::warn!(
+                    "[{}] failed to connect to {} (err: {}), retrying in {:?}",
+                    name,
+                    address,
+                    err,
+                    backoff
+                );
+                None
+            }
+        };
+
+        let Some(mut connection) = connection else {
+            tokio::select! {
+                _ = tokio::time::sleep(backoff) => {}
+                _ = &mut closing => {
+                    log::warn!(
+                        "[{}] closed while disconnected from {}, dropping the buffered messages",
+                        name,
+                        address
+                    );
+                    return;
+                }
+            }
+            backoff = (backoff * 2).min(config.max_backoff);
+            continue;
+        };
+
+        log::info!("[{}] connected to {}", name, address);
+        backoff = config.initial_backoff;
+        is_connected.store(true, Ordering::SeqCst);
+
+        loop {
+            let frame = match pending_frame.take() {
+                Some(frame) => frame,
+                None => tokio::select! {
+                    frame = frames.recv() => match frame {
+                        Some(frame) => frame,
+                        // the publisher was closed or dropped
+                        None => return,
+                    },
+                    // the subscriber never sends anything once the handshake is done, so
+                    // reading only completes when the connection is closed
+                    _ = connection.next() => {
+                        log::warn!("[{}] connection to {} closed by the subscriber", name, address);
+                        break;
+                    }
+                },
+            };
+
+            if let Err(err) = connection.send(frame.clone()).await {
+                log::warn!("[{}] connection to {} lost (err: {})", name, address, err);
+                pending_frame = Some(frame);
+                break;
+            }
+        }
+
+        is_connected.store(false, Ordering::SeqCst);
+    }
+}
+
+/// Opens a connection and performs the handshake.
+///
+/// # Returns
+///
+/// The connection, the reason of the rejection if the subscriber rejected the handshake,
+/// or an error if the connection failed
+async fn open_publisher_connection(
+    address: SocketAddr,
+    handshake: &Handshake,
+) -> Result<std::result::Result<Connection, String>> {
+    let stream = TcpStream::connect(address).await?;
+    let mut connection = Framed::new(stream, LengthDelimitedCodec::new());
+
+    let handshake = JsonCodec.encode(handshake)?;
+    connection.send(handshake).await?;
+
+    let reply = connection
+        .next()
+        .await
+        .ok_or("connection closed during the handshake")??;
+
+    match JsonCodec.decode(&reply)? {
+        HandshakeReply::Accepted => Ok(Ok(connection)),
+        HandshakeReply::Rejected { reason } => Ok(Err(reason)),
+    }
+}
+
+/// A subscriber receiving messages from publishers on other hosts over TCP.
+///
+/// The subscriber listens for connections in a background task and accepts any publisher
+/// passing the handshake, so that reconnecting publishers are served transparently.
+/// Like [`async_pub_sub::SubscriberImpl`], it can also subscribe to local publishers.
+///
+/// # Type Parameters
+///
+/// * `Message` - The type of message that can be received, decoded on reception
+pub struct TcpSubscriber<Message>
+where
+    Message: Send + 'static,
+{
+    name: &'static str,
+    local_address: SocketAddr,
+    messages: SelectAll<Pin<Box<dyn Stream<Item = Message> + Send + Sync + 'static>>>,
+    /// The names of the local publishers, forgotten by the topology on drop
+    publishers: Vec<&'static str>,
+}
+
+impl<Message> TcpSubscriber<Message>
+where
+    Message: Send + Sync + 'static,
+{
+    /// Creates a new subscriber of JSON messages listening on the given address.
+    ///
+    /// Must be called from within a tokio runtime.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - A static string identifier for the subscriber
+    /// * `address` - The address to listen on
+    /// * `config` - The transport configuration
+    pub async fn bind(
+        name: &'static str,
+        address: impl ToSocketAddrs,
+        config: TcpConfig,
+    ) -> Result<Self>
+    where
+        JsonCodec: Codec<Message>,
+    {
+        Self::bind_with_codec(name, address, config, JsonCodec).await
+    }
+
+    /// Creates a new subscriber listening on the given address, the messages being decoded
+    /// with the given codec.
+    ///
+    /// Must be called from within a tokio runtime.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - A static string identifier for the subscriber
+    /// * `address` - The address to listen on
+    /// * `config` - The transport configuration
+    /// * `codec` - The codec decoding the messages
+    pub async fn bind_with_codec<C>(
+        name: &'static str,
+        address: impl ToSocketAddrs,
+        config: TcpConfig,
+        codec: C,
+    ) -> Result<Self>
+    where
+        C: Codec<Message> + Clone + 'static,
+    {
+        let listener = TcpListener::bind(address).await?;
+        let local_address = listener.local_addr()?;
+        let (sender, receiver) = mpsc::channel(config.buffer_size.max(1));
+
+        tokio::spawn(run_subscriber_listener(
+            name, listener, config, codec, sender,
+        ));
+
+        let mut messages = SelectAll::new();
+        messages.push(
+            Box::pin(stream::unfold(receiver, |mut receiver| async move {
+                receiver.recv().await.map(|message| (message, receiver))
+            })) as Pin<Box<dyn Stream<Item = Message> + Send + Sync + 'static>>,
+        );
+
+        Ok(Self {
+            name,
+            local_address,
+            messages,
+            publishers: Vec::new(),
+        })
+    }
+
+    /// Returns the address the subscriber listens on.
+    pub fn local_address(&self) -> SocketAddr {
+        self.local_address
+    }
+
+    /// Receives the next message, either from a remote or from a local publisher.
+    pub async fn receive(&mut self) -> Message {
+        self.messages.select_next_some().await
+    }
+
+    /// Receives the next message, either from a remote or from a local publisher.
+    ///
+    /// # Returns
+    ///
+    /// The next message, or `None` once the listener and the local publishers are gone
+    pub async fn next_message(&mut self) -> Option<Message> {
+        self.messages.next().await
+    }
+}
+
+impl<Message> Drop for TcpSubscriber<Message>
+where
+    Message: Send + 'static,
+{
+    fn drop(&mut self) {
+        for publisher in &self.publishers {
+            Topology::forget::<Message>(publisher, self.name);
+        }
+    }
+}
+
+impl<Message> Subscriber for TcpSubscriber<Message>
+where
+    Message: Send + Sync + 'static,
+{
+    type Message = Message;
+
+    fn get_name(&self) -> &'static str {
+        self.name
+    }
+
+    fn subscribe_to(&mut self, publisher: &mut dyn Publisher<Message = Message>) -> Result<()> {
+        let stream = publisher.get_message_stream(self.name)?;
+        self.messages.push(stream);
+        Topology::record::<Message>(publisher.get_name(), self.name);
+        self.publishers.push(publisher.get_name());
+        Ok(())
+    }
+
+    fn receive(&mut self) -> BoxFuture<'_, Message> {
+        TcpSubscriber::receive(self).boxed()
+    }
+
+    fn try_receive(&mut self) -> Option<Message> {
+        self.messages.next().now_or_never().flatten()
+    }
+
+    fn next_message(&mut self) -> BoxFuture<'_, Option<Message>> {
+        TcpSubscriber::next_message(self).boxed()
+    }
+}
+
+/// Accepts the connections of the publishers until the subscriber is dropped.
+async fn run_subscriber_listener<Message, C>(
+    name: &'static str,
+    listener: TcpListener,
+    config: TcpConfig,
+    codec: C,
+    messages: mpsc::Sender<Message>,
+) where
+    Message: Send + 'static,
+    C: Codec<Message> + Clone + 'static,
+{
+    loop {
+        let accepted = tokio::select! {
+            _ = messages.closed() => return,
+            accepted = listener.accept() => accepted,
+        };
+
+        match accepted {
+            Ok((stream, peer_address)) => {
+                tokio::spawn(run_subscriber_connection(
+                    name,
+                    stream,
+                    peer_address,
+                    config.clone(),
+                    codec.clone(),
+                    messages.clone(),
+                ));
+            }
+            Err(err) => log::warn!("[{}] failed to accept connection (err: {})", name, err),
+        }
+    }
+}
+
+/// Validates the handshake of a publisher and forwards its messages to the subscriber.
+async fn run_subscriber_connection<Message, C>(
+    name: &'static str,
+    stream: TcpStream,
+    peer_address: SocketAddr,
+    config: TcpConfig,
+    codec: C,
+    messages: mpsc::Sender<Message>,
+) where
+    Message: Send + 'static,
+    C: Codec<Message>,
+{
+    let mut connection = Framed::new(stream, LengthDelimitedCodec::new());
+
+    match tokio::time::timeout(
+        config.handshake_timeout,
+        accept_handshake(&mut connection, config.schema_id, config.schema_version),
+    )
+    .await
+    .unwrap_or_else(|_| Err("the handshake timed out".into()))
+    {
+        Ok(true) => log::info!("[{}] accepted connection from {}", name, peer_address),
+        Ok(false) => return,
+        Err(err) => {
+            log::warn!(
+                "[{}] handshake with {} failed (err: {})",
+                name,
+                peer_address,
+                err
+            );
+            return;
+        }
+    }
+
+    loop {
+        let frame = tokio::select! {
+            // the subscriber was dropped
+            _ = messages.closed() => return,
+            frame = connection.next() => frame,
+        };
+        let Some(frame) = frame else {
+            break;
+        };
+
+        let Some(message) = decode_message(name, peer_address, &codec, frame) else {
+            continue;
+        };
+
+        if messages.send(message).await.is_err() {
+            // the subscriber was dropped
+            return;
+        }
+    }
+
+    log::info!("[{}] connection from {} closed", name, peer_address);
+}
+
+/// Decodes a frame received from a publisher, logging and dropping invalid frames.
+fn decode_message<Message>(
+    name: &'static str,
+    peer_address: SocketAddr,
+    codec: &impl Codec<Message>,
+    frame: std::io::Result<bytes::BytesMut>,
+) -> Option<Message> {
+    let decoded = match frame {
+        Ok(frame) => codec.decode(&frame),
+        Err(err) => Err(err.into()),
+    };
+
+    match decoded {
+        Ok(message) => Some(message),
+        Err(err) => {
+            log::warn!(
+                "[{}] dropping invalid frame from {} (err: {})",
+                name,
+                peer_address,
+                err
+            );
+            None
+        }
+    }
+}
+
+/// Reads the handshake of a publisher and answers it.
+///
+/// # Returns
+///
+/// Whether the handshake was accepted
+async fn accept_handshake(
+    connection: &mut Connection,
+    schema_id: &str,
+    schema_version: u32,
+) -> Result<bool> {
+    let handshake = connection
+        .next()
+        .await
+        .ok_or("connection closed during the handshake")??;
+    let handshake: Handshake = JsonCodec.decode(&handshake)?;
+
+    let rejection_reason = if handshake.schema_id != schema_id {
+        Some(format!(
+            "expected messages of schema {:?}, got {:?}",
+            schema_id, handshake.schema_id
+        ))
+    } else if handshake.schema_version != schema_version {
+        Some(format!(
+            "expected schema version {}, got {}",
+            schema_version, handshake.schema_version
+        ))
+    } else {
+        None
+    };
+
+    let reply = match &rejection_reason {
+        Some(reason) => HandshakeReply::Rejected {
+            reason: reason.clone(),
+        },
+        None => HandshakeReply::Accepted,
+    };
+    let reply = JsonCodec.encode(&reply)?;
+    connection.send(reply).await?;
+
+    Ok(rejection_reason.is_none())
+}
@@ -0,0 +1,359 @@
+//! Unix domain socket transport, bridging publishers and subscribers across processes.
+//!
+//! Messages are encoded with a [`Codec`], JSON by default, and sent as length-delimited
+//! frames. A [`UdsPublisher`] is used
+//! in place of a local publisher on the sending side, and a [`UdsSubscriber`] in place of a
+//! local subscriber on the receiving side.
+
+use std::{
+    marker::PhantomData,
+    path::Path,
+    pin::Pin,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use bytes::Bytes;
+use futures::{future::BoxFuture, stream::SelectAll, FutureExt, SinkExt, Stream, StreamExt};
+use tokio::{
+    net::{UnixListener, UnixStream},
+    sync::{Mutex, Notify},
+};
+use tokio_util::codec::{FramedWrite, LengthDelimitedCodec};
+
+use async_pub_sub::{Codec, JsonCodec, PublishError, Publisher, Result, Subscriber, Topology};
+
+use super::frame::{message_stream, write_frame};
+
+/// A publisher sending its messages to a subscriber in another process over a Unix
+/// domain socket.
+///
+/// As the subscriber lives on the other end of the socket, the publisher cannot be bound
+/// to a local subscriber.
+///
+/// Closing the publisher rejects the messages published afterwards and closes the socket once
+/// the messages being published have been written, the subscriber seeing the end of the stream.
+///
+/// # Type Parameters
+///
+/// * `Message` - The type of message that can be published, encoded before being sent
+/// * `C` - The codec encoding the messages
+pub struct UdsPublisher<Message, C = JsonCodec> {
+    name: &'static str,
+    writer: Mutex<FramedWrite<UnixStream, LengthDelimitedCodec>>,
+    is_closed: AtomicBool,
+    /// Notified once the publisher is closed
+    close_requested: Notify,
+    codec: C,
+    _message: PhantomData<fn(Message)>,
+}
+
+impl<Message> UdsPublisher<Message> {
+    /// Connects a new publisher to the socket listening at the given path.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - A static string identifier for the publisher
+    /// * `path` - The path of the socket the subscriber listens on
+    pub async fn connect(name: &'static str, path: impl AsRef<Path>) -> Result<Self> {
+        let stream = UnixStream::connect(path).await?;
+        Ok(Self::from_stream(name, stream))
+    }
+
+    /// Creates a new publisher writing to an already connected socket.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - A static string identifier for the publisher
+    /// * `stream` - The connected socket
+    pub fn from_stream(name: &'static str, stream: UnixStream) -> Self {
+        Self {
+            name,
+            writer: Mutex::new(FramedWrite::new(stream, LengthDelimitedCodec::new())),
+            is_closed: AtomicBool::new(false),
+            close_requested: Notify::new(),
+            codec: JsonCodec,
+            _message: PhantomData,
+        }
+    }
+}
+
+impl<Message, C> UdsPublisher<Message, C>
+where
+    Message: Send + 'static,
+    C: Codec<Message>,
+{
+    /// Replaces the codec encoding the messages, the subscriber must decode them with the
+    /// same codec.
+    ///
+    /// # Arguments
+    ///
+    /// * `codec` - The codec encoding the messages
+    pub fn with_codec<C2>(self, codec: C2) -> UdsPublisher<Message, C2> {
+        UdsPublisher {
+            name: self.name,
+            writer: self.writer,
+            is_closed: self.is_closed,
+            close_requested: self.close_requested,
+            codec,
+            _message: PhantomData,
+        }
+    }
+
+    /// Encodes a message and sends it over the socket.
+    ///
+    /// # Arguments
+    ///
+    /// * `message` - The message to publish
+    ///
+    /// # Returns
+    ///
+    /// A [`PublishError`] carrying the message if it cannot be written, or if the publisher is
+    /// closed
+    pub async fn publish(&self, message: Message) -> Result<()> {
+        let frame = self.codec.encode(&message)?;
+        let mut writer = self.writer.lock().await;
+        if self.is_closed() {
+            return Err(PublishError::new(self.name, "the publisher is closed", message).into());
+        }
+
+        // the error is not `Send`, it must not be held across an await point
+        let result = write_frame(&mut writer, frame)
+            .await
+            .map_err(|err| err.to_string());
+        // the publisher was closed while the message was being written
+        if self.is_closed() {
+            let _ = SinkExt::<Bytes>::close(&mut *writer).await;
+        }
+        result.map_err(|err| PublishError::new(self.name, err, message).into())
+    }
+
+    /// Closes the publisher, the socket being closed once the messages being published have
+    /// been written.
+    pub fn close(&self) {
+        self.is_closed.store(true, Ordering::SeqCst);
+        self.close_requested.notify_waiters();
+        // the socket is closed right away, unless a message is being written, in which case
+        // the `publish` call writing it closes the socket
+        if let Ok(mut writer) = self.writer.try_lock() {
+            let _ = SinkExt::<Bytes>::close(&mut *writer).now_or_never();
+        }
+    }
+
+    /// Returns whether the publisher has been closed.
+    pub fn is_closed(&self) -> bool {
+        self.is_closed.load(Ordering::SeqCst)
+    }
+
+    /// Waits until the publisher has been closed, the messages published before have been
+    /// written and the socket closed.
+    pub async fn closed(&self) {
+        // created before checking the flag, so that a concurrent `close` is not missed
+        let close_requested = self.close_requested.notified();
+        if !self.is_closed() {
+            close_requested.await;
+        }
+
+        let mut writer = self.writer.lock().await;
+        if let Err(err) = SinkExt::<Bytes>::close(&mut *writer).await {
+            log::warn!("[{}] failed to close the socket (err: {})", self.name, err);
+        }
+    }
+}
+
+impl<Message, C> Publisher for UdsPublisher<Message, C>
+where
+    Message: Send + 'static,
+    C: Codec<Message>,
+{
+    type Message = Message;
+
+    fn get_name(&self) -> &'static str {
+        self.name
+    }
+
+    fn publish(&self, message: Message) -> BoxFuture<'_, Result<()>> {
+        UdsPublisher::publish(self, message).boxed()
+    }
+
+    fn close(&self) {
+        UdsPublisher::close(self)
+    }
+
+    fn is_closed(&self) -> bool {
+        UdsPublisher::is_closed(self)
+    }
+
+    fn closed(&self) -> BoxFuture<'_, ()> {
+        UdsPublisher::closed(self).boxed()
+    }
+
+    /// Always fails, the subscriber of this publisher is on the other end of the socket.
+    fn get_message_stream(
+        &mut self,
+        subscriber_name: &'static str,
+    ) -> Result<Pin<Box<dyn Stream<Item = Message> + Send + Sync + 'static>>> {
+        Err(format!(
+            "{} publisher sends its messages over a unix socket and cannot be bound to {}",
+            self.name, subscriber_name
+        )
+        .into())
+    }
+}
+
+/// A subscriber receiving messages from a publisher in another process over a Unix
+/// domain socket.
+///
+/// Like [`async_pub_sub::SubscriberImpl`], it can also subscribe to local publishers, the
+/// messages received from the socket and from the local publishers are merged.
+///
+/// # Type Parameters
+///
+/// * `Message` - The type of message that can be received, decoded on reception
+pub struct UdsSubscriber<Message>
+where
+    Message: Send + Sync + 'static,
+{
+    name: &'static str,
+    messages: SelectAll<Pin<Box<dyn Stream<Item = Message> + Send + Sync + 'static>>>,
+    /// The names of the local publishers, forgotten by the topology on drop
+    publishers: Vec<&'static str>,
+}
+
+impl<Message> UdsSubscriber<Message>
+where
+    Message: Send + Sync + 'static,
+{
+    /// Waits for a publisher to connect to the listener and creates a subscriber reading
+    /// JSON messages from that connection.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - A static string identifier for the subscriber
+    /// * `listener` - The listener the publisher connects to
+    pub async fn accept(name: &'static str, listener: &UnixListener) -> Result<Self>
+    where
+        JsonCodec: Codec<Message>,
+    {
+        Self::accept_with_codec(name, listener, JsonCodec).await
+    }
+
+    /// Waits for a publisher to connect to the listener and creates a subscriber reading
+    /// from that connection, the messages being decoded with the given codec.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - A static string identifier for the subscriber
+    /// * `listener` - The listener the publisher connects to
+    /// * `codec` - The codec decoding the messages
+    pub async fn accept_with_codec<C>(
+        name: &'static str,
+        listener: &UnixListener,
+        codec: C,
+    ) -> Result<Self>
+    where
+        C: Codec<Message> + 'static,
+    {
+        let (stream, _) = listener.accept().await?;
+        Ok(Self::from_stream_with_codec(name, stream, codec))
+    }
+
+    /// Creates a new subscriber reading JSON messages from an already connected socket.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - A static string identifier for the subscriber
+    /// * `stream` - The connected socket
+    pub fn from_stream(name: &'static str, stream: UnixStream) -> Self
+    where
+        JsonCodec: Codec<Message>,
+    {
+        Self::from_stream_with_codec(name, stream, JsonCodec)
+    }
+
+    /// Creates a new subscriber reading from an already connected socket, the messages
+    /// being decoded with the given codec.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - A static string identifier for the subscriber
+    /// * `stream` - The connected socket
+    /// * `codec` - The codec decoding the messages
+    pub fn from_stream_with_codec<C>(name: &'static str, stream: UnixStream, codec: C) -> Self
+    where
+        C: Codec<Message> + 'static,
+    {
+        let mut messages = SelectAll::new();
+        messages.push(message_stream(name, stream, codec));
+        Self {
+            name,
+            messages,
+            publishers: Vec::new(),
+        }
+    }
+
+    /// Receives the next message, either from the socket or from a local publisher.
+    ///
+    /// # Panics
+    ///
+    /// Once the peer has disconnected and the local publishers are gone, there are no more
+    /// messages to receive, use [`UdsSubscriber::next_message`] to stop at the end of the
+    /// stream instead.
+    pub async fn receive(&mut self) -> Message {
+        match self.next_message().await {
+            Some(message) => message,
+            None => panic!("[{}] all the publishers are disconnected", self.name),
+        }
+    }
+
+    /// Receives the next message, either from the socket or from a local publisher.
+    ///
+    /// # Returns
+    ///
+    /// The next message, or `None` once the peer has disconnected and the local publishers
+    /// are gone
+    pub async fn next_message(&mut self) -> Option<Message> {
+        self.messages.next().await
+    }
+}
+
+impl<Message> Drop for UdsSubscriber<Message>
+where
+    Message: Send + Sync + 'static,
+{
+    fn drop(&mut self) {
+        for publisher in &self.publishers {
+            Topology::forget::<Message>(publisher, self.name);
+        }
+    }
+}
+
+impl<Message> Subscriber for UdsSubscriber<Message>
+where
+    Message: Send + Sync + 'static,
+{
+    type Message = Message;
+
+    fn get_name(&self) -> &'static str {
+        self.name
+    }
+
+    fn subscribe_to(&mut self, publisher: &mut dyn Publisher<Message = Message>) -> Result<()> {
+        let stream = publisher.get_message_stream(self.name)?;
+        self.messages.push(stream);
+        Topology::record::<Message>(publisher.get_name(), self.name);
+        self.publishers.push(publisher.get_name());
+        Ok(())
+    }
+
+    fn receive(&mut self) -> BoxFuture<'_, Message> {
+        UdsSubscriber::receive(self).boxed()
+    }
+
+    fn try_receive(&mut self) -> Option<Message> {
+        self.messages.next().now_or_never().flatten()
+    }
+
+    fn next_message(&mut self) -> BoxFuture<'_, Option<Message>> {
+        UdsSubscriber::next_message(self).boxed()
+    }
+}
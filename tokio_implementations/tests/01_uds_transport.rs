@@ -0,0 +1,229 @@
+#![cfg(feature = "uds")]
+
+use async_pub_sub::{
+    bytes::Bytes, macros::routes, Codec, LoggingSubscriberLayer, PublishError, Publisher,
+    PublisherImpl, Result, Subscriber, SubscriberBuilder,
+};
+use serde::{Deserialize, Serialize};
+use tokio::net::{UnixListener, UnixStream};
+use tokio_implementations::transport::uds::{UdsPublisher, UdsSubscriber};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Measure {
+    sensor: String,
+    value: f64,
+}
+
+impl std::fmt::Display for Measure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.sensor, self.value)
+    }
+}
+
+/// A codec encoding the measures as `sensor=value` text.
+#[derive(Clone, Copy)]
+struct TextCodec;
+
+impl Codec<Measure> for TextCodec {
+    fn encode(&self, message: &Measure) -> Result<Bytes> {
+        Ok(format!("{}={}", message.sensor, message.value).into())
+    }
+
+    fn decode(&self, bytes: &[u8]) -> Result<Measure> {
+        let text = std::str::from_utf8(bytes)?;
+        let (sensor, value) = text.split_once('=').ok_or("missing '='")?;
+        Ok(Measure {
+            sensor: sensor.to_string(),
+            value: value.parse()?,
+        })
+    }
+}
+
+fn measure(value: f64) -> Measure {
+    Measure {
+        sensor: "temperature".to_string(),
+        value,
+    }
+}
+
+#[tokio::test]
+async fn test_uds_pub_sub() -> Result<()> {
+    // -- Setup & Fixtures
+    let (publisher_stream, subscriber_stream) = UnixStream::pair()?;
+    let publisher = UdsPublisher::from_stream("publisher", publisher_stream);
+    let mut subscriber = UdsSubscriber::<Measure>::from_stream("subscriber", subscriber_stream);
+
+    // -- Exec
+    publisher.publish(measure(1.0)).await?;
+    publisher.publish(measure(2.0)).await?;
+
+    // -- Check
+    assert_eq!(subscriber.receive().await, measure(1.0));
+    assert_eq!(subscriber.receive().await, measure(2.0));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_uds_pub_sub_with_codec() -> Result<()> {
+    // -- Setup & Fixtures
+    let (publisher_stream, subscriber_stream) = UnixStream::pair()?;
+    let publisher = UdsPublisher::from_stream("publisher", publisher_stream).with_codec(TextCodec);
+    let mut subscriber =
+        UdsSubscriber::from_stream_with_codec("subscriber", subscriber_stream, TextCodec);
+
+    // -- Exec
+    publisher.publish(measure(1.5)).await?;
+
+    // -- Check
+    assert_eq!(subscriber.receive().await, measure(1.5));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_uds_listener() -> Result<()> {
+    // -- Setup & Fixtures
+    let path = std::env::temp_dir().join(format!("uds_listener_{}.sock", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let listener = UnixListener::bind(&path)?;
+
+    let (subscriber, publisher) = tokio::join!(
+        UdsSubscriber::<Measure>::accept("subscriber", &listener),
+        UdsPublisher::<Measure>::connect("publisher", &path),
+    );
+    let (mut subscriber, publisher) = (subscriber?, publisher?);
+
+    // -- Exec
+    Publisher::publish(&publisher, measure(3.0)).await?;
+
+    // -- Check
+    assert_eq!(Subscriber::receive(&mut subscriber).await, measure(3.0));
+
+    std::fs::remove_file(&path)?;
+    Ok(())
+}
+
+#[test_log::test(tokio::test)]
+async fn test_uds_routes_and_layers() -> Result<()> {
+    // -- Setup & Fixtures
+    let (publisher_stream, subscriber_stream) = UnixStream::pair()?;
+    let remote_publisher = UdsPublisher::from_stream("remote_publisher", publisher_stream);
+    let mut local_publisher = PublisherImpl::new("local_publisher", 1);
+    let mut subscriber = SubscriberBuilder::new()
+        .layer(LoggingSubscriberLayer)
+        .subscriber(UdsSubscriber::from_stream("subscriber", subscriber_stream));
+
+    routes! {
+        local_publisher -> subscriber,
+    }?;
+
+    // -- Exec
+    remote_publisher.publish(measure(4.0)).await?;
+    let remote_message = subscriber.receive().await;
+    local_publisher.publish(measure(5.0)).await?;
+    let local_message = subscriber.receive().await;
+
+    // -- Check
+    assert_eq!(remote_message, measure(4.0));
+    assert_eq!(local_message, measure(5.0));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_uds_publisher_cannot_be_bound_locally() -> Result<()> {
+    let (publisher_stream, _subscriber_stream) = UnixStream::pair()?;
+    let mut publisher = UdsPublisher::<Measure>::from_stream("publisher", publisher_stream);
+    let mut subscriber = async_pub_sub::SubscriberImpl::new("subscriber");
+
+    assert!(subscriber.subscribe_to(&mut publisher).is_err());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_uds_subscriber_sees_disconnection() -> Result<()> {
+    // -- Setup & Fixtures
+    let (publisher_stream, subscriber_stream) = UnixStream::pair()?;
+    let publisher = UdsPublisher::from_stream("publisher", publisher_stream);
+    let mut subscriber = UdsSubscriber::<Measure>::from_stream("subscriber", subscriber_stream);
+
+    // -- Exec
+    publisher.publish(measure(6.0)).await?;
+    drop(publisher);
+
+    // -- Check
+    assert_eq!(subscriber.next_message().await, Some(measure(6.0)));
+    assert_eq!(subscriber.next_message().await, None);
+    let received = tokio::spawn(async move { subscriber.receive().await }).await;
+    assert!(received
+        .expect_err("receive should not wait forever")
+        .is_panic());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_uds_subscriber_task_ends_on_disconnection() -> Result<()> {
+    // -- Setup & Fixtures
+    let path = std::env::temp_dir().join(format!("uds_disconnect_{}.sock", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let listener = UnixListener::bind(&path)?;
+
+    let (subscriber, publisher) = tokio::join!(
+        UdsSubscriber::<Measure>::accept("subscriber", &listener),
+        UdsPublisher::<Measure>::connect("publisher", &path),
+    );
+    let (mut subscriber, publisher) = (subscriber?, publisher?);
+    let received = tokio::spawn(async move {
+        let mut received = Vec::new();
+        while let Some(message) = Subscriber::next_message(&mut subscriber).await {
+            received.push(message);
+        }
+        received
+    });
+
+    // -- Exec
+    publisher.publish(measure(1.0)).await?;
+    publisher.publish(measure(2.0)).await?;
+    drop(publisher);
+
+    // -- Check
+    let received = tokio::time::timeout(std::time::Duration::from_secs(1), received).await??;
+    assert_eq!(received, vec![measure(1.0), measure(2.0)]);
+
+    std::fs::remove_file(&path)?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_uds_publisher_close() -> Result<()> {
+    // -- Setup & Fixtures
+    let (publisher_stream, subscriber_stream) = UnixStream::pair()?;
+    let publisher = UdsPublisher::from_stream("publisher", publisher_stream);
+    let mut subscriber = UdsSubscriber::<Measure>::from_stream("subscriber", subscriber_stream);
+
+    publisher.publish(measure(1.0)).await?;
+
+    // -- Exec
+    Publisher::close(&publisher);
+    let rejected = publisher.publish(measure(2.0)).await;
+    Publisher::closed(&publisher).await;
+
+    // -- Check
+    assert!(Publisher::is_closed(&publisher));
+    let err = rejected.expect_err("a closed publisher should reject messages");
+    let err = err
+        .downcast::<PublishError<Measure>>()
+        .expect("the error should carry the rejected message");
+    assert_eq!(err.into_message(), measure(2.0));
+
+    assert_eq!(
+        Subscriber::next_message(&mut subscriber).await,
+        Some(measure(1.0))
+    );
+    assert_eq!(Subscriber::next_message(&mut subscriber).await, None);
+
+    Ok(())
+}
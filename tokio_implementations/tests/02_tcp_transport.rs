@@ -0,0 +1,337 @@
+#![cfg(feature = "tcp")]
+
+use std::time::Duration;
+
+use async_pub_sub::{
+    bytes::Bytes, Codec, PublishError, Publisher, PublisherImpl, Result, Subscriber,
+};
+use serde::{Deserialize, Serialize};
+use tokio_implementations::transport::tcp::{TcpConfig, TcpPublisher, TcpSubscriber};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Measure {
+    sensor: String,
+    value: f64,
+}
+
+/// A codec encoding the measures as `sensor=value` text.
+#[derive(Clone, Copy)]
+struct TextCodec;
+
+impl Codec<Measure> for TextCodec {
+    fn encode(&self, message: &Measure) -> Result<Bytes> {
+        Ok(format!("{}={}", message.sensor, message.value).into())
+    }
+
+    fn decode(&self, bytes: &[u8]) -> Result<Measure> {
+        let text = std::str::from_utf8(bytes)?;
+        let (sensor, value) = text.split_once('=').ok_or("missing '='")?;
+        Ok(Measure {
+            sensor: sensor.to_string(),
+            value: value.parse()?,
+        })
+    }
+}
+
+fn measure(value: f64) -> Measure {
+    Measure {
+        sensor: "temperature".to_string(),
+        value,
+    }
+}
+
+fn config() -> TcpConfig {
+    TcpConfig {
+        initial_backoff: Duration::from_millis(10),
+        max_backoff: Duration::from_millis(50),
+        ..TcpConfig::new("tests.Measure")
+    }
+}
+
+#[test_log::test(tokio::test)]
+async fn test_tcp_pub_sub() -> Result<()> {
+    // -- Setup & Fixtures
+    let mut subscriber =
+        TcpSubscriber::<Measure>::bind("subscriber", "127.0.0.1:0", config()).await?;
+    let publisher = TcpPublisher::connect("publisher", subscriber.local_address(), config());
+
+    // -- Exec
+    publisher.publish(measure(1.0)).await?;
+    publisher.publish(measure(2.0)).await?;
+
+    // -- Check
+    assert_eq!(subscriber.receive().await, measure(1.0));
+    assert_eq!(subscriber.receive().await, measure(2.0));
+
+    Ok(())
+}
+
+#[test_log::test(tokio::test)]
+async fn test_tcp_pub_sub_with_codec() -> Result<()> {
+    // -- Setup & Fixtures
+    let mut subscriber =
+        TcpSubscriber::bind_with_codec("subscriber", "127.0.0.1:0", config(), TextCodec).await?;
+    let publisher = TcpPublisher::connect("publisher", subscriber.local_address(), config())
+        .with_codec(TextCodec);
+
+    // -- Exec
+    publisher.publish(measure(1.5)).await?;
+
+    // -- Check
+    assert_eq!(subscriber.receive().await, measure(1.5));
+
+    Ok(())
+}
+
+#[test_log::test(tokio::test)]
+async fn test_tcp_publisher_buffers_until_subscriber_is_up() -> Result<()> {
+    // -- Setup & Fixtures
+    let address = std::net::TcpListener::bind("127.0.0.1:0")?.local_addr()?;
+    let publisher = TcpPublisher::connect("publisher", address, config());
+
+    // -- Exec
+    publisher.publish(measure(1.0)).await?;
+    publisher.publish(measure(2.0)).await?;
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    let mut subscriber = TcpSubscriber::<Measure>::bind("subscriber", address, config()).await?;
+
+    // -- Check
+    assert_eq!(subscriber.receive().await, measure(1.0));
+    assert_eq!(subscriber.receive().await, measure(2.0));
+
+    Ok(())
+}
+
+#[test_log::test(tokio::test)]
+async fn test_tcp_publisher_buffer_full() -> Result<()> {
+    // -- Setup & Fixtures
+    let address = std::net::TcpListener::bind("127.0.0.1:0")?.local_addr()?;
+    let config = TcpConfig {
+        buffer_size: 1,
+        ..config()
+    };
+    let publisher = TcpPublisher::connect("publisher", address, config);
+
+    // -- Exec
+    publisher.publish(measure(1.0)).await?;
+    let result = publisher.publish(measure(2.0)).await;
+
+    // -- Check
+    assert!(result.is_err());
+
+    Ok(())
+}
+
+#[test_log::test(tokio::test)]
+async fn test_tcp_publisher_waits_while_connected() -> Result<()> {
+    // -- Setup & Fixtures
+    let config = TcpConfig {
+        buffer_size: 1,
+        ..config()
+    };
+    let mut subscriber =
+        TcpSubscriber::<Measure>::bind("subscriber", "127.0.0.1:0", config.clone()).await?;
+    let publisher = TcpPublisher::connect("publisher", subscriber.local_address(), config);
+    publisher.publish(measure(0.0)).await?;
+    assert_eq!(subscriber.receive().await, measure(0.0));
+
+    // -- Exec
+    // the buffer only holds one message, publishing waits for the connection
+    for value in 1..=100 {
+        publisher.publish(measure(value as f64)).await?;
+    }
+
+    // -- Check
+    for value in 1..=100 {
+        assert_eq!(subscriber.receive().await, measure(value as f64));
+    }
+
+    Ok(())
+}
+
+#[test_log::test(tokio::test)]
+async fn test_tcp_schema_version_mismatch() -> Result<()> {
+    // -- Setup & Fixtures
+    let subscriber_config = TcpConfig {
+        schema_version: 2,
+        ..config()
+    };
+    let mut subscriber =
+        TcpSubscriber::<Measure>::bind("subscriber", "127.0.0.1:0", subscriber_config).await?;
+    let publisher = TcpPublisher::connect("publisher", subscriber.local_address(), config());
+
+    // -- Exec
+    publisher.publish(measure(1.0)).await?;
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    // -- Check
+    assert!(publisher.publish(measure(2.0)).await.is_err());
+    let received = tokio::time::timeout(Duration::from_millis(50), subscriber.receive()).await;
+    assert!(received.is_err());
+
+    Ok(())
+}
+
+#[test_log::test(tokio::test)]
+async fn test_tcp_schema_id_mismatch() -> Result<()> {
+    // -- Setup & Fixtures
+    let subscriber_config = TcpConfig {
+        schema_id: "tests.OtherMeasure",
+        ..config()
+    };
+    let mut subscriber =
+        TcpSubscriber::<Measure>::bind("subscriber", "127.0.0.1:0", subscriber_config).await?;
+    let publisher = TcpPublisher::connect("publisher", subscriber.local_address(), config());
+
+    // -- Exec
+    publisher.publish(measure(1.0)).await?;
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    // -- Check
+    assert!(publisher.publish(measure(2.0)).await.is_err());
+    let received = tokio::time::timeout(Duration::from_millis(50), subscriber.receive()).await;
+    assert!(received.is_err());
+
+    Ok(())
+}
+
+#[test_log::test(tokio::test)]
+async fn test_tcp_publisher_reconnects_after_disconnection() -> Result<()> {
+    // -- Setup & Fixtures
+    let mut subscriber =
+        TcpSubscriber::<Measure>::bind("subscriber", "127.0.0.1:0", config()).await?;
+    let address = subscriber.local_address();
+    let publisher = TcpPublisher::connect("publisher", address, config());
+
+    publisher.publish(measure(1.0)).await?;
+    assert_eq!(subscriber.receive().await, measure(1.0));
+    assert!(publisher.is_connected());
+
+    // -- Exec
+    // the subscriber goes down mid-stream, the messages published meanwhile are buffered
+    drop(subscriber);
+    wait_until(|| !publisher.is_connected()).await;
+    publisher.publish(measure(2.0)).await?;
+    publisher.publish(measure(3.0)).await?;
+
+    let mut subscriber = rebind(address).await?;
+    wait_until(|| publisher.is_connected()).await;
+    publisher.publish(measure(4.0)).await?;
+
+    // -- Check
+    assert_eq!(subscriber.receive().await, measure(2.0));
+    assert_eq!(subscriber.receive().await, measure(3.0));
+    assert_eq!(subscriber.receive().await, measure(4.0));
+
+    Ok(())
+}
+
+/// Binds a new subscriber on the address of a dropped one, once its listener is closed.
+async fn rebind(address: std::net::SocketAddr) -> Result<TcpSubscriber<Measure>> {
+    for _ in 0..100 {
+        if let Ok(subscriber) = TcpSubscriber::bind("subscriber", address, config()).await {
+            return Ok(subscriber);
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    Err(format!("{} is still in use", address).into())
+}
+
+async fn wait_until(condition: impl Fn() -> bool) {
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while !condition() {
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+    })
+    .await
+    .expect("the condition should be met");
+}
+
+#[test_log::test(tokio::test)]
+async fn test_tcp_subscriber_with_local_publisher() -> Result<()> {
+    // -- Setup & Fixtures
+    let mut subscriber =
+        TcpSubscriber::<Measure>::bind("subscriber", "127.0.0.1:0", config()).await?;
+    let mut local_publisher = PublisherImpl::new("local_publisher", 1);
+    subscriber.subscribe_to(&mut local_publisher)?;
+
+    // -- Exec
+    Publisher::publish(&local_publisher, measure(3.0)).await?;
+
+    // -- Check
+    assert_eq!(Subscriber::receive(&mut subscriber).await, measure(3.0));
+
+    Ok(())
+}
+
+#[test_log::test(tokio::test)]
+async fn test_tcp_publisher_close_sends_the_buffered_messages() -> Result<()> {
+    // -- Setup & Fixtures
+    let mut subscriber =
+        TcpSubscriber::<Measure>::bind("subscriber", "127.0.0.1:0", config()).await?;
+    let publisher = TcpPublisher::connect("publisher", subscriber.local_address(), config());
+
+    publisher.publish(measure(1.0)).await?;
+    publisher.publish(measure(2.0)).await?;
+
+    // -- Exec
+    Publisher::close(&publisher);
+    let rejected = publisher.publish(measure(3.0)).await;
+    Publisher::closed(&publisher).await;
+
+    // -- Check
+    assert!(Publisher::is_closed(&publisher));
+    let err = rejected.expect_err("a closed publisher should reject messages");
+    let err = err
+        .downcast::<PublishError<Measure>>()
+        .expect("the error should carry the rejected message");
+    assert_eq!(err.into_message(), measure(3.0));
+
+    assert_eq!(subscriber.receive().await, measure(1.0));
+    assert_eq!(subscriber.receive().await, measure(2.0));
+
+    Ok(())
+}
+
+#[test_log::test(tokio::test)]
+async fn test_tcp_publisher_close_stops_reconnecting() -> Result<()> {
+    // -- Setup & Fixtures
+    // nothing listens on the address, the publisher keeps reconnecting
+    let address = tokio::net::TcpListener::bind("127.0.0.1:0")
+        .await?
+        .local_addr()?;
+    let publisher = TcpPublisher::connect("publisher", address, config());
+    publisher.publish(measure(1.0)).await?;
+
+    // -- Exec
+    Publisher::close(&publisher);
+
+    // -- Check
+    tokio::time::timeout(Duration::from_secs(1), Publisher::closed(&publisher)).await?;
+    assert!(Publisher::is_closed(&publisher));
+
+    Ok(())
+}
+
+#[test_log::test(tokio::test)]
+async fn test_tcp_handshake_timeout() -> Result<()> {
+    use tokio::io::AsyncReadExt;
+
+    // -- Setup & Fixtures
+    let config = TcpConfig {
+        handshake_timeout: Duration::from_millis(50),
+        ..config()
+    };
+    let subscriber = TcpSubscriber::<Measure>::bind("subscriber", "127.0.0.1:0", config).await?;
+
+    // -- Exec
+    // the peer connects but never sends its handshake
+    let mut stream = tokio::net::TcpStream::connect(subscriber.local_address()).await?;
+
+    // -- Check
+    // the subscriber closes the connection once the handshake timed out
+    let read = tokio::time::timeout(Duration::from_secs(1), stream.read(&mut [0; 16])).await??;
+    assert_eq!(read, 0);
+
+    Ok(())
+}
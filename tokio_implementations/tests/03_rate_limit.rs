@@ -0,0 +1,35 @@
+use std::time::Duration;
+
+use async_pub_sub::{
+    Publisher, PublisherBuilder, RateLimitPolicy, RateLimitPublisherLayer, Result, SubscriberImpl,
+};
+use tokio_implementations::{publisher::mpsc::MpscPublisher, timer::TokioTimer};
+
+#[tokio::test(start_paused = true)]
+async fn test_rate_limited_mpsc_publisher() -> Result<()> {
+    // -- Setup & Fixtures
+    let mut publisher = PublisherBuilder::new()
+        .layer(RateLimitPublisherLayer::new(
+            5.0,
+            1,
+            RateLimitPolicy::Delay,
+            TokioTimer,
+        ))
+        .publisher(MpscPublisher::new("publisher", 10));
+    let mut subscriber = SubscriberImpl::new("subscriber");
+    subscriber.subscribe_to(&mut publisher)?;
+    let start = tokio::time::Instant::now();
+
+    // -- Exec
+    for value in 0..3 {
+        publisher.publish(value).await?;
+    }
+
+    // -- Check
+    assert_eq!(start.elapsed(), Duration::from_millis(400));
+    for value in 0..3 {
+        assert_eq!(subscriber.receive().await, value);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,56 @@
+use std::time::Duration;
+
+use async_pub_sub::{
+    DurationStats, MetricsPublisherLayer, MetricsRegistry, MetricsSubscriberLayer, Publisher,
+    PublisherBuilder, Result, Subscriber, SubscriberBuilder, SubscriberImpl,
+};
+use tokio_implementations::{publisher::mpsc::MpscPublisher, timer::TokioTimer};
+
+#[tokio::test(start_paused = true)]
+async fn test_mpsc_publisher_metrics() -> Result<()> {
+    // -- Setup & Fixtures
+    let registry = MetricsRegistry::new(TokioTimer);
+    let mut publisher = PublisherBuilder::new()
+        .layer(MetricsPublisherLayer::new(registry.clone()))
+        .publisher(MpscPublisher::new("publisher", 1));
+    let mut subscriber = SubscriberBuilder::new()
+        .layer(MetricsSubscriberLayer::new(registry.clone()))
+        .subscriber(SubscriberImpl::new("subscriber"));
+    subscriber.subscribe_to(&mut publisher)?;
+
+    // -- Exec
+    // the second message waits for the first one to be received, outside of the queue
+    let publication = tokio::spawn(async move {
+        for value in 0..2 {
+            publisher.publish(value).await.unwrap();
+        }
+    });
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    assert_eq!(
+        registry
+            .channel("publisher", "subscriber")
+            .unwrap()
+            .queue_depth,
+        1
+    );
+    assert_eq!(subscriber.receive().await, 0);
+    assert_eq!(subscriber.receive().await, 1);
+    publication.await?;
+
+    // -- Check
+    let metrics = registry.channel("publisher", "subscriber").unwrap();
+    assert_eq!(metrics.published, 2);
+    assert_eq!(metrics.received, 2);
+    assert_eq!(metrics.queue_depth, 0);
+    assert_eq!(
+        metrics.publish_wait,
+        DurationStats {
+            count: 2,
+            total: Duration::from_millis(50),
+            max: Duration::from_millis(50),
+        }
+    );
+    assert_eq!(metrics.latency.max, Duration::from_millis(50));
+
+    Ok(())
+}
@@ -0,0 +1,75 @@
+use async_pub_sub::{futures::FutureExt, PublishError, Publisher, Result, SubscriberImpl};
+use tokio_implementations::publisher::mpsc::MpscPublisher;
+
+#[tokio::test]
+async fn test_mpsc_close_drains_queued_messages_in_order() -> Result<()> {
+    // -- Setup & Fixtures
+    let mut publisher = MpscPublisher::new("publisher", 10);
+    let mut subscriber = SubscriberImpl::new("subscriber");
+    subscriber.subscribe_to(&mut publisher)?;
+
+    for message in 1..=3 {
+        publisher.publish(message).await?;
+    }
+
+    // -- Exec
+    Publisher::close(&publisher);
+    let rejected = publisher.publish(4).await;
+
+    // -- Check
+    assert!(Publisher::is_closed(&publisher));
+    let err = rejected.expect_err("a closed publisher should reject messages");
+    let err = err
+        .downcast::<PublishError<i32>>()
+        .expect("the error should carry the rejected message");
+    assert_eq!(err.into_message(), 4);
+
+    assert_eq!(subscriber.next_message().await, Some(1));
+    assert_eq!(subscriber.next_message().await, Some(2));
+    assert_eq!(subscriber.next_message().await, Some(3));
+    assert_eq!(subscriber.next_message().await, None);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_mpsc_closed_waits_for_the_queue_to_be_drained() -> Result<()> {
+    // -- Setup & Fixtures
+    let mut publisher = MpscPublisher::new("publisher", 10);
+    let mut subscriber = SubscriberImpl::new("subscriber");
+    subscriber.subscribe_to(&mut publisher)?;
+
+    publisher.publish(1).await?;
+    publisher.publish(2).await?;
+
+    // -- Exec & Check
+    Publisher::close(&publisher);
+    assert!(Publisher::closed(&publisher).now_or_never().is_none());
+
+    assert_eq!(subscriber.next_message().await, Some(1));
+    assert!(Publisher::closed(&publisher).now_or_never().is_none());
+
+    assert_eq!(subscriber.next_message().await, Some(2));
+    assert_eq!(subscriber.next_message().await, None);
+    assert!(Publisher::closed(&publisher).now_or_never().is_some());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_mpsc_closed_completes_when_the_subscriber_is_dropped() -> Result<()> {
+    // -- Setup & Fixtures
+    let mut publisher = MpscPublisher::new("publisher", 10);
+    let mut subscriber = SubscriberImpl::new("subscriber");
+    subscriber.subscribe_to(&mut publisher)?;
+    publisher.publish(1).await?;
+
+    // -- Exec
+    drop(subscriber);
+
+    // -- Check
+    assert!(Publisher::is_closed(&publisher));
+    assert!(Publisher::closed(&publisher).now_or_never().is_some());
+
+    Ok(())
+}